@@ -1,31 +1,88 @@
-use crate::error::ClientError;
+use crate::error::{ClientError, RequestError, RequestErrorDetails};
+use crate::extensions::gzip::gzip;
 use crate::extensions::reqwest::{to_header_map, ResponseExt};
+use crate::pool::PoolConfig;
 use crate::Headers;
 use algonaut_core::{Address, Round};
 use algonaut_model::algod::v2::{
     Account, Application, Block, Catchup, CompiledTealWithHash, DryrunRequest, DryrunResponse,
-    GenesisBlock, KeyRegistration, NodeStatus, PendingTransaction, PendingTransactions, Supply,
-    TransactionParams, TransactionResponse, Version,
+    GenesisBlock, KeyRegistration, LedgerStateDelta, NodeStatus, ParticipationKey,
+    PendingTransaction, PendingTransactions, StateProof, Supply, TransactionParams,
+    TransactionResponse, Version,
 };
 use reqwest::header::HeaderMap;
-use reqwest::Url;
+use reqwest::{Response, StatusCode, Url};
+use std::time::Duration;
 
 /// Client for interacting with the Algorand protocol daemon
 pub struct Client {
     url: String,
     headers: HeaderMap,
     http_client: reqwest::Client,
+    gzip_request: bool,
+    long_poll_retries: u32,
 }
 
 impl Client {
     pub fn new(url: &str, headers: Headers) -> Result<Client, ClientError> {
+        Client::with_pool_config(url, headers, &PoolConfig::default())
+    }
+
+    /// Creates a client whose connection pool uses the given settings.
+    pub fn with_pool_config(
+        url: &str,
+        headers: Headers,
+        pool: &PoolConfig,
+    ) -> Result<Client, ClientError> {
         Ok(Client {
             url: Url::parse(url)?.as_ref().into(),
             headers: to_header_map(headers)?,
-            http_client: reqwest::Client::new(),
+            http_client: pool.http_client()?,
+            gzip_request: false,
+            long_poll_retries: 3,
         })
     }
 
+    /// Whether to gzip the bodies of the requests that upload data (transactions, programs,
+    /// dryrun requests...). Requests are re-sent uncompressed if the node doesn't support it.
+    pub fn gzip_request(mut self, gzip_request: bool) -> Self {
+        self.gzip_request = gzip_request;
+        self
+    }
+
+    /// How many times [status_after_round](Self::status_after_round) re-issues the wait when the
+    /// connection drops before the round arrives. Defaults to 3.
+    pub fn long_poll_retries(mut self, long_poll_retries: u32) -> Self {
+        self.long_poll_retries = long_poll_retries;
+        self
+    }
+
+    /// Posts `body`, gzipped if enabled.
+    async fn post_body(
+        &self,
+        path: &str,
+        content_type: &'static str,
+        body: Vec<u8>,
+    ) -> Result<Response, ClientError> {
+        let post = |body: Vec<u8>| {
+            self.http_client
+                .post(&format!("{}{}", self.url, path))
+                .headers(self.headers.clone())
+                .header("Content-Type", content_type)
+                .body(body)
+        };
+        if self.gzip_request {
+            let response = post(gzip(&body))
+                .header("Content-Encoding", "gzip")
+                .send()
+                .await?;
+            if response.status() != StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                return Ok(response);
+            }
+        }
+        Ok(post(body).send().await?)
+    }
+
     pub async fn genesis(&self) -> Result<GenesisBlock, ClientError> {
         let response = self
             .http_client
@@ -151,6 +208,51 @@ impl Client {
         Ok(response)
     }
 
+    /// The msgpack-encoded block, read as it arrives.
+    pub async fn block_msgpack(&self, round: Round) -> Result<BodyChunks, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/blocks/{}", self.url, round))
+            .headers(self.headers.clone())
+            .query(&[("format", "msgpack")])
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?;
+
+        Ok(BodyChunks(Some(response)))
+    }
+
+    pub async fn state_proof(&self, round: Round) -> Result<StateProof, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/stateproofs/{}", self.url, round))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn ledger_state_delta(&self, round: Round) -> Result<LedgerStateDelta, ClientError> {
+        let response = self
+            .http_client
+            .get(format!("{}v2/deltas/{}", self.url, round))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     pub async fn start_catchup(&self, catchpoint: &str) -> Result<Catchup, ClientError> {
         let response = self
             .http_client
@@ -220,6 +322,67 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn participation_keys(&self) -> Result<Vec<ParticipationKey>, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/participation", self.url))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn participation_key(&self, id: &str) -> Result<ParticipationKey, ClientError> {
+        let response = self
+            .http_client
+            .get(&format!("{}v2/participation/{}", self.url, id))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
+    pub async fn append_participation_key_state_proof(
+        &self,
+        id: &str,
+        keys: &[u8],
+    ) -> Result<(), ClientError> {
+        let _ = self
+            .post_body(
+                &format!("v2/participation/{}", id),
+                "application/msgpack",
+                keys.to_vec(),
+            )
+            .await?
+            .http_error_for_status()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_participation_key(&self, id: &str) -> Result<(), ClientError> {
+        let _ = self
+            .http_client
+            .delete(&format!("{}v2/participation/{}", self.url, id))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn shutdown(&self, timeout: usize) -> Result<(), ClientError> {
         self.http_client
             .post(&format!("{}v2/shutdown", self.url))
@@ -250,7 +413,36 @@ impl Client {
         Ok(response)
     }
 
+    /// Waits for the round, reconnecting up to [long_poll_retries](Self::long_poll_retries) times
+    /// if the connection drops.
     pub async fn status_after_round(&self, round: Round) -> Result<NodeStatus, ClientError> {
+        let mut retries = 0;
+        let response = loop {
+            let res = self
+                .http_client
+                .get(format!(
+                    "{}v2/status/wait-for-block-after/{}",
+                    self.url, round.0
+                ))
+                .headers(self.headers.clone())
+                .send()
+                .await;
+            match res {
+                Err(e) if is_connection_error(&e) && retries < self.long_poll_retries => {
+                    retries += 1
+                }
+                res => break res?,
+            }
+        };
+
+        Ok(response.http_error_for_status().await?.json().await?)
+    }
+
+    pub async fn status_after_round_timeout(
+        &self,
+        round: Round,
+        timeout: Duration,
+    ) -> Result<NodeStatus, ClientError> {
         let response = self
             .http_client
             .get(&format!(
@@ -258,6 +450,7 @@ impl Client {
                 self.url, round.0
             ))
             .headers(self.headers.clone())
+            .timeout(timeout)
             .send()
             .await?
             .http_error_for_status()
@@ -270,12 +463,7 @@ impl Client {
 
     pub async fn compile_teal(&self, teal: Vec<u8>) -> Result<CompiledTealWithHash, ClientError> {
         let response = self
-            .http_client
-            .post(&format!("{}v2/teal/compile", self.url))
-            .headers(self.headers.clone())
-            .header("Content-Type", "application/x-binary")
-            .body(teal)
-            .send()
+            .post_body("v2/teal/compile", "application/x-binary", teal)
             .await?
             .http_error_for_status()
             .await?
@@ -286,13 +474,16 @@ impl Client {
     }
 
     pub async fn dryrun_teal(&self, req: &DryrunRequest) -> Result<DryrunResponse, ClientError> {
+        let body = serde_json::to_vec(req).map_err(|e| {
+            RequestError::new(
+                None,
+                RequestErrorDetails::Client {
+                    description: e.to_string(),
+                },
+            )
+        })?;
         let response = self
-            .http_client
-            .post(&format!("{}v2/teal/dryrun", self.url))
-            .headers(self.headers.clone())
-            .header("Content-Type", "application/json")
-            .json(req)
-            .send()
+            .post_body("v2/teal/dryrun", "application/json", body)
             .await?
             .http_error_for_status()
             .await?
@@ -307,12 +498,7 @@ impl Client {
         rawtxn: &[u8],
     ) -> Result<TransactionResponse, ClientError> {
         let response = self
-            .http_client
-            .post(&format!("{}v2/transactions", self.url))
-            .headers(self.headers.clone())
-            .header("Content-Type", "application/x-binary")
-            .body(rawtxn.to_vec())
-            .send()
+            .post_body("v2/transactions", "application/x-binary", rawtxn.to_vec())
             .await?
             .http_error_for_status()
             .await?
@@ -385,4 +571,173 @@ impl Client {
 
         Ok(response)
     }
+
+    /// Gets an arbitrary path (e.g. "/v2/status"), returning the raw response body.
+    pub async fn get_raw(&self, path: &str) -> Result<Vec<u8>, ClientError> {
+        let response = self
+            .http_client
+            .get(format!("{}{}", self.url, path.trim_start_matches('/')))
+            .headers(self.headers.clone())
+            .send()
+            .await?
+            .http_error_for_status()
+            .await?
+            .bytes()
+            .await?;
+
+        Ok(response.to_vec())
+    }
+
+    /// Posts `body` to an arbitrary path, returning the raw response body.
+    pub async fn post_raw(&self, path: &str, body: Vec<u8>) -> Result<Vec<u8>, ClientError> {
+        let response = self
+            .post_body(
+                path.trim_start_matches('/'),
+                "application/octet-stream",
+                body,
+            )
+            .await?
+            .http_error_for_status()
+            .await?
+            .bytes()
+            .await?;
+
+        Ok(response.to_vec())
+    }
+}
+
+/// The connection failed or dropped before getting a response, as opposed to e.g. timeouts.
+/// The body of a response, read in chunks as they arrive.
+#[derive(Debug)]
+pub struct BodyChunks(Option<Response>);
+
+impl BodyChunks {
+    /// The next chunk of the body, `None` once it has been read entirely.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, ClientError> {
+        match self.0.as_mut() {
+            Some(response) => Ok(response.chunk().await?.map(|chunk| chunk.to_vec())),
+            None => Ok(None),
+        }
+    }
+
+    /// The next chunk of the body, `None` once it has been read entirely.
+    // The WASM response can only be read at once.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, ClientError> {
+        match self.0.take() {
+            Some(response) => Ok(Some(response.bytes().await?.to_vec())),
+            None => Ok(None),
+        }
+    }
+}
+
+fn is_connection_error(error: &reqwest::Error) -> bool {
+    (error.is_connect() || error.is_request()) && !error.is_timeout()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RequestErrorDetails;
+    use crate::test_utils::{status_response, MockServer};
+    use std::io::Read;
+
+    #[tokio::test]
+    async fn test_status_after_round_retries_dropped_connection() {
+        let server = MockServer::start_after_drops(1, status_response(10));
+        let client = Client::new(&server.url, vec![]).unwrap();
+
+        let res = client.status_after_round(Round(9)).await;
+
+        assert_eq!(10, res.unwrap().last_round);
+    }
+
+    #[tokio::test]
+    async fn test_status_after_round_retry_limit() {
+        let server = MockServer::start_after_drops(2, status_response(10));
+        let client = Client::new(&server.url, vec![])
+            .unwrap()
+            .long_poll_retries(1);
+
+        let res = client.status_after_round(Round(9)).await;
+
+        assert!(matches!(
+            res,
+            Err(ClientError::Request(RequestError {
+                details: RequestErrorDetails::Client { .. },
+                ..
+            }))
+        ));
+    }
+
+    fn gunzip(bytes: &[u8]) -> Vec<u8> {
+        let mut decompressed = vec![];
+        flate2::read::GzDecoder::new(bytes)
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        decompressed
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_gzip_request() {
+        let server = MockServer::start(vec![("200 OK", r#"{"txId": "TXID"}"#.to_owned())]);
+        let client = Client::new(&server.url, vec![]).unwrap().gzip_request(true);
+        let rawtxn = vec![7; 2_000];
+
+        let res = client.broadcast_raw_transaction(&rawtxn).await.unwrap();
+
+        assert_eq!("TXID", res.tx_id);
+        let requests = server.requests_with_bodies();
+        let (head, body) = &requests[0];
+        assert!(head.to_lowercase().contains("content-encoding: gzip"));
+        assert!(body.len() < rawtxn.len());
+        assert_eq!(rawtxn, gunzip(body));
+    }
+
+    #[tokio::test]
+    async fn test_gzip_request_falls_back_to_uncompressed() {
+        let server = MockServer::start(vec![
+            ("415 Unsupported Media Type", "".to_owned()),
+            ("200 OK", r#"{"txId": "TXID"}"#.to_owned()),
+        ]);
+        let client = Client::new(&server.url, vec![]).unwrap().gzip_request(true);
+        let rawtxn = vec![7; 2_000];
+
+        let res = client.broadcast_raw_transaction(&rawtxn).await.unwrap();
+
+        assert_eq!("TXID", res.tx_id);
+        let requests = server.requests_with_bodies();
+        assert_eq!(rawtxn, gunzip(&requests[0].1));
+        assert!(!requests[1].0.to_lowercase().contains("content-encoding"));
+        assert_eq!(rawtxn, requests[1].1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_participation_key() {
+        let server = MockServer::start(vec![("200 OK", "".to_owned())]);
+        let client = Client::new(&server.url, vec![]).unwrap();
+
+        let res = client.delete_participation_key("KEYID").await;
+
+        assert!(res.is_ok());
+        assert!(server.requests()[0].starts_with("DELETE /v2/participation/KEYID HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_participation_key_error() {
+        let server = MockServer::start(vec![("404 Not Found", "".to_owned())]);
+        let client = Client::new(&server.url, vec![]).unwrap();
+
+        let res = client.delete_participation_key("KEYID").await;
+
+        server.requests();
+        match res {
+            Err(ClientError::Request(e)) => assert!(matches!(
+                e.details,
+                RequestErrorDetails::Http { status: 404, .. }
+            )),
+            _ => panic!("Expected http error, got: {:?}", res),
+        }
+    }
 }
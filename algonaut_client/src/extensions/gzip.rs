@@ -0,0 +1,12 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// Gzip compresses a request body.
+pub(crate) fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(bytes)
+        .expect("writing to a vector doesn't fail");
+    encoder.finish().expect("writing to a vector doesn't fail")
+}
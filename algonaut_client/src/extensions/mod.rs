@@ -1 +1,2 @@
+pub(super) mod gzip;
 pub(super) mod reqwest;
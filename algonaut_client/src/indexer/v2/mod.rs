@@ -5,9 +5,9 @@ use algonaut_core::Round;
 use algonaut_model::indexer::v2::{
     AccountInfoResponse, AccountResponse, AccountTransactionResponse, ApplicationInfoResponse,
     ApplicationResponse, AssetResponse, AssetTransactionResponse, AssetsInfoResponse,
-    BalancesResponse, Block, QueryAccount, QueryAccountInfo, QueryAccountTransaction,
-    QueryApplicationInfo, QueryApplications, QueryAssetTransaction, QueryAssets, QueryAssetsInfo,
-    QueryBalances, QueryTransaction, TransactionResponse,
+    BalancesResponse, Block, IndexerHealth, QueryAccount, QueryAccountInfo,
+    QueryAccountTransaction, QueryApplicationInfo, QueryApplications, QueryAssetTransaction,
+    QueryAssets, QueryAssetsInfo, QueryBalances, QueryTransaction, TransactionResponse,
 };
 use reqwest::header::HeaderMap;
 use reqwest::Url;
@@ -28,17 +28,20 @@ impl Client {
         })
     }
 
-    /// Returns Ok if healthy
-    pub async fn health(&self) -> Result<(), ClientError> {
-        let _ = self
+    /// Returns the indexer's health if healthy
+    pub async fn health(&self) -> Result<IndexerHealth, ClientError> {
+        let response = self
             .http_client
             .get(&format!("{}health", self.url))
             .headers(self.headers.clone())
             .send()
             .await?
             .http_error_for_status()
+            .await?
+            .json()
             .await?;
-        Ok(())
+
+        Ok(response)
     }
 
     /// Search for accounts.
@@ -8,6 +8,12 @@ mod extensions;
 pub mod indexer;
 /// Key management daemon
 pub mod kmd;
+/// HTTP connection pool settings
+pub mod pool;
+/// Mock server and response fixtures for unit tests
+#[cfg(any(test, feature = "test-utils"))]
+#[doc(hidden)]
+pub mod test_utils;
 /// Api token management utils
 pub mod token;
 
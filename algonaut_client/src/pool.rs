@@ -0,0 +1,27 @@
+use crate::error::ClientError;
+use std::time::Duration;
+
+/// Connection pool settings of the HTTP client.
+///
+/// Unset settings keep reqwest's defaults: no limit of idle connections per host, and idle
+/// connections kept alive for 90 seconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Maximum number of idle connections kept alive per host.
+    pub max_idle_per_host: Option<usize>,
+    /// How long idle connections are kept alive.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl PoolConfig {
+    pub(crate) fn http_client(&self) -> Result<reqwest::Client, ClientError> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(max_idle_per_host) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle_per_host);
+        }
+        if let Some(idle_timeout) = self.idle_timeout {
+            builder = builder.pool_idle_timeout(idle_timeout);
+        }
+        Ok(builder.build()?)
+    }
+}
@@ -0,0 +1,279 @@
+//! Helpers for unit tests of the clients and of the crates wrapping them: a minimal HTTP server
+//! and algod response fixtures. Not part of the stable API.
+
+use algonaut_core::Address;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread::JoinHandle;
+
+/// Minimal HTTP server for unit tests: answers each request (one per connection) with the next
+/// canned response and records what was received.
+pub struct MockServer {
+    pub url: String,
+    handle: JoinHandle<Vec<Vec<u8>>>,
+}
+
+impl MockServer {
+    /// Starts serving the given (status line, JSON body) responses, in order.
+    pub fn start(responses: Vec<(&'static str, String)>) -> MockServer {
+        let count = responses.len();
+        let mut responses = responses.into_iter();
+        MockServer::start_with(count, move |_| responses.next().unwrap())
+    }
+
+    /// Starts serving `count` requests, answering each with the (status line, JSON body)
+    /// returned by `respond` for the received request.
+    pub fn start_with<F>(count: usize, mut respond: F) -> MockServer
+    where
+        F: FnMut(&str) -> (&'static str, String) + Send + 'static,
+    {
+        MockServer::serve(count, "application/json", move |request| {
+            let (status, body) = respond(&String::from_utf8_lossy(request));
+            Some((status, body.into_bytes()))
+        })
+    }
+
+    /// Starts serving the given (status line, msgpack body) responses, in order.
+    pub fn start_msgpack(responses: Vec<(&'static str, Vec<u8>)>) -> MockServer {
+        let count = responses.len();
+        let mut responses = responses.into_iter();
+        MockServer::serve(count, "application/msgpack", move |_| responses.next())
+    }
+
+    /// Drops the first `drops` connections after reading their request, then answers the next
+    /// one with the JSON `body`.
+    pub fn start_after_drops(drops: usize, body: String) -> MockServer {
+        let mut body = Some(body);
+        let mut count = 0;
+        MockServer::serve(drops + 1, "application/json", move |_| {
+            count += 1;
+            if count > drops {
+                body.take().map(|body| ("200 OK", body.into_bytes()))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Serves `count` requests with the (status line, body) returned by `respond`, dropping the
+    /// connection without response on `None`.
+    fn serve<F>(count: usize, content_type: &'static str, mut respond: F) -> MockServer
+    where
+        F: FnMut(&[u8]) -> Option<(&'static str, Vec<u8>)> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            (0..count)
+                .map(|_| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let request = read_request(&mut stream);
+                    if let Some((status, body)) = respond(&request) {
+                        let head = format!(
+                            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            status,
+                            content_type,
+                            body.len()
+                        );
+                        stream.write_all(head.as_bytes()).unwrap();
+                        stream.write_all(&body).unwrap();
+                    }
+                    request
+                })
+                .collect()
+        });
+        MockServer { url, handle }
+    }
+
+    /// Waits until all the responses were served and returns the received requests.
+    pub fn requests(self) -> Vec<String> {
+        self.handle
+            .join()
+            .unwrap()
+            .iter()
+            .map(|request| String::from_utf8_lossy(request).into_owned())
+            .collect()
+    }
+
+    /// Waits until all the responses were served and returns the (head, body) of the received
+    /// requests, with the bodies as sent (e.g. compressed).
+    pub fn requests_with_bodies(self) -> Vec<(String, Vec<u8>)> {
+        self.handle
+            .join()
+            .unwrap()
+            .into_iter()
+            .map(|request| {
+                let head_end = head_end(&request).unwrap();
+                (
+                    String::from_utf8_lossy(&request[..head_end]).into_owned(),
+                    request[head_end..].to_vec(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Starts a server that accepts connections but never responds.
+pub fn stalled_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let _connections: Vec<TcpStream> = listener.incoming().filter_map(Result::ok).collect();
+    });
+    url
+}
+
+fn head_end(request: &[u8]) -> Option<usize> {
+    request
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|i| i + 4)
+}
+
+fn read_request(stream: &mut TcpStream) -> Vec<u8> {
+    let mut request = vec![];
+    let mut buf = [0; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut buf).unwrap();
+        request.extend_from_slice(&buf[..n]);
+        if let Some(end) = head_end(&request) {
+            break end;
+        }
+    };
+    let head = String::from_utf8_lossy(&request[..header_end]).to_lowercase();
+    let content_length = head
+        .lines()
+        .find_map(|l| l.strip_prefix("content-length:"))
+        .map(|l| l.trim().parse::<usize>().unwrap())
+        .unwrap_or(0);
+    while request.len() < header_end + content_length {
+        let n = stream.read(&mut buf).unwrap();
+        request.extend_from_slice(&buf[..n]);
+    }
+    request
+}
+
+/// Algod's `/v2/status` response at `last_round`.
+pub fn status_response(last_round: u64) -> String {
+    format!(
+        r#"{{
+            "catchup-time": 0,
+            "last-round": {},
+            "last-version": "future",
+            "next-version": "future",
+            "next-version-round": {},
+            "next-version-supported": true,
+            "stopped-at-unsupported-round": false,
+            "time-since-last-round": 0
+        }}"#,
+        last_round,
+        last_round + 1
+    )
+}
+
+/// Algod's `/versions` response of a node of the network with the (base64) `genesis_hash`.
+pub fn versions_response(genesis_hash: &str) -> String {
+    format!(
+        r#"{{
+            "build": {{
+                "branch": "rel/stable",
+                "build_number": 1,
+                "channel": "stable",
+                "commit_hash": "a1b2c3d4",
+                "major": 2,
+                "minor": 8
+            }},
+            "genesis_hash_b64": "{}",
+            "genesis_id": "mainnet-v1.0",
+            "versions": ["v1", "v2"]
+        }}"#,
+        genesis_hash
+    )
+}
+
+/// Algod's `/v2/transactions/params` response at `last_round`, with the min fee of 1000.
+pub fn params_response(genesis_id: &str, genesis_hash: &str, last_round: u64) -> String {
+    format!(
+        r#"{{
+            "consensus-version": "future",
+            "fee": 0,
+            "genesis-hash": "{}",
+            "genesis-id": "{}",
+            "last-round": {},
+            "min-fee": 1000
+        }}"#,
+        genesis_hash, genesis_id, last_round
+    )
+}
+
+/// Algod's `/v2/transactions/pending/{txid}` response of a transaction confirmed in
+/// `confirmed_round` (still pending if `None`).
+pub fn pending_response(confirmed_round: Option<u64>, pool_error: &str) -> String {
+    format!(
+        r#"{{
+            "confirmed-round": {},
+            "pool-error": "{}",
+            "txn": {{}}
+        }}"#,
+        confirmed_round.map_or("null".to_owned(), |r| r.to_string()),
+        pool_error
+    )
+}
+
+/// Algod's (JSON) `/v2/blocks/{round}` response of an empty testnet block.
+pub fn block_response(round: u64) -> String {
+    format!(
+        r#"{{
+            "block": {{
+                "earn": 27521,
+                "fees": "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE",
+                "frac": 2020020,
+                "gen": "testnet-v1.0",
+                "gh": "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+                "prev": "blk-4VPJ3DLAJVBZ3NEKJZ5A7EVQ7SYOV6CUBGDNBAS4JVOT5XPICXNQ",
+                "proto": "future",
+                "rate": 0,
+                "rnd": {},
+                "rwcalr": 500000,
+                "rwd": "7777777777777777777777777777777777777777777777777774MSJUVU",
+                "seed": "u6QdZ2mRuxuoJSjlt1KVorQ5ZegQ42TMVVJT+az47sY=",
+                "ts": 1678886400,
+                "txn": ""
+            }}
+        }}"#,
+        round
+    )
+}
+
+/// Algod's `/v2/accounts/{address}` response of an account holding 10 units of each
+/// `(asset id, frozen)`.
+pub fn account_response(address: &Address, holdings: &[(u64, bool)]) -> String {
+    let assets: Vec<String> = holdings
+        .iter()
+        .map(|(asset_id, is_frozen)| {
+            format!(
+                r#"{{
+                    "amount": 10,
+                    "asset-id": {},
+                    "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "is-frozen": {}
+                }}"#,
+                asset_id, is_frozen
+            )
+        })
+        .collect();
+    format!(
+        r#"{{
+            "address": "{}",
+            "amount": 5000000,
+            "amount-without-pending-rewards": 5000000,
+            "assets": [{}],
+            "pending-rewards": 0,
+            "rewards": 0,
+            "round": 18241,
+            "status": "Offline"
+        }}"#,
+        address.to_string(),
+        assets.join(",")
+    )
+}
@@ -1,9 +1,11 @@
+use crate::CoreError;
 use crate::Signature;
 use algonaut_crypto::Ed25519PublicKey;
 use algonaut_encoding::U8_32Visitor;
-use data_encoding::BASE32_NOPAD;
+use data_encoding::{BASE32_NOPAD, BASE64, HEXLOWER_PERMISSIVE};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sha2::Digest;
+use std::convert::TryInto;
 use std::fmt::{self, Debug, Formatter};
 use std::str::FromStr;
 
@@ -53,6 +55,39 @@ impl Address {
         BASE32_NOPAD.encode(&checksum_address)
     }
 
+    /// The 32 bytes of the address (without checksum) as lowercase hex, e.g. to cross-reference
+    /// with tools showing the raw public key.
+    pub fn to_hex(&self) -> String {
+        HEXLOWER_PERMISSIVE.encode(&self.0)
+    }
+
+    /// Decodes the hex encoding of the 32 bytes of an address, in any case.
+    pub fn from_hex(hex: &str) -> Result<Address, CoreError> {
+        Self::from_raw_bytes(&HEXLOWER_PERMISSIVE.decode(hex.as_bytes())?)
+    }
+
+    /// The 32 bytes of the address (without checksum) as (padded) base64, like `goal` and the
+    /// REST APIs show raw public keys.
+    pub fn to_base64(&self) -> String {
+        BASE64.encode(&self.0)
+    }
+
+    /// Decodes the base64 encoding of the 32 bytes of an address.
+    pub fn from_base64(base64: &str) -> Result<Address, CoreError> {
+        Self::from_raw_bytes(&BASE64.decode(base64.as_bytes())?)
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Address, CoreError> {
+        let bytes: [u8; HASH_LEN] = bytes.try_into().map_err(|_| {
+            CoreError::General(format!(
+                "Invalid address length: {} bytes, expected {}",
+                bytes.len(),
+                HASH_LEN
+            ))
+        })?;
+        Ok(Address(bytes))
+    }
+
     pub fn verify_bytes(&self, message: &[u8], signature: &Signature) -> bool {
         let mut message_to_verify = b"MX".to_vec();
         message_to_verify.extend_from_slice(message);
@@ -136,6 +171,12 @@ impl MultisigAddress {
         self.public_keys.contains(&address.as_public_key())
     }
 
+    /// Whether `address` is the address of this multisig, e.g. the expected sender of a multisig
+    /// transaction. Unlike [contains](Self::contains), which checks the individual keys.
+    pub fn matches(&self, address: &Address) -> bool {
+        self.address() == *address
+    }
+
     /// Generates a checksum from the contained public keys usable as an address
     pub fn address(&self) -> Address {
         let mut buf = b"MultisigAddr".to_vec();
@@ -193,6 +234,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn multisig_matches_address() {
+        let addresses: Vec<Address> = [
+            "DN7MBMCL5JQ3PFUQS7TMX5AH4EEKOBJVDUF4TCV6WERATKFLQF4MQUPZTA",
+            "BFRTECKTOOE7A5LHCF3TTEOH2A7BW46IYT2SX5VP6ANKEXHZYJY77SJTVM",
+            "47YPQTIGQEO7T4Y4RWDYWEKV6RTR2UNBQXBABEEGM72ESWDQNCQ52OPASU",
+        ]
+        .iter()
+        .map(|a| a.parse().unwrap())
+        .collect();
+        let multisig = MultisigAddress::new(1, 2, &addresses).unwrap();
+
+        assert!(multisig.matches(
+            &"RWJLJCMQAFZ2ATP2INM2GZTKNL6OULCCUBO5TQPXH3V2KR4AG7U5UA5JNM"
+                .parse()
+                .unwrap()
+        ));
+        assert!(!multisig.matches(&addresses[0]));
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let addr: Address = "737777777777777777777777777777777777777777777777777UFEJ2CI"
+            .parse()
+            .unwrap();
+
+        let hex = addr.to_hex();
+
+        assert_eq!(64, hex.len());
+        assert_eq!(
+            &addr.0[..],
+            &HEXLOWER_PERMISSIVE.decode(hex.as_bytes()).unwrap()[..]
+        );
+        assert_eq!(addr, Address::from_hex(&hex).unwrap());
+        assert_eq!(addr, Address::from_hex(&hex.to_uppercase()).unwrap());
+        assert!(Address::from_hex(&hex[2..]).is_err());
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        for _ in 0..100 {
+            let addr = Address(OsRng.gen());
+
+            let base64 = addr.to_base64();
+
+            assert_eq!(addr, Address::from_base64(&base64).unwrap());
+            assert_eq!(addr, Address::from_str(&addr.to_string()).unwrap());
+        }
+        assert_eq!(
+            "//////////////////////////////////////////8=",
+            Address([255; 32]).to_base64()
+        );
+        assert!(Address::from_base64("AAAA").is_err());
+    }
+
     #[test]
     fn serializes_deserializes() {
         let addr = Address(OsRng.gen());
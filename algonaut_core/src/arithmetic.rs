@@ -0,0 +1,35 @@
+//! Integer operations backing the arithmetic operators of [MicroAlgos](crate::MicroAlgos) and
+//! [Round](crate::Round).
+//!
+//! By default these behave like the plain integer operators (panicking on overflow in debug builds).
+//! With the `checked-arithmetic` feature they saturate at the numeric bounds instead.
+
+#[cfg(not(feature = "checked-arithmetic"))]
+pub(crate) fn add(lhs: u64, rhs: u64) -> u64 {
+    lhs + rhs
+}
+
+#[cfg(not(feature = "checked-arithmetic"))]
+pub(crate) fn sub(lhs: u64, rhs: u64) -> u64 {
+    lhs - rhs
+}
+
+#[cfg(not(feature = "checked-arithmetic"))]
+pub(crate) fn mul(lhs: u64, rhs: u64) -> u64 {
+    lhs * rhs
+}
+
+#[cfg(feature = "checked-arithmetic")]
+pub(crate) fn add(lhs: u64, rhs: u64) -> u64 {
+    lhs.saturating_add(rhs)
+}
+
+#[cfg(feature = "checked-arithmetic")]
+pub(crate) fn sub(lhs: u64, rhs: u64) -> u64 {
+    lhs.saturating_sub(rhs)
+}
+
+#[cfg(feature = "checked-arithmetic")]
+pub(crate) fn mul(lhs: u64, rhs: u64) -> u64 {
+    lhs.saturating_mul(rhs)
+}
@@ -0,0 +1,144 @@
+use crate::error::CoreError;
+use std::fmt::{self, Display, Formatter};
+
+/// Maximum number of decimals of an asset.
+pub const MAX_ASSET_DECIMALS: u32 = 19;
+
+/// An amount of an asset in base units, displayed scaled by the asset's decimals, e.g. `1500000`
+/// base units of an asset with 6 decimals are displayed as `1.5`.
+///
+/// Like [MicroAlgos](crate::MicroAlgos) for algos, but the decimals vary per asset. The
+/// conversions are exact: no floats are involved.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AssetAmount {
+    raw: u64,
+    decimals: u32,
+}
+
+impl AssetAmount {
+    /// Fails if `decimals` exceeds [MAX_ASSET_DECIMALS].
+    pub fn new(raw: u64, decimals: u32) -> Result<AssetAmount, CoreError> {
+        check_decimals(decimals)?;
+        Ok(AssetAmount { raw, decimals })
+    }
+
+    /// The amount in base units.
+    pub fn raw(&self) -> u64 {
+        self.raw
+    }
+
+    /// The decimals of the asset, at most [MAX_ASSET_DECIMALS].
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Parses a decimal amount (e.g. `1.5`) of an asset with `decimals` decimals.
+    ///
+    /// Fails if the amount has more fractional digits than the asset's decimals, or doesn't fit
+    /// in `u64` base units.
+    pub fn parse(amount: &str, decimals: u32) -> Result<AssetAmount, CoreError> {
+        check_decimals(decimals)?;
+        let invalid = || CoreError::General(format!("Invalid asset amount: {}", amount));
+        let (integer, fraction) = match amount.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(_) => return Err(invalid()),
+            None => (amount, ""),
+        };
+        if integer.is_empty()
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        if fraction.len() > decimals as usize {
+            return Err(CoreError::General(format!(
+                "Asset amount {} has more than {} decimals",
+                amount, decimals
+            )));
+        }
+        let overflow = || CoreError::General(format!("Asset amount overflows u64: {}", amount));
+        let scaled_integer = integer
+            .parse::<u64>()
+            .map_err(|_| overflow())?
+            .checked_mul(10u64.pow(decimals))
+            .ok_or_else(overflow)?;
+        let scaled_fraction = if fraction.is_empty() {
+            0
+        } else {
+            // At most 19 digits, which fit in u64.
+            fraction.parse::<u64>().map_err(|_| invalid())?
+                * 10u64.pow(decimals - fraction.len() as u32)
+        };
+        let raw = scaled_integer
+            .checked_add(scaled_fraction)
+            .ok_or_else(overflow)?;
+        Ok(AssetAmount { raw, decimals })
+    }
+}
+
+impl Display for AssetAmount {
+    /// The scaled amount, without trailing fractional zeros.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let factor = 10u64.pow(self.decimals);
+        let integer = self.raw / factor;
+        let fraction = self.raw % factor;
+        if fraction == 0 {
+            return write!(f, "{}", integer);
+        }
+        let fraction = format!("{:0width$}", fraction, width = self.decimals as usize);
+        write!(f, "{}.{}", integer, fraction.trim_end_matches('0'))
+    }
+}
+
+fn check_decimals(decimals: u32) -> Result<(), CoreError> {
+    if decimals > MAX_ASSET_DECIMALS {
+        return Err(CoreError::General(format!(
+            "Asset decimals {} exceed {}",
+            decimals, MAX_ASSET_DECIMALS
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_six_decimals() {
+        let amount = AssetAmount::new(1_500_000, 6).unwrap();
+
+        assert_eq!("1.5", amount.to_string());
+        assert_eq!("0.000001", AssetAmount::new(1, 6).unwrap().to_string());
+        assert_eq!("2", AssetAmount::new(2_000_000, 6).unwrap().to_string());
+        assert_eq!(amount, AssetAmount::parse("1.5", 6).unwrap());
+        assert_eq!(1, AssetAmount::parse("0.000001", 6).unwrap().raw());
+        assert!(AssetAmount::parse("0.0000001", 6).is_err());
+    }
+
+    #[test]
+    fn test_zero_decimals() {
+        assert_eq!("42", AssetAmount::new(42, 0).unwrap().to_string());
+        assert_eq!(42, AssetAmount::parse("42", 0).unwrap().raw());
+        assert!(AssetAmount::parse("42.5", 0).is_err());
+        assert!(AssetAmount::parse("42.", 0).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_amounts() {
+        assert!(AssetAmount::parse("", 6).is_err());
+        assert!(AssetAmount::parse(".5", 6).is_err());
+        assert!(AssetAmount::parse("-1", 6).is_err());
+        assert!(AssetAmount::parse("1e6", 6).is_err());
+        assert!(AssetAmount::parse("18446744073709.551616", 6).is_err());
+        assert_eq!(
+            u64::MAX,
+            AssetAmount::parse("18446744073709.551615", 6)
+                .unwrap()
+                .raw()
+        );
+        assert!(AssetAmount::new(1, 20).is_err());
+    }
+}
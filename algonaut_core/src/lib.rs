@@ -2,8 +2,7 @@ use algonaut_crypto::HashDigest;
 use algonaut_crypto::Signature;
 use algonaut_encoding::U8_32Visitor;
 use data_encoding::BASE64;
-use derive_more::{Add, Display, Sub};
-use error::CoreError;
+use derive_more::Display;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sha2::Digest;
 use static_assertions::_core::ops::{Add, Sub};
@@ -13,18 +12,35 @@ use std::ops::Mul;
 
 pub use address::Address;
 pub use address::MultisigAddress;
+pub use asset_amount::AssetAmount;
+pub use asset_amount::MAX_ASSET_DECIMALS;
+pub use error::CoreError;
 pub use multisig::MultisigSignature;
 pub use multisig::MultisigSubsig;
 
 mod address;
+mod arithmetic;
+mod asset_amount;
 mod error;
 mod multisig;
 
 pub const MICRO_ALGO_CONVERSION_FACTOR: f64 = 1e6;
 
+/// Max number of transactions in an atomic group.
+pub const MAX_TX_GROUP_SIZE: usize = 16;
+
+/// Max length in bytes of a transaction note.
+pub const MAX_NOTE_LEN: usize = 1024;
+
+/// Minimum fee of a transaction.
+pub const MIN_TXN_FEE: MicroAlgos = MicroAlgos(1_000);
+
+/// Max number of rounds between the first and last valid rounds of a transaction.
+pub const MAX_TX_VALIDITY_WINDOW: u64 = 1_000;
+
 /// MicroAlgos are the base unit of currency in Algorand
 #[derive(
-    Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Display, Add, Sub,
+    Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Display,
 )]
 pub struct MicroAlgos(pub u64);
 
@@ -36,13 +52,79 @@ impl MicroAlgos {
     pub fn from_algos(algos: f64) -> MicroAlgos {
         MicroAlgos((algos * MICRO_ALGO_CONVERSION_FACTOR) as u64)
     }
+
+    /// Converts algos to microalgos, rounding to the nearest microalgo (e.g. `8.2` is
+    /// `8_200_000`, where [from_algos](Self::from_algos) truncates the float error to `8_199_999`).
+    ///
+    /// Fails for negative, NaN or infinite amounts, and amounts over `u64::MAX` microalgos.
+    pub fn from_algos_checked(algos: f64) -> Result<MicroAlgos, CoreError> {
+        if !algos.is_finite() || algos < 0.0 {
+            return Err(CoreError::General(format!(
+                "Invalid algos amount: {}",
+                algos
+            )));
+        }
+        let micro_algos = (algos * MICRO_ALGO_CONVERSION_FACTOR).round();
+        // u64::MAX isn't representable as f64 and converts to 2^64.
+        if micro_algos >= u64::MAX as f64 {
+            return Err(CoreError::General(format!(
+                "Algos amount overflows microalgos: {}",
+                algos
+            )));
+        }
+        Ok(MicroAlgos(micro_algos as u64))
+    }
+}
+
+/// Splits `total` among recipients proportionally to `weights`, e.g. `&[1, 1, 1]` for an even
+/// split.
+///
+/// The shares are rounded down, and the leftover microalgos are assigned one each to the first
+/// recipients with a non-zero weight, so the shares always sum to `total`. If all the weights are
+/// zero, the split is even.
+pub fn split_amount(total: MicroAlgos, weights: &[u64]) -> Vec<MicroAlgos> {
+    if !weights.is_empty() && weights.iter().all(|w| *w == 0) {
+        return split_amount(total, &vec![1; weights.len()]);
+    }
+    let total_weight: u128 = weights.iter().map(|w| *w as u128).sum();
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|w| (total.0 as u128 * *w as u128 / total_weight) as u64)
+        .collect();
+    let mut leftover = total.0 - shares.iter().sum::<u64>();
+    for (share, weight) in shares.iter_mut().zip(weights) {
+        if leftover == 0 {
+            break;
+        }
+        if *weight > 0 {
+            *share += 1;
+            leftover -= 1;
+        }
+    }
+    shares.into_iter().map(MicroAlgos).collect()
+}
+
+impl Add for MicroAlgos {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        MicroAlgos(arithmetic::add(self.0, rhs.0))
+    }
 }
 
 impl Add<u64> for MicroAlgos {
     type Output = Self;
 
     fn add(self, rhs: u64) -> Self::Output {
-        MicroAlgos(self.0 + rhs)
+        MicroAlgos(arithmetic::add(self.0, rhs))
+    }
+}
+
+impl Sub for MicroAlgos {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        MicroAlgos(arithmetic::sub(self.0, rhs.0))
     }
 }
 
@@ -50,7 +132,7 @@ impl Sub<u64> for MicroAlgos {
     type Output = Self;
 
     fn sub(self, rhs: u64) -> Self::Output {
-        MicroAlgos(self.0 - rhs)
+        MicroAlgos(arithmetic::sub(self.0, rhs))
     }
 }
 
@@ -61,19 +143,35 @@ impl Mul<u64> for MicroAlgos {
     type Output = Self;
 
     fn mul(self, rhs: u64) -> Self::Output {
-        MicroAlgos(self.0 * rhs)
+        MicroAlgos(arithmetic::mul(self.0, rhs))
     }
 }
 
 /// Round of the Algorand consensus protocol
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize, Display, Add, Sub)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Display)]
 pub struct Round(pub u64);
 
+impl Add for Round {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Round(arithmetic::add(self.0, rhs.0))
+    }
+}
+
 impl Add<u64> for Round {
     type Output = Self;
 
     fn add(self, rhs: u64) -> Self::Output {
-        Round(self.0 + rhs)
+        Round(arithmetic::add(self.0, rhs))
+    }
+}
+
+impl Sub for Round {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Round(arithmetic::sub(self.0, rhs.0))
     }
 }
 
@@ -81,7 +179,7 @@ impl Sub<u64> for Round {
     type Output = Self;
 
     fn sub(self, rhs: u64) -> Self::Output {
-        Round(self.0 - rhs)
+        Round(arithmetic::sub(self.0, rhs))
     }
 }
 
@@ -92,10 +190,22 @@ impl Mul<u64> for Round {
     type Output = Self;
 
     fn mul(self, rhs: u64) -> Self::Output {
-        Round(self.0 * rhs)
+        Round(arithmetic::mul(self.0, rhs))
     }
 }
 
+/// Id of an application
+#[derive(
+    Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Display,
+)]
+pub struct AppId(pub u64);
+
+/// Id of an asset
+#[derive(
+    Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Display,
+)]
+pub struct AssetId(pub u64);
+
 /// Participation public key used in key registration transactions
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub struct VotePk(pub [u8; 32]);
@@ -181,7 +291,7 @@ pub struct SignedLogic {
 
 impl SignedLogic {
     pub fn as_address(&self) -> Address {
-        Address(sha2::Sha512Trunc256::digest(&self.logic.bytes_to_sign()).into())
+        self.logic.address()
     }
 
     /// Performs signature verification against the sender address, and general consistency checks.
@@ -192,7 +302,11 @@ impl SignedLogic {
                 let pk = address.as_public_key();
                 pk.verify(&self.logic.bytes_to_sign(), sig)
             }
-            LogicSignature::DelegatedMultiSig(msig) => msig.verify(&self.logic.bytes_to_sign()),
+            LogicSignature::DelegatedMultiSig(msig) => {
+                let multisig_address = msig.address();
+                multisig_address.address() == address
+                    && msig.verify(&multisig_address, &self.logic.bytes_to_sign())
+            }
         }
     }
 }
@@ -202,7 +316,7 @@ impl Debug for SignedLogic {
         write!(
             f,
             "logic: {:?}, args: {:?}, sig: {:?}",
-            BASE64.encode(&self.logic.0),
+            self.logic.to_base64(),
             self.args
                 .iter()
                 .map(|a| BASE64.encode(a))
@@ -212,10 +326,24 @@ impl Debug for SignedLogic {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
 pub struct CompiledTeal(pub Vec<u8>);
 
 impl CompiledTeal {
+    /// Decodes the program bytes from base64, e.g. the `result` of algod's compile endpoint.
+    pub fn from_base64(base64_str: &str) -> Result<CompiledTeal, CoreError> {
+        Ok(CompiledTeal(BASE64.decode(base64_str.as_bytes())?))
+    }
+
+    pub fn to_base64(&self) -> String {
+        BASE64.encode(&self.0)
+    }
+
+    /// Escrow (contract account) address of the program.
+    pub fn address(&self) -> Address {
+        Address(sha2::Sha512Trunc256::digest(&self.bytes_to_sign()).into())
+    }
+
     pub fn bytes_to_sign(&self) -> Vec<u8> {
         let mut prefix_encoded_tx = b"Program".to_vec();
         prefix_encoded_tx.extend_from_slice(&self.0);
@@ -230,9 +358,49 @@ pub enum LogicSignature {
     DelegatedMultiSig(MultisigSignature),
 }
 
+/// Msgpack encoding with sorted keys, as Algorand expects to sign and hash: structs are encoded
+/// as maps and the keys of all (nested) maps are sorted.
+///
+/// Implement it for user data (e.g. `impl ToMsgPack for Note {}`) to encode e.g. structured notes
+/// with sorted keys. Empty and zero values are encoded as serialized: skip them with serde
+/// attributes where needed.
 pub trait ToMsgPack: Serialize {
     fn to_msg_pack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
-        rmp_serde::to_vec_named(&self)
+        let bytes = rmp_serde::to_vec_named(&self)?;
+        let mut value = rmpv::decode::read_value(&mut &bytes[..])
+            .map_err(|e| rmp_serde::encode::Error::Syntax(e.to_string()))?;
+        sort_maps(&mut value);
+        let mut canonical = Vec::with_capacity(bytes.len());
+        rmpv::encode::write_value(&mut canonical, &value)
+            .map_err(|e| rmp_serde::encode::Error::Syntax(e.to_string()))?;
+        Ok(canonical)
+    }
+}
+
+fn sort_maps(value: &mut rmpv::Value) {
+    match value {
+        rmpv::Value::Map(entries) => {
+            for (key, value) in entries.iter_mut() {
+                sort_maps(key);
+                sort_maps(value);
+            }
+            entries.sort_by(|(a, b), (c, d)| cmp_keys(a, c).then_with(|| cmp_keys(b, d)));
+        }
+        rmpv::Value::Array(values) => values.iter_mut().for_each(sort_maps),
+        _ => {}
+    }
+}
+
+/// Strings are sorted by their bytes and integers by value, like the reference implementation.
+fn cmp_keys(a: &rmpv::Value, b: &rmpv::Value) -> std::cmp::Ordering {
+    match (a, b) {
+        (rmpv::Value::String(a), rmpv::Value::String(b)) => a.as_bytes().cmp(b.as_bytes()),
+        (rmpv::Value::Integer(a), rmpv::Value::Integer(b)) => match (a.as_u64(), b.as_u64()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => a.as_i64().cmp(&b.as_i64()),
+        },
+        (rmpv::Value::Binary(a), rmpv::Value::Binary(b)) => a.cmp(b),
+        _ => a.to_string().cmp(&b.to_string()),
     }
 }
 
@@ -253,3 +421,256 @@ pub struct SuggestedTransactionParams {
     pub first_valid: Round,
     pub last_valid: Round,
 }
+
+impl SuggestedTransactionParams {
+    /// Params for transactions valid from `first_valid` for the max validity window
+    /// ([MAX_TX_VALIDITY_WINDOW]) and paying the minimum fee ([MIN_TXN_FEE]), e.g. to build
+    /// transactions offline.
+    ///
+    /// The consensus version, which isn't needed to build transactions, is left empty.
+    pub fn new(genesis_id: &str, genesis_hash: HashDigest, first_valid: Round) -> Self {
+        SuggestedTransactionParams {
+            genesis_id: genesis_id.to_owned(),
+            genesis_hash,
+            consensus_version: "".to_owned(),
+            fee: MicroAlgos(0),
+            min_fee: MIN_TXN_FEE,
+            first_valid,
+            last_valid: first_valid + MAX_TX_VALIDITY_WINDOW,
+        }
+    }
+
+    /// [new](Self::new) params for TestNet.
+    pub fn testnet(first_valid: Round) -> Self {
+        let genesis_hash = BASE64
+            .decode(b"SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=")
+            .expect("valid base64")
+            .try_into()
+            .expect("32 bytes");
+        Self::new("testnet-v1.0", HashDigest(genesis_hash), first_valid)
+    }
+}
+
+/// An immutable copy of the params that affect the encoding of transactions, to build them
+/// reproducibly, e.g. for golden tests: the same snapshot and inputs give byte-identical
+/// transactions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParamsSnapshot {
+    genesis_id: String,
+    genesis_hash: HashDigest,
+    fee: MicroAlgos,
+    min_fee: MicroAlgos,
+    first_valid: Round,
+    last_valid: Round,
+}
+
+impl ParamsSnapshot {
+    pub fn new(params: &SuggestedTransactionParams) -> Self {
+        ParamsSnapshot {
+            genesis_id: params.genesis_id.clone(),
+            genesis_hash: params.genesis_hash,
+            fee: params.fee.max(params.min_fee),
+            min_fee: params.min_fee,
+            first_valid: params.first_valid,
+            last_valid: params.last_valid,
+        }
+    }
+
+    pub fn genesis_id(&self) -> &str {
+        &self.genesis_id
+    }
+
+    pub fn genesis_hash(&self) -> HashDigest {
+        self.genesis_hash
+    }
+
+    /// The effective fee: the max of the suggested fee and the minimum fee.
+    pub fn fee(&self) -> MicroAlgos {
+        self.fee
+    }
+
+    pub fn min_fee(&self) -> MicroAlgos {
+        self.min_fee
+    }
+
+    pub fn first_valid(&self) -> Round {
+        self.first_valid
+    }
+
+    pub fn last_valid(&self) -> Round {
+        self.last_valid
+    }
+}
+
+impl From<SuggestedTransactionParams> for ParamsSnapshot {
+    fn from(params: SuggestedTransactionParams) -> Self {
+        ParamsSnapshot::new(&params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_msg_pack_sorts_keys() {
+        #[derive(Serialize)]
+        struct Reference {
+            zone: u64,
+            invoice: String,
+            tags: std::collections::HashMap<String, u64>,
+        }
+        impl ToMsgPack for Reference {}
+        let reference = Reference {
+            zone: 3,
+            invoice: "inv-1".to_owned(),
+            tags: vec![("vat".to_owned(), 2), ("net".to_owned(), 1)]
+                .into_iter()
+                .collect(),
+        };
+        let str = |s: &str| rmpv::Value::String(s.into());
+        let mut expected = vec![];
+        rmpv::encode::write_value(
+            &mut expected,
+            &rmpv::Value::Map(vec![
+                (str("invoice"), str("inv-1")),
+                (
+                    str("tags"),
+                    rmpv::Value::Map(vec![(str("net"), 1.into()), (str("vat"), 2.into())]),
+                ),
+                (str("zone"), 3.into()),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(expected, reference.to_msg_pack().unwrap());
+    }
+
+    #[test]
+    fn test_split_amount_even() {
+        let shares = split_amount(MicroAlgos(10), &[1, 1, 1]);
+
+        assert_eq!(vec![MicroAlgos(4), MicroAlgos(3), MicroAlgos(3)], shares);
+    }
+
+    #[test]
+    fn test_split_amount_weighted() {
+        let total = MicroAlgos(1_000_001);
+        let shares = split_amount(total, &[3, 0, 2, 2]);
+
+        assert_eq!(
+            vec![
+                MicroAlgos(428_572),
+                MicroAlgos(0),
+                MicroAlgos(285_715),
+                MicroAlgos(285_714)
+            ],
+            shares
+        );
+        assert_eq!(total.0, shares.iter().map(|s| s.0).sum::<u64>());
+    }
+
+    #[test]
+    fn test_split_amount_sums_to_total() {
+        let total = MicroAlgos(u64::MAX);
+        for weights in [&[1, 1, 1][..], &[u64::MAX, 1, 7], &[0, 0], &[5]] {
+            let shares = split_amount(total, weights);
+
+            assert_eq!(weights.len(), shares.len());
+            assert_eq!(
+                total.0 as u128,
+                shares.iter().map(|s| s.0 as u128).sum::<u128>()
+            );
+        }
+        assert!(split_amount(total, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_micro_algos_arithmetic() {
+        assert_eq!(MicroAlgos(3), MicroAlgos(1) + MicroAlgos(2));
+        assert_eq!(MicroAlgos(3), MicroAlgos(1) + 2);
+        assert_eq!(MicroAlgos(1), MicroAlgos(3) - MicroAlgos(2));
+        assert_eq!(MicroAlgos(1), MicroAlgos(3) - 2);
+        assert_eq!(MicroAlgos(6), MicroAlgos(3) * 2);
+    }
+
+    #[test]
+    fn test_from_algos_checked_rounds_to_nearest() {
+        assert_eq!(MicroAlgos(8_199_999), MicroAlgos::from_algos(8.2));
+        assert_eq!(
+            MicroAlgos(8_200_000),
+            MicroAlgos::from_algos_checked(8.2).unwrap()
+        );
+        assert_eq!(
+            MicroAlgos(1_100_000),
+            MicroAlgos::from_algos_checked(1.1).unwrap()
+        );
+        assert_eq!(MicroAlgos(0), MicroAlgos::from_algos_checked(0.0).unwrap());
+    }
+
+    #[test]
+    fn test_from_algos_checked_rejects_invalid_amounts() {
+        assert!(MicroAlgos::from_algos_checked(-1.0).is_err());
+        assert!(MicroAlgos::from_algos_checked(f64::NAN).is_err());
+        assert!(MicroAlgos::from_algos_checked(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_from_algos_checked_rejects_overflow() {
+        assert!(MicroAlgos::from_algos_checked(u64::MAX as f64).is_err());
+        assert!(MicroAlgos::from_algos_checked(20_000_000_000_000.0).is_err());
+        assert!(MicroAlgos::from_algos_checked(10_000_000_000.0).is_ok());
+    }
+
+    #[test]
+    fn test_compiled_teal_base64_round_trip() {
+        let program = CompiledTeal(vec![
+            0x01, 0x20, 0x01, 0x01, 0x22, // int 1
+        ]);
+
+        assert_eq!("ASABASI=", program.to_base64());
+        assert_eq!(program, CompiledTeal::from_base64("ASABASI=").unwrap());
+        assert!(CompiledTeal::from_base64("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_compiled_teal_address() {
+        let program = CompiledTeal(vec![
+            0x01, 0x20, 0x01, 0x01, 0x22, // int 1
+        ]);
+
+        assert_eq!(
+            "6Z3C3LDVWGMX23BMSYMANACQOSINPFIRF77H7N3AWJZYV6OH6GWTJKVMXY",
+            program.address().to_string()
+        );
+    }
+
+    #[test]
+    fn test_round_arithmetic() {
+        assert_eq!(Round(3), Round(1) + Round(2));
+        assert_eq!(Round(3), Round(1) + 2);
+        assert_eq!(Round(1), Round(3) - Round(2));
+        assert_eq!(Round(1), Round(3) - 2);
+        assert_eq!(Round(6), Round(3) * 2);
+    }
+
+    #[test]
+    #[cfg(feature = "checked-arithmetic")]
+    fn test_micro_algos_arithmetic_saturates() {
+        assert_eq!(MicroAlgos(u64::MAX), MicroAlgos(u64::MAX) + MicroAlgos(1));
+        assert_eq!(MicroAlgos(u64::MAX), MicroAlgos(u64::MAX) + 1);
+        assert_eq!(MicroAlgos(0), MicroAlgos(0) - MicroAlgos(1));
+        assert_eq!(MicroAlgos(0), MicroAlgos(0) - 1);
+        assert_eq!(MicroAlgos(u64::MAX), MicroAlgos(u64::MAX) * 2);
+    }
+
+    #[test]
+    #[cfg(feature = "checked-arithmetic")]
+    fn test_round_arithmetic_saturates() {
+        assert_eq!(Round(u64::MAX), Round(u64::MAX) + Round(1));
+        assert_eq!(Round(u64::MAX), Round(u64::MAX) + 1);
+        assert_eq!(Round(0), Round(0) - Round(1));
+        assert_eq!(Round(0), Round(0) - 1);
+        assert_eq!(Round(u64::MAX), Round(u64::MAX) * 2);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::MultisigAddress;
 use algonaut_crypto::Ed25519PublicKey;
 use algonaut_crypto::Signature;
 use serde::{Deserialize, Serialize, Serializer};
@@ -18,28 +19,47 @@ pub struct MultisigSignature {
 }
 
 impl MultisigSignature {
-    pub fn verify(&self, message: &[u8]) -> bool {
+    /// Verifies that this is a complete signature of `message` by `address`: the subsigs match
+    /// the address keys in order, every present signature is valid and at least threshold are present.
+    pub fn verify(&self, address: &MultisigAddress, message: &[u8]) -> bool {
         if self.version != MULTISIG_VERSION || self.threshold == 0 || self.subsigs.is_empty() {
             return false;
         }
-        if self.threshold as usize > self.subsigs.len() {
+        if self.version != address.version
+            || self.threshold != address.threshold
+            || self.subsigs.len() != address.public_keys.len()
+            || self
+                .subsigs
+                .iter()
+                .zip(&address.public_keys)
+                .any(|(subsig, key)| subsig.key != *key)
+        {
             return false;
         }
         self.verify_subsigs(message)
     }
 
-    /// Checks threshold subsigs are signed and that the signatures are valid.
+    /// The multisig address the subsigs' keys and threshold correspond to.
+    pub fn address(&self) -> MultisigAddress {
+        MultisigAddress {
+            version: self.version,
+            threshold: self.threshold,
+            public_keys: self.subsigs.iter().map(|subsig| subsig.key).collect(),
+        }
+    }
+
+    /// Checks that the present signatures are valid and at least threshold are present.
     fn verify_subsigs(&self, message: &[u8]) -> bool {
-        self.subsigs
-            .iter()
-            .filter(|subsig| {
-                subsig
-                    .sig
-                    .map(|sig| subsig.key.verify(message, &sig))
-                    .unwrap_or(false) // not signed yet
-            })
-            .count()
-            == self.threshold as usize
+        let mut signed = 0;
+        for subsig in &self.subsigs {
+            if let Some(sig) = &subsig.sig {
+                if !subsig.key.verify(message, sig) {
+                    return false;
+                }
+                signed += 1;
+            }
+        }
+        signed >= self.threshold as usize
     }
 }
 
@@ -82,3 +102,90 @@ impl Serialize for MultisigSubsig {
         state.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    const MESSAGE: &[u8] = b"off-chain message";
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn public_key(key_pair: &Ed25519KeyPair) -> Ed25519PublicKey {
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(key_pair.public_key().as_ref());
+        Ed25519PublicKey(bytes)
+    }
+
+    fn sign(key_pair: &Ed25519KeyPair) -> Option<Signature> {
+        let mut bytes = [0; 64];
+        bytes.copy_from_slice(key_pair.sign(MESSAGE).as_ref());
+        Some(Signature(bytes))
+    }
+
+    /// A 2-of-3 multisig address and unsigned multisig over its keys.
+    fn two_of_three(keys: &[Ed25519KeyPair]) -> (MultisigAddress, MultisigSignature) {
+        let addresses: Vec<Address> = keys.iter().map(|k| Address(public_key(k).0)).collect();
+        let address = MultisigAddress::new(MULTISIG_VERSION, 2, &addresses).unwrap();
+        let msig = MultisigSignature {
+            subsigs: keys
+                .iter()
+                .map(|k| MultisigSubsig {
+                    key: public_key(k),
+                    sig: None,
+                })
+                .collect(),
+            threshold: 2,
+            version: MULTISIG_VERSION,
+        };
+        (address, msig)
+    }
+
+    #[test]
+    fn test_verify_two_of_three() {
+        let keys = [key_pair(), key_pair(), key_pair()];
+        let (address, mut msig) = two_of_three(&keys);
+        msig.subsigs[0].sig = sign(&keys[0]);
+        msig.subsigs[2].sig = sign(&keys[2]);
+
+        assert!(msig.verify(&address, MESSAGE));
+        assert!(!msig.verify(&address, b"other message"));
+    }
+
+    #[test]
+    fn test_verify_below_threshold() {
+        let keys = [key_pair(), key_pair(), key_pair()];
+        let (address, mut msig) = two_of_three(&keys);
+        msig.subsigs[1].sig = sign(&keys[1]);
+
+        assert!(!msig.verify(&address, MESSAGE));
+    }
+
+    #[test]
+    fn test_verify_rejects_subsig_from_wrong_key() {
+        let keys = [key_pair(), key_pair(), key_pair()];
+        let (address, mut msig) = two_of_three(&keys);
+        msig.subsigs[0].sig = sign(&keys[0]);
+        msig.subsigs[1].sig = sign(&key_pair());
+        msig.subsigs[2].sig = sign(&keys[2]);
+
+        assert!(!msig.verify(&address, MESSAGE));
+
+        // Nor is a subsig accepted from a key of another address.
+        let other = key_pair();
+        let (_, mut msig) = two_of_three(&keys);
+        msig.subsigs[0].sig = sign(&keys[0]);
+        msig.subsigs[1] = MultisigSubsig {
+            key: public_key(&other),
+            sig: sign(&other),
+        };
+
+        assert!(!msig.verify(&address, MESSAGE));
+    }
+}
@@ -7,6 +7,8 @@ use thiserror::Error;
 pub enum CryptoError {
     #[display(fmt = "Key length is invalid.")]
     InvalidKeyLength,
+    #[display(fmt = "Signature length is invalid.")]
+    InvalidSignatureLength,
     #[display(fmt = "Mnemonic length is invalid.")]
     InvalidMnemonicLength,
     #[display(fmt = "Mnemonic contains invalid words.")]
@@ -2,6 +2,7 @@ use std::fmt::{self, Formatter};
 
 use algonaut_encoding::{deserialize_bytes32, SignatureVisitor, U8_32Visitor};
 use data_encoding::{BASE32_NOPAD, BASE64};
+use error::CryptoError;
 use fmt::Debug;
 use ring::signature::UnparsedPublicKey;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -39,6 +40,18 @@ pub struct MasterDerivationKey(pub [u8; 32]);
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct Signature(pub [u8; 64]);
 
+impl Signature {
+    /// Wraps a raw 64 bytes signature, e.g. returned by a remote signer.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, CryptoError> {
+        let mut signature = [0; 64];
+        if bytes.len() != signature.len() {
+            return Err(CryptoError::InvalidSignatureLength);
+        }
+        signature.copy_from_slice(bytes);
+        Ok(Signature(signature))
+    }
+}
+
 impl Debug for Signature {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", &BASE64.encode(&self.0))
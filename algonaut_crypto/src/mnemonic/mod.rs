@@ -1,6 +1,7 @@
 use crate::error::CryptoError;
 use sha2::Digest;
 use static_assertions::const_assert_eq;
+use std::fmt;
 
 mod wordlist;
 
@@ -62,6 +63,47 @@ pub fn to_key(string: &str) -> Result<[u8; KEY_LEN_BYTES], CryptoError> {
     Ok(key)
 }
 
+/// A mnemonic that doesn't reveal its words when formatted with `Display` or `Debug`, so it can
+/// be kept in structs that are logged.
+///
+/// The errors returned by [to_key] never contain the passed mnemonic either.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RedactedMnemonic(String);
+
+impl RedactedMnemonic {
+    pub fn new(mnemonic: String) -> Self {
+        RedactedMnemonic(mnemonic)
+    }
+
+    /// The mnemonic words, in clear.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// See [to_key].
+    pub fn to_key(&self) -> Result<[u8; KEY_LEN_BYTES], CryptoError> {
+        to_key(&self.0)
+    }
+}
+
+impl From<String> for RedactedMnemonic {
+    fn from(mnemonic: String) -> Self {
+        RedactedMnemonic::new(mnemonic)
+    }
+}
+
+impl fmt::Display for RedactedMnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted {}-word mnemonic>", MNEM_LEN_WORDS)
+    }
+}
+
+impl fmt::Debug for RedactedMnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 // Returns a word corresponding to the 11 bit checksum of the data
 fn checksum(data: &[u8]) -> &str {
     let d = ChecksumAlg::digest(data);
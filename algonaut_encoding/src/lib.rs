@@ -78,6 +78,17 @@ where
     Ok(BASE64.decode(s.as_bytes()).unwrap())
 }
 
+pub fn deserialize_bytes_vec<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+    <Vec<&str>>::deserialize(deserializer)?
+        .into_iter()
+        .map(|s| BASE64.decode(s.as_bytes()).map_err(D::Error::custom))
+        .collect()
+}
+
 pub fn serialize_bytes<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
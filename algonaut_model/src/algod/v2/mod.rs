@@ -1,8 +1,10 @@
-use algonaut_core::{CompiledTeal, MicroAlgos, Round};
+use algonaut_core::{Address, AppId, AssetId, CompiledTeal, MicroAlgos, Round};
 use algonaut_crypto::{deserialize_hash, HashDigest};
-use algonaut_encoding::deserialize_bytes;
-use data_encoding::BASE64;
-use serde::{Deserialize, Deserializer, Serialize};
+use algonaut_encoding::{deserialize_bytes, deserialize_bytes_vec, serialize_bytes};
+use data_encoding::{BASE64, HEXLOWER};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Account {
@@ -14,7 +16,7 @@ pub struct Account {
 
     /// Specifies the amount of MicroAlgos in the account, without the pending rewards.
     #[serde(rename = "amount-without-pending-rewards")]
-    pub amount_without_pending_rewards: u64,
+    pub amount_without_pending_rewards: MicroAlgos,
 
     /// `appl` applications local data stored in this account.
     #[serde(rename = "apps-local-state")]
@@ -48,6 +50,11 @@ pub struct Account {
     #[serde(rename = "created-assets")]
     pub created_assets: Option<Vec<Asset>>,
 
+    /// Minimum balance the account has to keep, given its assets, applications and boxes.
+    /// Zero for nodes that don't report it.
+    #[serde(rename = "min-balance", default)]
+    pub min_balance: MicroAlgos,
+
     ///
     pub participation: Option<AccountParticipation>,
 
@@ -78,6 +85,106 @@ pub struct Account {
     /// * Online - indicates that the associated account used as part of the delegation pool.
     /// * NotParticipating - indicates that the associated account is neither a delegator nor a delegate.
     pub status: String,
+
+    /// The count of all applications that have been opted in, equivalent to the count of
+    /// application local data (AppLocalState objects) stored in this account.
+    #[serde(rename = "total-apps-opted-in")]
+    pub total_apps_opted_in: Option<u64>,
+
+    /// The count of all assets that have been opted in, equivalent to the count of AssetHolding
+    /// objects held by this account.
+    #[serde(rename = "total-assets-opted-in")]
+    pub total_assets_opted_in: Option<u64>,
+
+    /// For app accounts only, the number of boxes of the application.
+    #[serde(rename = "total-boxes")]
+    pub total_boxes: Option<u64>,
+
+    /// For app accounts only, the total bytes (names and values) of the application's boxes.
+    #[serde(rename = "total-box-bytes")]
+    pub total_box_bytes: Option<u64>,
+
+    /// The count of all apps (AppParams objects) created by this account.
+    #[serde(rename = "total-created-apps")]
+    pub total_created_apps: Option<u64>,
+
+    /// The count of all assets (AssetParams objects) created by this account.
+    #[serde(rename = "total-created-assets")]
+    pub total_created_assets: Option<u64>,
+}
+
+impl Account {
+    /// Whether the inline asset and application lists don't contain everything reported by the
+    /// totals, e.g. because the account holds more resources than the node returns in a single
+    /// response. In that case the per-asset / per-application endpoints have to be used.
+    pub fn has_incomplete_resources(&self) -> bool {
+        fn incomplete<T>(list: &Option<Vec<T>>, total: Option<u64>) -> bool {
+            let len = list.as_ref().map(|l| l.len() as u64).unwrap_or(0);
+            total.map(|total| total > len).unwrap_or(false)
+        }
+        incomplete(&self.assets, self.total_assets_opted_in)
+            || incomplete(&self.created_assets, self.total_created_assets)
+            || incomplete(&self.apps_local_state, self.total_apps_opted_in)
+            || incomplete(&self.created_apps, self.total_created_apps)
+    }
+
+    /// Ids of the applications the account is opted into (has local state for).
+    pub fn opted_in_app_ids(&self) -> Vec<AppId> {
+        self.apps_local_state
+            .iter()
+            .flatten()
+            .map(|state| AppId(state.id))
+            .collect()
+    }
+
+    /// Ids of the assets the account holds (is opted into), including zero balance holdings.
+    pub fn held_asset_ids(&self) -> Vec<AssetId> {
+        self.assets
+            .iter()
+            .flatten()
+            .map(|holding| AssetId(holding.asset_id))
+            .collect()
+    }
+
+    /// The typed delegation [status](Self::status), `None` if it isn't a known status.
+    pub fn status(&self) -> Option<AccountStatus> {
+        match self.status.as_str() {
+            "Offline" => Some(AccountStatus::Offline),
+            "Online" => Some(AccountStatus::Online),
+            "NotParticipating" => Some(AccountStatus::NotParticipating),
+            _ => None,
+        }
+    }
+}
+
+/// Delegation status of an account's MicroAlgos.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AccountStatus {
+    /// The account is delegated.
+    Offline,
+    /// The account is part of the delegation pool.
+    Online,
+    /// The account is neither a delegator nor a delegate, see
+    /// [nonparticipating](https://developer.algorand.org/docs/get-details/transactions/#nonparticipating).
+    /// It doesn't earn rewards.
+    NotParticipating,
+}
+
+/// MicroAlgos per reward unit: rewards accrue per whole Algo held.
+const REWARD_UNIT: u64 = 1_000_000;
+
+/// The rewards accrued by the account since its last update and not yet added to its balance,
+/// given the current rewards level (e.g. a block header's `earn`).
+///
+/// Accounts that don't participate in consensus rewards accrue nothing. `None` if the rewards
+/// overflow, e.g. because the account's reward base is stale or missing.
+pub fn pending_rewards(account: &Account, current_rewards_level: u64) -> Option<MicroAlgos> {
+    if account.status() == Some(AccountStatus::NotParticipating) {
+        return Some(MicroAlgos(0));
+    }
+    let reward_units = account.amount_without_pending_rewards.0 / REWARD_UNIT;
+    let level_delta = current_rewards_level.saturating_sub(account.reward_base.unwrap_or(0));
+    reward_units.checked_mul(level_delta).map(MicroAlgos)
 }
 
 /// Signature types.
@@ -104,6 +211,16 @@ pub struct AccountParticipation {
     )]
     pub selection_participation_key: Vec<u8>,
 
+    /// `stprf` Root of the state proof key (if any).
+    /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
+    #[serde(
+        rename = "state-proof-key",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub state_proof_key: Vec<u8>,
+
     /// `voteFst` First round for which this participation is valid.
     #[serde(rename = "vote-first-valid")]
     pub vote_first_valid: u64,
@@ -127,8 +244,41 @@ pub struct AccountParticipation {
     pub vote_participation_key: Vec<u8>,
 }
 
-/// Application state delta.
+/// Represents a participation key used by the node.
 #[derive(Debug, Serialize, Deserialize)]
+pub struct ParticipationKey {
+    /// The key's ParticipationID.
+    pub id: String,
+
+    /// Address the key was generated for.
+    pub address: String,
+
+    /// When registered, this is the first round it may be used.
+    #[serde(rename = "effective-first-valid")]
+    pub effective_first_valid: Option<Round>,
+
+    /// When registered, this is the last round it may be used.
+    #[serde(rename = "effective-last-valid")]
+    pub effective_last_valid: Option<Round>,
+
+    /// Key information stored on the account.
+    pub key: AccountParticipation,
+
+    /// Round when this key was last used to propose a block.
+    #[serde(rename = "last-block-proposal")]
+    pub last_block_proposal: Option<Round>,
+
+    /// Round when this key was last used to generate a state proof.
+    #[serde(rename = "last-state-proof")]
+    pub last_state_proof: Option<Round>,
+
+    /// Round when this key was last used to vote.
+    #[serde(rename = "last-vote")]
+    pub last_vote: Option<Round>,
+}
+
+/// Application state delta.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AccountStateDelta {
     /// Address
     pub address: String,
@@ -154,11 +304,10 @@ pub struct ApplicationLocalState {
     pub id: u64,
 
     /// `tkv` storage.
-    #[serde(rename = "key-value")]
+    #[serde(rename = "key-value", default)]
     pub key_value: TealKeyValueStore,
 
     /// `hsch` schema.
-    #[serde(rename = "key-value")]
     pub schema: ApplicationStateSchema,
 }
 
@@ -170,20 +319,22 @@ pub struct ApplicationParams {
     #[serde(
         rename = "approval-program",
         default,
-        skip_serializing_if = "Vec::is_empty",
-        deserialize_with = "deserialize_bytes"
+        skip_serializing_if = "is_empty_program",
+        serialize_with = "serialize_compiled_teal",
+        deserialize_with = "deserialize_compiled_teal"
     )]
-    pub approval_program: Vec<u8>,
+    pub approval_program: CompiledTeal,
 
     /// `clearp` approval program.
     /// Pattern : "^(?:[A-Za-z0-9+/]{4})*(?:[A-Za-z0-9+/]{2}==\|[A-Za-z0-9+/]{3}=)?$"
     #[serde(
         rename = "clear-state-program",
         default,
-        skip_serializing_if = "Vec::is_empty",
-        deserialize_with = "deserialize_bytes"
+        skip_serializing_if = "is_empty_program",
+        serialize_with = "serialize_compiled_teal",
+        deserialize_with = "deserialize_compiled_teal"
     )]
-    pub clear_state_program: Vec<u8>,
+    pub clear_state_program: CompiledTeal,
 
     /// The address that created this application. This is the address where the parameters and
     /// global state for this application can be found.
@@ -203,6 +354,55 @@ pub struct ApplicationParams {
     pub local_state_schema: ApplicationStateSchema,
 }
 
+impl Application {
+    /// Hash of the approval program, in address form like the `hash` returned when compiling
+    /// (see [CompiledTealWithHash]): compare them to check that the deployed program matches the
+    /// source.
+    pub fn approval_hash(&self) -> Address {
+        self.params.approval_program.address()
+    }
+
+    /// Hash of the clear state program, see [approval_hash](Self::approval_hash).
+    pub fn clear_hash(&self) -> Address {
+        self.params.clear_state_program.address()
+    }
+
+    /// The global state, with the keys decoded: as UTF-8 when valid, as hex otherwise.
+    pub fn global_state_decoded(&self) -> HashMap<String, TealValue> {
+        self.params
+            .global_state
+            .iter()
+            .map(|kv| (decode_state_key(&kv.key), kv.value.clone()))
+            .collect()
+    }
+}
+
+/// Decodes a base64 state key, to UTF-8 if valid and to hex otherwise.
+fn decode_state_key(key: &str) -> String {
+    match BASE64.decode(key.as_bytes()) {
+        Ok(bytes) => String::from_utf8(bytes).unwrap_or_else(|e| HEXLOWER.encode(e.as_bytes())),
+        Err(_) => key.to_owned(),
+    }
+}
+
+fn is_empty_program(program: &CompiledTeal) -> bool {
+    program.0.is_empty()
+}
+
+fn serialize_compiled_teal<S>(program: &CompiledTeal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_bytes(&program.0, serializer)
+}
+
+fn deserialize_compiled_teal<'de, D>(deserializer: D) -> Result<CompiledTeal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(CompiledTeal(deserialize_bytes(deserializer)?))
+}
+
 /// Specifies maximums on the number of each type that may be stored.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApplicationStateSchema {
@@ -364,6 +564,39 @@ pub struct DryrunSource {
     pub txn_index: u64,
 }
 
+impl DryrunSource {
+    /// Approval program source of application `app_index` (of the request's `apps`).
+    pub fn approval_program(app_index: u64, source: &str) -> Self {
+        Self::new("approv", app_index, 0, source)
+    }
+
+    /// Clear state program source of application `app_index` (of the request's `apps`).
+    pub fn clear_state_program(app_index: u64, source: &str) -> Self {
+        Self::new("clearp", app_index, 0, source)
+    }
+
+    /// Logic signature source of the transaction at `txn_index` (of the request's `txns`).
+    pub fn logic_sig(txn_index: u64, source: &str) -> Self {
+        Self::new("lsig", 0, txn_index, source)
+    }
+
+    /// Sets the index of the transaction the source belongs to, e.g. the application call
+    /// creating the application.
+    pub fn txn_index(mut self, txn_index: u64) -> Self {
+        self.txn_index = txn_index;
+        self
+    }
+
+    fn new(field_name: &str, app_index: u64, txn_index: u64, source: &str) -> Self {
+        DryrunSource {
+            app_index,
+            field_name: field_name.to_owned(),
+            source: source.to_owned(),
+            txn_index,
+        }
+    }
+}
+
 /// Stores the TEAL eval step data
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DryrunState {
@@ -427,29 +660,114 @@ pub struct ErrorResponse<T> {
     pub message: String,
 }
 
-/// Represents a TEAL value delta.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct EvalDelta {
-    /// `at` delta action.
-    pub action: u64,
+/// A change of an application state value.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ApiEvalDelta", into = "ApiEvalDelta")]
+pub enum EvalDelta {
+    SetBytes(Vec<u8>),
+    SetUint(u64),
+    Delete,
+}
+
+/// Represents a TEAL value delta, as returned by the API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApiEvalDelta {
+    /// `at` delta action: 1 set bytes, 2 set uint, 3 delete.
+    action: u64,
 
     /// `bs` bytes value.
-    pub bytes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bytes: Option<String>,
 
     /// `ui` uint value.
-    pub uint: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uint: Option<u64>,
+}
+
+impl TryFrom<ApiEvalDelta> for EvalDelta {
+    type Error = String;
+
+    fn try_from(api_delta: ApiEvalDelta) -> Result<Self, Self::Error> {
+        match api_delta.action {
+            1 => Ok(EvalDelta::SetBytes(
+                BASE64
+                    .decode(api_delta.bytes.unwrap_or_default().as_bytes())
+                    .map_err(|e| e.to_string())?,
+            )),
+            2 => Ok(EvalDelta::SetUint(api_delta.uint.unwrap_or_default())),
+            3 => Ok(EvalDelta::Delete),
+            action => Err(format!("Invalid delta action: {}", action)),
+        }
+    }
+}
+
+impl From<EvalDelta> for ApiEvalDelta {
+    fn from(delta: EvalDelta) -> Self {
+        match delta {
+            EvalDelta::SetBytes(bytes) => ApiEvalDelta {
+                action: 1,
+                bytes: Some(BASE64.encode(&bytes)),
+                uint: None,
+            },
+            EvalDelta::SetUint(uint) => ApiEvalDelta {
+                action: 2,
+                bytes: None,
+                uint: Some(uint),
+            },
+            EvalDelta::Delete => ApiEvalDelta {
+                action: 3,
+                bytes: None,
+                uint: None,
+            },
+        }
+    }
 }
 
 /// Key-value pairs for StateDelta.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct EvalDeltaKeyValue {
-    pub key: String,
+    #[serde(
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub key: Vec<u8>,
     pub value: EvalDelta,
 }
 
 /// Application state delta.
 pub type StateDelta = Vec<EvalDeltaKeyValue>;
 
+/// Applies the changes of `delta` to an application state, keyed by the raw keys.
+pub fn apply_state_delta(state: &mut BTreeMap<Vec<u8>, TealValue>, delta: &[EvalDeltaKeyValue]) {
+    for change in delta {
+        match &change.value {
+            EvalDelta::SetBytes(bytes) => {
+                state.insert(
+                    change.key.clone(),
+                    TealValue {
+                        bytes: bytes.clone(),
+                        value_type: 1,
+                        uint: 0,
+                    },
+                );
+            }
+            EvalDelta::SetUint(uint) => {
+                state.insert(
+                    change.key.clone(),
+                    TealValue {
+                        bytes: vec![],
+                        value_type: 2,
+                        uint: *uint,
+                    },
+                );
+            }
+            EvalDelta::Delete => {
+                state.remove(&change.key);
+            }
+        }
+    }
+}
+
 /// Represents a key-value pair in an application store.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TealKeyValue {
@@ -461,7 +779,7 @@ pub struct TealKeyValue {
 pub type TealKeyValueStore = Vec<TealKeyValue>;
 
 /// Represents a TEAL value.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TealValue {
     /// `tb` bytes value.
     #[serde(
@@ -494,15 +812,545 @@ pub struct Version {
     pub versions: Vec<String>,
 }
 
-/// Version contains the current algod version.
+impl Version {
+    pub fn node_info(self) -> NodeInfo {
+        NodeInfo {
+            build_version: format!(
+                "{}.{}.{}",
+                self.build.major, self.build.minor, self.build.build_number
+            ),
+            channel: self.build.channel,
+            commit_hash: self.build.commit_hash,
+            genesis_id: self.genesis_id,
+            genesis_hash: self.genesis_hash_b64,
+            api_versions: self.versions,
+        }
+    }
+}
+
+/// Summary of a node's build and network, for diagnostics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeInfo {
+    /// `major.minor.build_number`, e.g. "3.9.4".
+    pub build_version: String,
+    /// Release channel, e.g. "stable" or "beta".
+    pub channel: String,
+    pub commit_hash: String,
+    pub genesis_id: String,
+    pub genesis_hash: HashDigest,
+    /// Supported API versions, e.g. "v2".
+    pub api_versions: Vec<String>,
+}
+
+/// The genesis of the network the node is connected to.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GenesisBlock {
-    pub addr: Option<String>,
+    /// Accounts funded at genesis.
+    #[serde(default)]
+    pub alloc: Vec<GenesisAllocation>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// Whether the network runs in dev mode (a block per transaction).
+    #[serde(default, rename = "devmode")]
+    pub dev_mode: bool,
+
+    /// Fee sink address.
+    #[serde(with = "address_string")]
+    pub fees: Address,
+
+    pub id: String,
+
+    pub network: String,
+
+    /// Consensus protocol version at genesis.
+    pub proto: String,
+
+    /// Rewards pool address.
+    #[serde(with = "address_string")]
+    pub rwd: Address,
+
+    #[serde(default)]
+    pub timestamp: i64,
+}
+
+impl GenesisBlock {
+    /// Address and initial stake of the accounts funded at genesis.
+    pub fn allocations(&self) -> Vec<(Address, MicroAlgos)> {
+        self.alloc
+            .iter()
+            .map(|alloc| (alloc.address, alloc.state.algo))
+            .collect()
+    }
+
+    /// Address collecting the transaction fees.
+    pub fn fee_sink(&self) -> Address {
+        self.fees
+    }
+
+    /// Address the participation rewards are paid from.
+    pub fn rewards_pool(&self) -> Address {
+        self.rwd
+    }
+
+    pub fn special_addresses(&self) -> SpecialAddresses {
+        SpecialAddresses {
+            fee_sink: self.fee_sink(),
+            rewards_pool: self.rewards_pool(),
+        }
+    }
+}
+
+/// The special accounts of a network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpecialAddresses {
+    /// Address collecting the transaction fees.
+    pub fee_sink: Address,
+    /// Address the participation rewards are paid from.
+    pub rewards_pool: Address,
+}
+
+/// An account funded at genesis.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenesisAllocation {
+    #[serde(rename = "addr", with = "address_string")]
+    pub address: Address,
+
+    /// Name of the account, e.g. "FeeSink", "RewardsPool" or "Wallet1".
+    #[serde(default)]
+    pub comment: String,
+
+    pub state: GenesisAccountState,
 }
 
-/// A transaction.
+/// State of an account at genesis.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct Transaction {}
+pub struct GenesisAccountState {
+    /// Initial stake.
+    #[serde(default)]
+    pub algo: MicroAlgos,
+
+    /// Participation status: 0 offline, 1 online, 2 not participating.
+    #[serde(default)]
+    pub onl: u8,
+
+    /// Participation public key (base64), for online accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vote: Option<String>,
+
+    /// Selection public key (base64), for online accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sel: Option<String>,
+
+    #[serde(default, rename = "voteFst", skip_serializing_if = "Option::is_none")]
+    pub vote_first: Option<Round>,
+
+    #[serde(default, rename = "voteLst", skip_serializing_if = "Option::is_none")]
+    pub vote_last: Option<Round>,
+
+    #[serde(default, rename = "voteKD", skip_serializing_if = "Option::is_none")]
+    pub vote_key_dilution: Option<u64>,
+}
+
+/// (De)serializes an address as its base32 string.
+mod address_string {
+    use algonaut_core::Address;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(address: &Address, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&address.to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Address, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A (signed) transaction.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Transaction {
+    /// The fields of the transaction needed to read back the assets and applications it creates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub txn: Option<TransactionFields>,
+}
+
+/// Fields of a [Transaction].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TransactionFields {
+    /// `snd` The sender, the creator of the assets and applications created by the transaction.
+    #[serde(rename = "snd", default)]
+    pub sender: String,
+
+    /// `apar` The params of an asset configuration.
+    #[serde(rename = "apar", default, skip_serializing_if = "Option::is_none")]
+    pub asset_params: Option<TransactionAssetParams>,
+
+    /// `apap` The approval program of an application call.
+    #[serde(
+        rename = "apap",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub approval_program: Vec<u8>,
+
+    /// `apsu` The clear state program of an application call.
+    #[serde(
+        rename = "apsu",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub clear_state_program: Vec<u8>,
+
+    /// `apgs` The global schema of an application call.
+    #[serde(rename = "apgs", default, skip_serializing_if = "Option::is_none")]
+    pub global_state_schema: Option<TransactionStateSchema>,
+
+    /// `apls` The local schema of an application call.
+    #[serde(rename = "apls", default, skip_serializing_if = "Option::is_none")]
+    pub local_state_schema: Option<TransactionStateSchema>,
+}
+
+/// The params of an asset configuration, see [AssetParams].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TransactionAssetParams {
+    #[serde(rename = "c", default)]
+    pub clawback: String,
+
+    #[serde(rename = "dc", default)]
+    pub decimals: u64,
+
+    #[serde(rename = "df", default)]
+    pub default_frozen: bool,
+
+    #[serde(rename = "f", default)]
+    pub freeze: String,
+
+    #[serde(rename = "m", default)]
+    pub manager: String,
+
+    #[serde(
+        rename = "am",
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub metadata_hash: Vec<u8>,
+
+    #[serde(rename = "an", default)]
+    pub name: String,
+
+    #[serde(rename = "r", default)]
+    pub reserve: String,
+
+    #[serde(rename = "t", default)]
+    pub total: u64,
+
+    #[serde(rename = "un", default)]
+    pub unit_name: String,
+
+    #[serde(rename = "au", default)]
+    pub url: String,
+}
+
+/// The schema of an application call, see [ApplicationStateSchema].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TransactionStateSchema {
+    #[serde(rename = "nbs", default)]
+    pub num_byte_slice: u64,
+
+    #[serde(rename = "nui", default)]
+    pub num_uint: u64,
+}
+
+impl From<&TransactionStateSchema> for ApplicationStateSchema {
+    fn from(schema: &TransactionStateSchema) -> Self {
+        ApplicationStateSchema {
+            num_byte_slice: schema.num_byte_slice,
+            num_uint: schema.num_uint,
+        }
+    }
+}
+
+/// The changes to the ledger made by the block of a round (`/v2/deltas/{round}`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerStateDelta {
+    /// The accounts, and their applications and assets, changed in the round.
+    #[serde(rename = "Accts")]
+    pub accounts: AccountDeltas,
+
+    /// Assets and applications created or deleted in the round, by id.
+    #[serde(rename = "Creatables", default)]
+    pub creatables: Option<HashMap<u64, ModifiedCreatable>>,
+
+    /// The transactions of the round, by id.
+    #[serde(rename = "Txids", default)]
+    pub tx_ids: Option<HashMap<String, IncludedTransaction>>,
+
+    /// Timestamp of the previous block.
+    #[serde(rename = "PrevTimestamp", default)]
+    pub prev_timestamp: i64,
+
+    /// Next round for which a state proof is expected.
+    #[serde(rename = "StateProofNext", default)]
+    pub state_proof_next: u64,
+}
+
+/// The changes to accounts of a [LedgerStateDelta].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDeltas {
+    /// The new (full) base data of the changed accounts.
+    #[serde(rename = "Accts", default)]
+    pub accounts: Option<Vec<BalanceRecord>>,
+
+    #[serde(rename = "AppResources", default)]
+    pub app_resources: Option<Vec<AppResourceRecord>>,
+
+    #[serde(rename = "AssetResources", default)]
+    pub asset_resources: Option<Vec<AssetResourceRecord>>,
+}
+
+/// The base data of an account after a round.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceRecord {
+    #[serde(rename = "Addr", with = "address_string")]
+    pub address: Address,
+
+    #[serde(rename = "AccountData")]
+    pub account_data: DeltaAccountData,
+}
+
+/// The base data of an account, as stored in the ledger.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaAccountData {
+    /// Delegation status: 0 offline, 1 online, 2 not participating.
+    #[serde(rename = "onl", default)]
+    pub status: u8,
+
+    #[serde(rename = "algo", default)]
+    pub micro_algos: MicroAlgos,
+
+    #[serde(rename = "ebase", default)]
+    pub rewards_base: u64,
+
+    /// Total rewards earned.
+    #[serde(rename = "ern", default)]
+    pub rewarded_micro_algos: MicroAlgos,
+
+    /// The address the account is rekeyed to, if it is.
+    #[serde(rename = "spend", default, skip_serializing_if = "Option::is_none")]
+    pub auth_addr: Option<String>,
+
+    #[serde(rename = "tapl", default)]
+    pub total_apps_opted_in: u64,
+
+    #[serde(rename = "tas", default)]
+    pub total_assets_opted_in: u64,
+
+    #[serde(rename = "tbx", default)]
+    pub total_boxes: u64,
+
+    #[serde(rename = "tbxb", default)]
+    pub total_box_bytes: u64,
+}
+
+/// The change of an application of an account: its params if the account is the creator, its
+/// local state if the account is opted in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppResourceRecord {
+    #[serde(rename = "Aidx")]
+    pub app_id: u64,
+
+    #[serde(rename = "Addr", with = "address_string")]
+    pub address: Address,
+
+    #[serde(rename = "Params", default)]
+    pub params: AppParamsDelta,
+
+    #[serde(rename = "State", default)]
+    pub state: AppLocalStateDelta,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppParamsDelta {
+    #[serde(rename = "Deleted", default)]
+    pub deleted: bool,
+
+    /// The new params, `None` if they didn't change.
+    #[serde(rename = "Params", default)]
+    pub params: Option<DeltaAppParams>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaAppParams {
+    /// The global state, by key.
+    #[serde(rename = "gs", default)]
+    pub global_state: Option<HashMap<String, DeltaTealValue>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppLocalStateDelta {
+    #[serde(rename = "Deleted", default)]
+    pub deleted: bool,
+
+    /// The new local state, `None` if it didn't change.
+    #[serde(rename = "LocalState", default)]
+    pub local_state: Option<DeltaAppLocalState>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaAppLocalState {
+    /// The local state, by key.
+    #[serde(rename = "tkv", default)]
+    pub key_value: Option<HashMap<String, DeltaTealValue>>,
+}
+
+/// A TEAL value of a [LedgerStateDelta].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaTealValue {
+    /// 1 for bytes, 2 for uint.
+    #[serde(rename = "tt", default)]
+    pub value_type: u64,
+
+    #[serde(rename = "tb", default)]
+    pub bytes: String,
+
+    #[serde(rename = "ui", default)]
+    pub uint: u64,
+}
+
+/// The change of an asset of an account: its params if the account is the creator, its holding
+/// if the account is opted in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetResourceRecord {
+    #[serde(rename = "Aidx")]
+    pub asset_id: u64,
+
+    #[serde(rename = "Addr", with = "address_string")]
+    pub address: Address,
+
+    #[serde(rename = "Holding", default)]
+    pub holding: AssetHoldingDelta,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetHoldingDelta {
+    /// Whether the account opted out of the asset.
+    #[serde(rename = "Deleted", default)]
+    pub deleted: bool,
+
+    /// The new holding, `None` if it didn't change.
+    #[serde(rename = "Holding", default)]
+    pub holding: Option<DeltaAssetHolding>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaAssetHolding {
+    #[serde(rename = "a", default)]
+    pub amount: u64,
+
+    #[serde(rename = "f", default)]
+    pub frozen: bool,
+}
+
+/// An asset or application created or deleted in a round.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModifiedCreatable {
+    /// 0 for assets, 1 for applications.
+    #[serde(rename = "Ctype")]
+    pub creatable_type: u64,
+
+    /// `false` if it was deleted.
+    #[serde(rename = "Created")]
+    pub created: bool,
+
+    #[serde(rename = "Creator", with = "address_string")]
+    pub creator: Address,
+}
+
+/// A transaction of a [LedgerStateDelta].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncludedTransaction {
+    #[serde(rename = "LastValid")]
+    pub last_valid: Round,
+
+    /// Position of the transaction in the block.
+    #[serde(rename = "Intra")]
+    pub intra: u64,
+}
+
+/// A state proof, attesting a range of rounds to light clients (e.g. bridges) that track the
+/// network's online stake but not the blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProof {
+    /// The attested message.
+    #[serde(rename = "Message")]
+    pub message: StateProofMessage,
+
+    /// The msgpack encoded proof, signed by the voters of [StateProofMessage::voters_commitment].
+    #[serde(
+        rename = "StateProof",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub state_proof: Vec<u8>,
+}
+
+/// The message of a [StateProof].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProofMessage {
+    /// Vector commitment of the headers of the attested blocks.
+    #[serde(
+        rename = "BlockHeadersCommitment",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub block_headers_commitment: Vec<u8>,
+
+    /// First round attested by the proof.
+    #[serde(rename = "FirstAttestedRound")]
+    pub first_attested_round: Round,
+
+    /// Last round attested by the proof.
+    #[serde(rename = "LastAttestedRound")]
+    pub last_attested_round: Round,
+
+    /// Natural log of the proven weight, with 16 bits of precision. Used by verifiers of the
+    /// next state proof.
+    #[serde(rename = "LnProvenWeight")]
+    pub ln_proven_weight: u64,
+
+    /// Vector commitment of the online accounts that can sign the next state proof.
+    #[serde(
+        rename = "VotersCommitment",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub voters_commitment: Vec<u8>,
+}
+
+impl StateProofMessage {
+    /// Whether `round` is in the interval attested by the message.
+    pub fn attests(&self, round: Round) -> bool {
+        self.first_attested_round <= round && round <= self.last_attested_round
+    }
+}
 
 /// A potentially truncated list of transactions currently in the node's transaction pool.
 /// You can compute whether or not the list is truncated if the number of elements in the
@@ -531,7 +1379,7 @@ pub struct PendingTransaction {
 
     /// Rewards in microalgos applied to the close remainder to account.
     #[serde(rename = "close-rewards")]
-    pub close_rewards: Option<u64>,
+    pub close_rewards: Option<MicroAlgos>,
 
     /// Closing amount for the transaction.
     #[serde(rename = "closing-amount")]
@@ -551,6 +1399,14 @@ pub struct PendingTransaction {
     #[serde(rename = "local-state-delta")]
     pub local_state_delta: Option<Vec<AccountStateDelta>>,
 
+    /// `lg` Logs for the application being executed by this transaction.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_bytes_vec"
+    )]
+    pub logs: Vec<Vec<u8>>,
+
     /// Indicates that the transaction was kicked out of this node's transaction pool
     /// (and specifies why that happened). An empty string indicates the transaction
     /// wasn't kicked out of this node's txpool due to an error.
@@ -559,16 +1415,127 @@ pub struct PendingTransaction {
 
     /// Rewards in microalgos applied to the receiver account.
     #[serde(rename = "receiver-rewards")]
-    pub receiver_rewards: Option<u64>,
+    pub receiver_rewards: Option<MicroAlgos>,
 
     /// Rewards in microalgos applied to the sender account.
     #[serde(rename = "sender-rewards")]
-    pub sender_rewards: Option<u64>,
+    pub sender_rewards: Option<MicroAlgos>,
 
     /// The raw signed transaction.
     pub txn: Transaction,
 }
 
+impl PendingTransaction {
+    /// Why the node removed the transaction from its pool, if it did: the transaction won't be
+    /// confirmed.
+    pub fn pool_error(&self) -> Option<&str> {
+        Some(self.pool_error.as_str()).filter(|e| !e.is_empty())
+    }
+
+    /// The rewards credited to the accounts of the (confirmed) transaction.
+    pub fn rewards(&self) -> TransactionRewards {
+        TransactionRewards {
+            sender: self.sender_rewards.unwrap_or(MicroAlgos(0)),
+            receiver: self.receiver_rewards.unwrap_or(MicroAlgos(0)),
+            close: self.close_rewards.unwrap_or(MicroAlgos(0)),
+        }
+    }
+
+    /// The params of the asset created by the (confirmed) transaction, as stored on-chain under
+    /// [asset_index](Self::asset_index). `None` if the transaction didn't create an asset.
+    pub fn asset_params(&self) -> Option<AssetParams> {
+        self.asset_index?;
+        let txn = self.txn.txn.as_ref()?;
+        let params = txn.asset_params.as_ref()?;
+        Some(AssetParams {
+            clawback: params.clawback.clone(),
+            creator: txn.sender.clone(),
+            decimals: params.decimals,
+            default_frozen: params.default_frozen,
+            freeze: params.freeze.clone(),
+            manager: params.manager.clone(),
+            metadata_hash: params.metadata_hash.clone(),
+            name: params.name.clone(),
+            reserve: params.reserve.clone(),
+            total: params.total,
+            unit_name: params.unit_name.clone(),
+            url: params.url.clone(),
+        })
+    }
+
+    /// The params of the application created by the (confirmed) transaction, as stored on-chain
+    /// under [application_index](Self::application_index), with the global state set by the
+    /// creation call. `None` if the transaction didn't create an application.
+    pub fn application_params(&self) -> Option<ApplicationParams> {
+        self.application_index?;
+        let txn = self.txn.txn.as_ref()?;
+        let mut global_state = BTreeMap::new();
+        apply_state_delta(
+            &mut global_state,
+            self.global_state_delta.as_deref().unwrap_or(&[]),
+        );
+        let schema = |schema: &Option<TransactionStateSchema>| {
+            schema
+                .as_ref()
+                .map(ApplicationStateSchema::from)
+                .unwrap_or(ApplicationStateSchema {
+                    num_byte_slice: 0,
+                    num_uint: 0,
+                })
+        };
+        Some(ApplicationParams {
+            approval_program: CompiledTeal(txn.approval_program.clone()),
+            clear_state_program: CompiledTeal(txn.clear_state_program.clone()),
+            creator: txn.sender.clone(),
+            global_state: global_state
+                .into_iter()
+                .map(|(key, value)| TealKeyValue {
+                    key: BASE64.encode(&key),
+                    value,
+                })
+                .collect(),
+            global_state_schema: schema(&txn.global_state_schema),
+            local_state_schema: schema(&txn.local_state_schema),
+        })
+    }
+
+    /// The [logs](Self::logs), rendered for reading.
+    pub fn logs_decoded(&self) -> Vec<LogEntry> {
+        self.logs
+            .iter()
+            .map(|log| LogEntry::new(log.clone()))
+            .collect()
+    }
+}
+
+/// A log of an application call, with its text if it's valid UTF-8 and its hex encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub bytes: Vec<u8>,
+    /// `None` if the log isn't valid UTF-8, e.g. an ABI encoded value.
+    pub text: Option<String>,
+    pub hex: String,
+}
+
+impl LogEntry {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        LogEntry {
+            text: String::from_utf8(bytes.clone()).ok(),
+            hex: HEXLOWER.encode(&bytes),
+            bytes,
+        }
+    }
+}
+
+/// Rewards credited to the accounts of a transaction, when it updated their balances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionRewards {
+    pub sender: MicroAlgos,
+    pub receiver: MicroAlgos,
+    /// Credited to the close remainder to account.
+    pub close: MicroAlgos,
+}
+
 /// Information about the status of a node
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NodeStatus {
@@ -645,6 +1612,205 @@ pub struct Block {
     pub cert: Option<serde_json::Value>,
 }
 
+impl Block {
+    /// The typed certificate, if the response includes it.
+    pub fn certificate(&self) -> Result<Option<BlockCertificate>, serde_json::Error> {
+        self.cert
+            .as_ref()
+            .map(BlockCertificate::deserialize)
+            .transpose()
+    }
+}
+
+/// Certificate of agreement on a block: the votes of the committee on the block's proposal.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockCertificate {
+    /// The proposal agreed upon.
+    #[serde(rename = "prop")]
+    pub proposal: ProposalValue,
+
+    #[serde(rename = "rnd")]
+    pub round: u64,
+
+    #[serde(rename = "per", default)]
+    pub period: u64,
+
+    #[serde(default)]
+    pub step: u64,
+
+    /// Votes for the proposal.
+    #[serde(rename = "vote", default, skip_serializing_if = "Vec::is_empty")]
+    pub votes: Vec<CertificateVote>,
+
+    /// Votes of senders that voted for two different proposals.
+    #[serde(rename = "eqv", default, skip_serializing_if = "Vec::is_empty")]
+    pub equivocation_votes: Vec<EquivocationVote>,
+}
+
+impl BlockCertificate {
+    /// Address of the account that proposed the block.
+    pub fn proposer(&self) -> Option<Address> {
+        self.proposal.original_proposer()
+    }
+}
+
+/// A proposed block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalValue {
+    /// Digest of the block.
+    #[serde(
+        rename = "dig",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub block_digest: Vec<u8>,
+
+    /// Digest of the encoded proposal.
+    #[serde(
+        rename = "encdig",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub encoding_digest: Vec<u8>,
+
+    /// Period in which the block was originally proposed.
+    #[serde(rename = "oper", default)]
+    pub original_period: u64,
+
+    /// Public key of the account that originally proposed the block.
+    #[serde(
+        rename = "oprop",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub original_proposer: Vec<u8>,
+}
+
+impl ProposalValue {
+    pub fn original_proposer(&self) -> Option<Address> {
+        address_from_bytes(&self.original_proposer)
+    }
+}
+
+/// A vote of a committee member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateVote {
+    /// Public key of the voter.
+    #[serde(
+        rename = "snd",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub sender: Vec<u8>,
+
+    /// Proof of the voter's selection in the committee.
+    #[serde(rename = "cred")]
+    pub credential: VoteCredential,
+
+    #[serde(rename = "sig")]
+    pub signature: OneTimeSignature,
+}
+
+impl CertificateVote {
+    pub fn sender(&self) -> Option<Address> {
+        address_from_bytes(&self.sender)
+    }
+}
+
+/// Two votes of the same sender for different proposals.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EquivocationVote {
+    #[serde(
+        rename = "snd",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub sender: Vec<u8>,
+
+    #[serde(rename = "cred")]
+    pub credential: VoteCredential,
+
+    #[serde(rename = "props", default)]
+    pub proposals: Vec<ProposalValue>,
+
+    #[serde(rename = "sigs", default)]
+    pub signatures: Vec<OneTimeSignature>,
+}
+
+/// Credential of a committee member.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteCredential {
+    /// VRF proof of the selection.
+    #[serde(
+        rename = "pf",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub proof: Vec<u8>,
+}
+
+/// Signature with the voter's participation (one-time) key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneTimeSignature {
+    #[serde(
+        rename = "s",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub sig: Vec<u8>,
+
+    #[serde(
+        rename = "p",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub pk: Vec<u8>,
+
+    #[serde(
+        rename = "ps",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub pk_sig: Vec<u8>,
+
+    #[serde(
+        rename = "p2",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub pk2: Vec<u8>,
+
+    #[serde(
+        rename = "p1s",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub pk1_sig: Vec<u8>,
+
+    #[serde(
+        rename = "p2s",
+        default,
+        serialize_with = "serialize_bytes",
+        deserialize_with = "deserialize_bytes"
+    )]
+    pub pk2_sig: Vec<u8>,
+}
+
+fn address_from_bytes(bytes: &[u8]) -> Option<Address> {
+    TryFrom::try_from(bytes).ok().map(Address)
+}
+
 /// BlockHeader
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlockHeader {
@@ -741,11 +1907,8 @@ impl<'de> Deserialize<'de> for CompiledTealWithHash {
         let api_obj = ApiCompiledTealWithHash::deserialize(deserializer)?;
         Ok(CompiledTealWithHash {
             hash: api_obj.hash.clone(),
-            program: CompiledTeal(
-                BASE64
-                    .decode(api_obj.result.as_bytes())
-                    .map_err(serde::de::Error::custom)?,
-            ),
+            program: CompiledTeal::from_base64(&api_obj.result)
+                .map_err(serde::de::Error::custom)?,
         })
     }
 }
@@ -785,6 +1948,877 @@ pub struct TransactionParams {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionResponse {
-    #[serde(rename = "txId")]
+    #[serde(rename = "txId", default)]
     pub tx_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_account_totals() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 5000000000,
+            "amount-without-pending-rewards": 5000000000,
+            "assets": [
+                {
+                    "amount": 10,
+                    "asset-id": 1,
+                    "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "is-frozen": false
+                },
+                {
+                    "amount": 20,
+                    "asset-id": 2,
+                    "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "is-frozen": false
+                }
+            ],
+            "pending-rewards": 0,
+            "rewards": 0,
+            "round": 18241,
+            "status": "Offline",
+            "total-apps-opted-in": 0,
+            "total-assets-opted-in": 1500,
+            "total-created-apps": 12,
+            "total-created-assets": 1200
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert_eq!(Some(1500), account.total_assets_opted_in);
+        assert_eq!(Some(0), account.total_apps_opted_in);
+        assert_eq!(Some(12), account.total_created_apps);
+        assert_eq!(Some(1200), account.total_created_assets);
+        assert!(account.has_incomplete_resources());
+    }
+
+    #[test]
+    fn test_deserialize_app_account_boxes() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 1000000,
+            "amount-without-pending-rewards": 1000000,
+            "pending-rewards": 0,
+            "rewards": 0,
+            "round": 18241,
+            "status": "Offline",
+            "total-box-bytes": 1037,
+            "total-boxes": 2
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert_eq!(Some(2), account.total_boxes);
+        assert_eq!(Some(1037), account.total_box_bytes);
+    }
+
+    #[test]
+    fn test_opted_in_app_ids_and_held_asset_ids() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 5000000000,
+            "amount-without-pending-rewards": 5000000000,
+            "apps-local-state": [
+                {
+                    "id": 12,
+                    "key-value": [
+                        {"key": "Y291bnQ=", "value": {"bytes": "", "type": 2, "uint": 3}}
+                    ],
+                    "schema": {"num-byte-slice": 0, "num-uint": 1}
+                },
+                {
+                    "id": 34,
+                    "schema": {"num-byte-slice": 1, "num-uint": 0}
+                }
+            ],
+            "apps-total-schema": {"num-byte-slice": 1, "num-uint": 1},
+            "assets": [
+                {
+                    "amount": 0,
+                    "asset-id": 56,
+                    "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "is-frozen": false
+                },
+                {
+                    "amount": 20,
+                    "asset-id": 78,
+                    "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "is-frozen": true
+                }
+            ],
+            "min-balance": 642500,
+            "pending-rewards": 0,
+            "rewards": 0,
+            "round": 18241,
+            "status": "Offline",
+            "total-apps-opted-in": 2,
+            "total-assets-opted-in": 2
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert_eq!(vec![AppId(12), AppId(34)], account.opted_in_app_ids());
+        assert_eq!(vec![AssetId(56), AssetId(78)], account.held_asset_ids());
+    }
+
+    #[test]
+    fn test_opted_in_ids_of_empty_account() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 0,
+            "amount-without-pending-rewards": 0,
+            "pending-rewards": 0,
+            "rewards": 0,
+            "round": 18241,
+            "status": "Offline"
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert!(account.opted_in_app_ids().is_empty());
+        assert!(account.held_asset_ids().is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_account_amounts() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 4000000123456,
+            "amount-without-pending-rewards": 4000000000000,
+            "min-balance": 200000,
+            "pending-rewards": 123456,
+            "reward-base": 27521,
+            "rewards": 123456,
+            "round": 18241,
+            "status": "Online"
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert_eq!(Round(18241), account.round);
+        assert_eq!(MicroAlgos(4_000_000_123_456), account.amount);
+        assert_eq!(
+            MicroAlgos(4_000_000_000_000),
+            account.amount_without_pending_rewards
+        );
+        assert_eq!(MicroAlgos(123_456), account.rewards);
+        assert_eq!(MicroAlgos(200_000), account.min_balance);
+        assert_eq!(
+            account.amount,
+            account.amount_without_pending_rewards + account.pending_rewards
+        );
+    }
+
+    #[test]
+    fn test_pending_rewards() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 12346913401,
+            "amount-without-pending-rewards": 12345678901,
+            "pending-rewards": 1234500,
+            "reward-base": 27521,
+            "rewards": 2500000,
+            "round": 18241,
+            "status": "Online"
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            Some(MicroAlgos(1_234_500)),
+            pending_rewards(&account, 27_621)
+        );
+        assert_eq!(Some(MicroAlgos(0)), pending_rewards(&account, 27_521));
+        let max_level_delta = u64::MAX / 12_345;
+        assert_eq!(
+            Some(MicroAlgos(12_345 * max_level_delta)),
+            pending_rewards(&account, 27_521 + max_level_delta)
+        );
+        assert_eq!(
+            None,
+            pending_rewards(&account, 27_521 + max_level_delta + 1)
+        );
+    }
+
+    #[test]
+    fn test_account_status() {
+        let account = |status: &str| -> Account {
+            serde_json::from_str(&format!(
+                r#"{{
+                    "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "amount": 5000000000,
+                    "amount-without-pending-rewards": 5000000000,
+                    "pending-rewards": 0,
+                    "rewards": 0,
+                    "round": 18241,
+                    "status": "{}"
+                }}"#,
+                status
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(Some(AccountStatus::Offline), account("Offline").status());
+        assert_eq!(Some(AccountStatus::Online), account("Online").status());
+        assert_eq!(
+            Some(AccountStatus::NotParticipating),
+            account("NotParticipating").status()
+        );
+        assert_eq!(None, account("Unknown").status());
+        assert_eq!(
+            AccountStatus::NotParticipating,
+            serde_json::from_str(r#""NotParticipating""#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serialize_dryrun_sources() {
+        let req = DryrunRequest {
+            accounts: vec![],
+            apps: vec![],
+            latest_timestamp: 0,
+            protocol_version: "".to_owned(),
+            round: Round(0),
+            sources: vec![
+                DryrunSource::approval_program(5, "#pragma version 6\nint 1").txn_index(1),
+                DryrunSource::logic_sig(2, "#pragma version 6\nint 0"),
+            ],
+            txns: vec![],
+        };
+
+        assert_eq!(
+            serde_json::json!([
+                {
+                    "app-index": 5,
+                    "field-name": "approv",
+                    "source": "#pragma version 6\nint 1",
+                    "txn-index": 1
+                },
+                {
+                    "app-index": 0,
+                    "field-name": "lsig",
+                    "source": "#pragma version 6\nint 0",
+                    "txn-index": 2
+                }
+            ]),
+            serde_json::to_value(&req).unwrap()["sources"]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_ledger_state_delta() {
+        let json = r#"{
+            "Accts": {
+                "Accts": [
+                    {
+                        "Addr": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                        "AccountData": {
+                            "algo": 4999998000,
+                            "ebase": 27521,
+                            "onl": 1,
+                            "tapl": 1
+                        }
+                    }
+                ],
+                "AppResources": [
+                    {
+                        "Aidx": 12,
+                        "Addr": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                        "Params": {
+                            "Deleted": false,
+                            "Params": {
+                                "gs": {
+                                    "counter": {"tt": 2, "ui": 4}
+                                }
+                            }
+                        },
+                        "State": {"Deleted": false, "LocalState": null}
+                    }
+                ],
+                "AssetResources": null
+            },
+            "Creatables": null,
+            "KvMods": null,
+            "PrevTimestamp": 1678886400,
+            "StateProofNext": 512,
+            "Txids": {
+                "LPZ6JBMHWGB7DHQ4H2ZTPN6MWWQDFA4WRRRUL4DTDPRW2MDALH6A": {"Intra": 0, "LastValid": 1100},
+                "QZ6LB6E2N6V3HJ6V6ZRSDZIUEXNFMRLCZ4HSI4HO4NKSWKU6STHQ": {"Intra": 1, "LastValid": 1100}
+            }
+        }"#;
+
+        let delta: LedgerStateDelta = serde_json::from_str(json).unwrap();
+
+        let accounts = delta.accounts.accounts.unwrap();
+        assert_eq!(
+            "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            accounts[0].address.to_string()
+        );
+        assert_eq!(
+            MicroAlgos(4_999_998_000),
+            accounts[0].account_data.micro_algos
+        );
+        assert_eq!(1, accounts[0].account_data.status);
+        let apps = delta.accounts.app_resources.unwrap();
+        assert_eq!(12, apps[0].app_id);
+        assert_eq!(None, apps[0].state.local_state);
+        let global_state = apps[0]
+            .params
+            .params
+            .as_ref()
+            .unwrap()
+            .global_state
+            .as_ref();
+        assert_eq!(
+            Some(&DeltaTealValue {
+                value_type: 2,
+                bytes: "".to_owned(),
+                uint: 4
+            }),
+            global_state.unwrap().get("counter")
+        );
+        assert_eq!(None, delta.accounts.asset_resources);
+        assert_eq!(2, delta.tx_ids.unwrap().len());
+        assert_eq!(512, delta.state_proof_next);
+    }
+
+    #[test]
+    fn test_deserialize_state_proof() {
+        let json = r#"{
+            "Message": {
+                "BlockHeadersCommitment": "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA=",
+                "FirstAttestedRound": 257,
+                "LastAttestedRound": 512,
+                "LnProvenWeight": 2334949,
+                "VotersCommitment": "ICEiIyQlJicoKSorLC0uLzAxMjM0NTY3ODk6Ozw9Pj8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+Pw=="
+            },
+            "StateProof": "hqFQgaFwgqFkAaFpAQ=="
+        }"#;
+
+        let state_proof: StateProof = serde_json::from_str(json).unwrap();
+
+        let message = &state_proof.message;
+        assert_eq!(
+            (1..=32).collect::<Vec<u8>>(),
+            message.block_headers_commitment
+        );
+        assert_eq!(64, message.voters_commitment.len());
+        assert_eq!(2_334_949, message.ln_proven_weight);
+        assert!(message.attests(Round(257)));
+        assert!(message.attests(Round(512)));
+        assert!(!message.attests(Round(513)));
+        assert_eq!(
+            BASE64.decode(b"hqFQgaFwgqFkAaFpAQ==").unwrap(),
+            state_proof.state_proof
+        );
+    }
+
+    #[test]
+    fn test_pending_transaction_pool_error() {
+        let pending = |pool_error: &str| -> PendingTransaction {
+            serde_json::from_str(&format!(
+                r#"{{"pool-error": "{}", "txn": {{}}}}"#,
+                pool_error
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(None, pending("").pool_error());
+        assert_eq!(Some("overspend"), pending("overspend").pool_error());
+    }
+
+    #[test]
+    fn test_pending_transaction_logs_decoded() {
+        let json = r#"{
+            "confirmed-round": 18242,
+            "logs": ["aGVsbG8gYWxnb25hdXQ=", "FR98dQAAAAAAAAD/"],
+            "pool-error": "",
+            "txn": {}
+        }"#;
+
+        let pending: PendingTransaction = serde_json::from_str(json).unwrap();
+        let logs = pending.logs_decoded();
+
+        assert_eq!(2, logs.len());
+        assert_eq!(b"hello algonaut".to_vec(), logs[0].bytes);
+        assert_eq!(Some("hello algonaut".to_owned()), logs[0].text);
+        assert_eq!("68656c6c6f20616c676f6e617574", logs[0].hex);
+        assert_eq!(None, logs[1].text);
+        assert_eq!("151f7c7500000000000000ff", logs[1].hex);
+    }
+
+    #[test]
+    fn test_pending_transaction_asset_params() {
+        let json = r#"{
+            "asset-index": 1234,
+            "confirmed-round": 18242,
+            "pool-error": "",
+            "txn": {
+                "sig": "gVDqGctmDVXiwDGG8fyGA2B4sjXcbzcnbS/6ApLoNB7OsSMfqTPBd2morJoV3mEm5OCiOCJAAjnujfRkOw4hBA==",
+                "txn": {
+                    "apar": {
+                        "an": "algonaut test",
+                        "au": "https://github.com/manuelmauro/algonaut",
+                        "c": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                        "dc": 2,
+                        "m": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                        "t": 1000000,
+                        "un": "ALGNT"
+                    },
+                    "fee": 1000,
+                    "fv": 18240,
+                    "gh": "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+                    "lv": 19240,
+                    "snd": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "type": "acfg"
+                }
+            }
+        }"#;
+
+        let pending: PendingTransaction = serde_json::from_str(json).unwrap();
+        let params = pending.asset_params().unwrap();
+
+        assert_eq!("ALGNT", params.unit_name);
+        assert_eq!(1_000_000, params.total);
+        assert_eq!(2, params.decimals);
+        assert_eq!(
+            "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            params.creator
+        );
+        assert_eq!("", params.freeze);
+        assert!(pending.application_params().is_none());
+    }
+
+    #[test]
+    fn test_pending_transaction_application_params() {
+        let json = r#"{
+            "application-index": 12,
+            "confirmed-round": 18242,
+            "global-state-delta": [
+                {"key": "Y291bnRlcg==", "value": {"action": 2, "uint": 1}}
+            ],
+            "pool-error": "",
+            "txn": {
+                "txn": {
+                    "apap": "BoEBQw==",
+                    "apgs": {"nui": 1},
+                    "apsu": "BoEBQw==",
+                    "snd": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "type": "appl"
+                }
+            }
+        }"#;
+
+        let pending: PendingTransaction = serde_json::from_str(json).unwrap();
+        let params = pending.application_params().unwrap();
+
+        assert_eq!(vec![0x06, 0x81, 0x01, 0x43], params.approval_program.0);
+        assert_eq!(1, params.global_state_schema.num_uint);
+        assert_eq!(0, params.local_state_schema.num_uint);
+        assert_eq!(1, params.global_state.len());
+        assert_eq!("Y291bnRlcg==", params.global_state[0].key);
+        assert_eq!(1, params.global_state[0].value.uint);
+        assert!(pending.asset_params().is_none());
+    }
+
+    #[test]
+    fn test_pending_transaction_rewards() {
+        let json = r#"{
+            "confirmed-round": 18242,
+            "pool-error": "",
+            "receiver-rewards": 4297,
+            "sender-rewards": 0,
+            "txn": {}
+        }"#;
+
+        let pending: PendingTransaction = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            TransactionRewards {
+                sender: MicroAlgos(0),
+                receiver: MicroAlgos(4_297),
+                close: MicroAlgos(0),
+            },
+            pending.rewards()
+        );
+    }
+
+    #[test]
+    fn test_account_without_totals_is_complete() {
+        let json = r#"{
+            "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            "amount": 5000000000,
+            "amount-without-pending-rewards": 5000000000,
+            "pending-rewards": 0,
+            "rewards": 0,
+            "round": 18241,
+            "status": "Offline"
+        }"#;
+
+        let account: Account = serde_json::from_str(json).unwrap();
+
+        assert_eq!(None, account.total_created_assets);
+        assert!(!account.has_incomplete_resources());
+    }
+
+    #[test]
+    fn test_deserialize_participation_keys() {
+        let json = r#"[
+            {
+                "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                "effective-first-valid": 10,
+                "effective-last-valid": 3000000,
+                "id": "SZMRHOZNUMHCKCIL3LMBYFRUEXTAP4NH5ZAB7NUSBBZHQFV5CBQQ",
+                "key": {
+                    "selection-participation-key": "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA=",
+                    "state-proof-key": "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyAhIiMkJSYnKCkqKywtLi8wMTIzNDU2Nzg5Ojs8PT4/QA==",
+                    "vote-first-valid": 0,
+                    "vote-key-dilution": 1733,
+                    "vote-last-valid": 3000000,
+                    "vote-participation-key": "ICEiIyQlJicoKSorLC0uLzAxMjM0NTY3ODk6Ozw9Pj8="
+                },
+                "last-vote": 1234
+            },
+            {
+                "address": "PU7ZTZJ5GSXET2ZPIWDWDT2TQQEP7WXOGXDQ3ARUCZW6PK7D4ULSE6NYCE",
+                "id": "RYPIRCB4NH6EB2IIYNEAZPDY5LVYFJ3SGZSQKG46ALR6Z3PPESFQ",
+                "key": {
+                    "selection-participation-key": "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyA=",
+                    "vote-first-valid": 100,
+                    "vote-key-dilution": 100,
+                    "vote-last-valid": 200,
+                    "vote-participation-key": "ICEiIyQlJicoKSorLC0uLzAxMjM0NTY3ODk6Ozw9Pj8="
+                }
+            }
+        ]"#;
+
+        let keys: Vec<ParticipationKey> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(2, keys.len());
+        assert_eq!(
+            "SZMRHOZNUMHCKCIL3LMBYFRUEXTAP4NH5ZAB7NUSBBZHQFV5CBQQ",
+            keys[0].id
+        );
+        assert_eq!(Some(Round(10)), keys[0].effective_first_valid);
+        assert_eq!(Some(Round(1234)), keys[0].last_vote);
+        assert_eq!(3000000, keys[0].key.vote_last_valid);
+        assert_eq!(32, keys[0].key.selection_participation_key.len());
+        assert_eq!(64, keys[0].key.state_proof_key.len());
+        assert_eq!(None, keys[1].effective_first_valid);
+        assert!(keys[1].key.state_proof_key.is_empty());
+    }
+
+    #[test]
+    fn test_application_program_hashes() {
+        let json = r#"{
+            "id": 1,
+            "params": {
+                "approval-program": "BiABASI=",
+                "clear-state-program": "BoEB",
+                "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                "global-state": [],
+                "global-state-schema": { "num-byte-slice": 0, "num-uint": 0 },
+                "local-state-schema": { "num-byte-slice": 0, "num-uint": 0 }
+            }
+        }"#;
+        let app: Application = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            CompiledTeal(vec![0x06, 0x20, 0x01, 0x01, 0x22]),
+            app.params.approval_program
+        );
+        assert_eq!(
+            "UEQK2XNALTPZMHL6QJZZ24B26CU4LOMFUEHX5L375SHTEYNJR2J7IQANRA",
+            app.approval_hash().to_string()
+        );
+        assert_eq!(
+            "ZG2RRCHBZ4K2QKP3NGMYVF2MVG7YW2TSNJPVFVLEGX7KGQ46QVPJGOFTK4",
+            app.clear_hash().to_string()
+        );
+    }
+
+    #[test]
+    fn test_application_empty_programs_not_serialized() {
+        let json = r#"{
+            "id": 1,
+            "params": {
+                "clear-state-program": "BoEB",
+                "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                "global-state": [],
+                "global-state-schema": { "num-byte-slice": 0, "num-uint": 0 },
+                "local-state-schema": { "num-byte-slice": 0, "num-uint": 0 }
+            }
+        }"#;
+        let app: Application = serde_json::from_str(json).unwrap();
+
+        let serialized = serde_json::to_value(&app).unwrap();
+
+        assert!(serialized["params"].get("approval-program").is_none());
+        assert_eq!("BoEB", serialized["params"]["clear-state-program"]);
+    }
+
+    #[test]
+    fn test_global_state_decoded() {
+        let json = r#"{
+            "id": 1,
+            "params": {
+                "creator": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                "global-state": [
+                    { "key": "Y291bnRlcg==", "value": { "bytes": "", "type": 2, "uint": 5 } },
+                    { "key": "b3duZXI=", "value": { "bytes": "aGk=", "type": 1, "uint": 0 } },
+                    { "key": "/wE=", "value": { "bytes": "", "type": 2, "uint": 7 } }
+                ],
+                "global-state-schema": { "num-byte-slice": 1, "num-uint": 2 },
+                "local-state-schema": { "num-byte-slice": 0, "num-uint": 0 }
+            }
+        }"#;
+        let app: Application = serde_json::from_str(json).unwrap();
+
+        let state = app.global_state_decoded();
+
+        assert_eq!(3, state.len());
+        assert_eq!(5, state["counter"].uint);
+        assert_eq!(b"hi".to_vec(), state["owner"].bytes);
+        assert_eq!(7, state["ff01"].uint);
+    }
+
+    #[test]
+    fn test_node_info() {
+        let json = r#"{
+            "build": {
+                "branch": "rel/stable",
+                "build_number": 4,
+                "channel": "stable",
+                "commit_hash": "6b940281",
+                "major": 3,
+                "minor": 9
+            },
+            "genesis_hash_b64": "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+            "genesis_id": "testnet-v1.0",
+            "versions": ["v2"]
+        }"#;
+        let version: Version = serde_json::from_str(json).unwrap();
+
+        let info = version.node_info();
+
+        assert_eq!("3.9.4", info.build_version);
+        assert_eq!("stable", info.channel);
+        assert_eq!("6b940281", info.commit_hash);
+        assert_eq!("testnet-v1.0", info.genesis_id);
+        assert_eq!(
+            "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+            BASE64.encode(&info.genesis_hash.0)
+        );
+        assert_eq!(vec!["v2".to_owned()], info.api_versions);
+    }
+
+    #[test]
+    fn test_genesis_allocations() {
+        let json = r#"{
+            "alloc": [
+                {
+                    "addr": "7777777777777777777777777777777777777777777777777774MSJUVU",
+                    "comment": "RewardsPool",
+                    "state": { "algo": 125000000000000, "onl": 2 }
+                },
+                {
+                    "addr": "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE",
+                    "comment": "FeeSink",
+                    "state": { "algo": 100000, "onl": 2 }
+                },
+                {
+                    "addr": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "comment": "Wallet1",
+                    "state": {
+                        "algo": 4000000000000000,
+                        "onl": 1,
+                        "sel": "oi8NmQ9fCGBgB1nTTujsOlx7nPkGaTDVaW8iIbN8DpE=",
+                        "vote": "GTJamQxK9A6kuLSSF0kv+P6KVxDdINtnVyTjdWILOvw=",
+                        "voteKD": 10000,
+                        "voteLst": 3000000
+                    }
+                }
+            ],
+            "fees": "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE",
+            "id": "v1",
+            "network": "sandnet",
+            "proto": "future",
+            "rwd": "7777777777777777777777777777777777777777777777777774MSJUVU"
+        }"#;
+
+        let genesis: GenesisBlock = serde_json::from_str(json).unwrap();
+
+        let fee_sink: Address = "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE"
+            .parse()
+            .unwrap();
+        let rewards_pool: Address = "7777777777777777777777777777777777777777777777777774MSJUVU"
+            .parse()
+            .unwrap();
+        let wallet: Address = "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA"
+            .parse()
+            .unwrap();
+        assert_eq!(fee_sink, genesis.fee_sink());
+        assert_eq!(rewards_pool, genesis.rewards_pool());
+        assert_eq!(
+            vec![
+                (rewards_pool, MicroAlgos(125_000_000_000_000)),
+                (fee_sink, MicroAlgos(100_000)),
+                (wallet, MicroAlgos(4_000_000_000_000_000)),
+            ],
+            genesis.allocations()
+        );
+        assert_eq!(Some(Round(3_000_000)), genesis.alloc[2].state.vote_last);
+    }
+
+    #[test]
+    fn test_deserialize_app_call_state_deltas() {
+        // Confirmation of an app call incrementing a global counter, setting a local
+        // name and deleting a local flag.
+        let json = r#"{
+            "application-index": 0,
+            "confirmed-round": 1205,
+            "global-state-delta": [
+                { "key": "Y291bnRlcg==", "value": { "action": 2, "uint": 3 } }
+            ],
+            "local-state-delta": [
+                {
+                    "address": "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+                    "delta": [
+                        { "key": "bmFtZQ==", "value": { "action": 1, "bytes": "YWxpY2U=" } },
+                        { "key": "ZmxhZw==", "value": { "action": 3 } }
+                    ]
+                }
+            ],
+            "logs": ["YWRkaW5n", "FR98dQAAAAAAAAAP"],
+            "pool-error": "",
+            "txn": {}
+        }"#;
+
+        let pending: PendingTransaction = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            vec![
+                b"adding".to_vec(),
+                b"\x15\x1f\x7c\x75\0\0\0\0\0\0\0\x0f".to_vec()
+            ],
+            pending.logs
+        );
+        let global_delta = pending.global_state_delta.unwrap();
+        assert_eq!(
+            vec![EvalDeltaKeyValue {
+                key: b"counter".to_vec(),
+                value: EvalDelta::SetUint(3),
+            }],
+            global_delta
+        );
+        let local_delta = pending.local_state_delta.unwrap();
+        assert_eq!(
+            "DPLD3RTSWC5STVBPZL5DIIVE2OC4BSAWTOYBLFN2X6EFLT2ZNF4SMX64UA",
+            local_delta[0].address
+        );
+
+        let mut global_state = BTreeMap::new();
+        apply_state_delta(&mut global_state, &global_delta);
+        assert_eq!(3, global_state[&b"counter".to_vec()].uint);
+
+        let mut local_state = BTreeMap::new();
+        local_state.insert(
+            b"flag".to_vec(),
+            TealValue {
+                bytes: vec![],
+                value_type: 2,
+                uint: 1,
+            },
+        );
+        apply_state_delta(&mut local_state, &local_delta[0].delta);
+        assert_eq!(
+            vec![(
+                b"name".to_vec(),
+                TealValue {
+                    bytes: b"alice".to_vec(),
+                    value_type: 1,
+                    uint: 0,
+                }
+            )],
+            local_state.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_eval_delta_round_trip() {
+        let delta = vec![
+            EvalDeltaKeyValue {
+                key: b"a".to_vec(),
+                value: EvalDelta::SetBytes(b"b".to_vec()),
+            },
+            EvalDeltaKeyValue {
+                key: b"c".to_vec(),
+                value: EvalDelta::Delete,
+            },
+        ];
+
+        let json = serde_json::to_string(&delta).unwrap();
+
+        assert_eq!(
+            r#"[{"key":"YQ==","value":{"action":1,"bytes":"Yg=="}},{"key":"Yw==","value":{"action":3}}]"#,
+            json
+        );
+        assert_eq!(delta, serde_json::from_str::<StateDelta>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_block_certificate() {
+        let proposer = BASE64.encode(&[1; 32]);
+        let voter = BASE64.encode(&[2; 32]);
+        let json = format!(
+            r#"{{
+                "block": {{
+                    "earn": 27521,
+                    "fees": "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE",
+                    "frac": 2505066206,
+                    "gen": "testnet-v1.0",
+                    "gh": "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+                    "prev": "blk-5ICJHSZBNYAQY6IDPWWUQ3ANAQSSZ6WKJZCL2TDDUKMORNMZTPOA",
+                    "proto": "https://github.com/algorandfoundation/specs/tree/65b4ab3266c52c56a0fa7d591754887d68faad0a",
+                    "rate": 26,
+                    "rnd": 8000000,
+                    "rwcalr": 8500000,
+                    "rwd": "7777777777777777777777777777777777777777777777777774MSJUVU",
+                    "seed": "qJtCe6Dv8cU6rUaPMYGFkLlzE6AobkijAe6gtpEpCfU=",
+                    "ts": 1593455627,
+                    "txn": "bc7OO5Kz7ZJtqE3fhBCCCv+aK5GzTTBjyIueT51VTEk="
+                }},
+                "cert": {{
+                    "prop": {{"dig": "AwMD", "encdig": "BAQE", "oprop": "{}"}},
+                    "rnd": 8000000,
+                    "step": 2,
+                    "vote": [
+                        {{
+                            "cred": {{"pf": "BQUF"}},
+                            "sig": {{"p": "BgYG", "s": "BwcH"}},
+                            "snd": "{}"
+                        }}
+                    ]
+                }}
+            }}"#,
+            proposer, voter
+        );
+
+        let block: Block = serde_json::from_str(&json).unwrap();
+        let cert = block.certificate().unwrap().unwrap();
+
+        assert_eq!(Some(Address([1; 32])), cert.proposer());
+        assert_eq!(8_000_000, cert.round);
+        assert_eq!(0, cert.period);
+        assert_eq!(2, cert.step);
+        assert_eq!(vec![3, 3, 3], cert.proposal.block_digest);
+        assert_eq!(1, cert.votes.len());
+        assert_eq!(Some(Address([2; 32])), cert.votes[0].sender());
+        assert_eq!(vec![5, 5, 5], cert.votes[0].credential.proof);
+        assert_eq!(vec![7, 7, 7], cert.votes[0].signature.sig);
+        assert!(cert.equivocation_votes.is_empty());
+    }
+}
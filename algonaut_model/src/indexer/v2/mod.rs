@@ -1081,6 +1081,37 @@ pub struct HealthCheck<T> {
     pub round: Round,
 }
 
+/// Health of an indexer, from its `/health` endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexerHealth {
+    /// Last round imported by the indexer.
+    pub round: Round,
+
+    #[serde(rename = "db-available")]
+    pub db_available: bool,
+
+    #[serde(rename = "is-migrating")]
+    pub is_migrating: bool,
+
+    #[serde(default)]
+    pub message: String,
+
+    /// Indexer version.
+    pub version: Option<String>,
+
+    /// Errors reported by the indexer, if any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl IndexerHealth {
+    /// Number of rounds the indexer is behind `algod_round`, the node's last round (0 if it's not
+    /// behind).
+    pub fn lag_rounds(&self, algod_round: Round) -> u64 {
+        algod_round.0.saturating_sub(self.round.0)
+    }
+}
+
 /// A simplified version of AssetHolding
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MiniAssetHolding {
@@ -0,0 +1,720 @@
+//! Encoding of [ARC-4](https://github.com/algorandfoundation/ARCs/blob/main/ARCs/arc-0004.md)
+//! ABI method calls and decoding of their return values.
+//!
+//! Transaction (`txn`, `pay`, ...) and reference (`account`, `asset`, `application`) argument
+//! types and `ufixed` are not supported.
+
+use crate::error::TransactionError;
+use algonaut_core::Address;
+use sha2::Digest;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Maximum number of method arguments passed as separate app arguments. With more, the arguments
+/// from the 15th are encoded together as a tuple (the 16th app argument, after the selector).
+const MAX_SEPARATE_ARGS: usize = 15;
+
+/// Prefix of the log holding the return value of a method call (the first 4 bytes of the
+/// SHA-512/256 hash of `return`).
+pub const RETURN_PREFIX: [u8; 4] = [0x15, 0x1f, 0x7c, 0x75];
+
+/// An ABI type.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AbiType {
+    /// `uint<N>`, with N a multiple of 8 up to 512.
+    Uint(u16),
+    Byte,
+    Bool,
+    Address,
+    String,
+    /// `<type>[<N>]`
+    StaticArray(Box<AbiType>, usize),
+    /// `<type>[]`
+    DynamicArray(Box<AbiType>),
+    Tuple(Vec<AbiType>),
+}
+
+/// A value of an [AbiType].
+///
+/// Unsigned integers are limited to 64 bit values, also for wider `uint<N>` types.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AbiValue {
+    Uint(u64),
+    Byte(u8),
+    Bool(bool),
+    Address(Address),
+    String(String),
+    /// Elements of a static or dynamic array.
+    Array(Vec<AbiValue>),
+    Tuple(Vec<AbiValue>),
+}
+
+impl AbiType {
+    /// Whether the encoding of the type has a variable length.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::String | AbiType::DynamicArray(_) => true,
+            AbiType::StaticArray(t, _) => t.is_dynamic(),
+            AbiType::Tuple(types) => types.iter().any(AbiType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Encodes a value of this type.
+    pub fn encode(&self, value: &AbiValue) -> Result<Vec<u8>, TransactionError> {
+        match (self, value) {
+            (AbiType::Uint(bits), AbiValue::Uint(n)) => {
+                let len = *bits as usize / 8;
+                if len < 8 && *n >> (len * 8) != 0 {
+                    return Err(abi_error(format!("{} overflows {}", n, self)));
+                }
+                let mut bytes = vec![0; len.saturating_sub(8)];
+                let be = n.to_be_bytes();
+                bytes.extend_from_slice(&be[8 - len.min(8)..]);
+                Ok(bytes)
+            }
+            (AbiType::Byte, AbiValue::Byte(b)) => Ok(vec![*b]),
+            (AbiType::Bool, AbiValue::Bool(b)) => Ok(vec![if *b { 0x80 } else { 0 }]),
+            (AbiType::Address, AbiValue::Address(address)) => Ok(address.0.to_vec()),
+            (AbiType::String, AbiValue::String(s)) => {
+                let mut bytes = encode_len(s.len())?;
+                bytes.extend_from_slice(s.as_bytes());
+                Ok(bytes)
+            }
+            (AbiType::StaticArray(t, len), AbiValue::Array(values)) => {
+                if values.len() != *len {
+                    return Err(abi_error(format!(
+                        "expected {} elements for {}, got {}",
+                        len,
+                        self,
+                        values.len()
+                    )));
+                }
+                encode_tuple(&vec![t.as_ref().clone(); *len], values)
+            }
+            (AbiType::DynamicArray(t), AbiValue::Array(values)) => {
+                let mut bytes = encode_len(values.len())?;
+                bytes.extend(encode_tuple(
+                    &vec![t.as_ref().clone(); values.len()],
+                    values,
+                )?);
+                Ok(bytes)
+            }
+            (AbiType::Tuple(types), AbiValue::Tuple(values)) => encode_tuple(types, values),
+            _ => Err(abi_error(format!("{:?} is not a {}", value, self))),
+        }
+    }
+
+    /// Decodes a value of this type, which has to span all of `bytes`.
+    pub fn decode(&self, bytes: &[u8]) -> Result<AbiValue, TransactionError> {
+        match self {
+            AbiType::Uint(bits) => {
+                let len = *bits as usize / 8;
+                if bytes.len() != len {
+                    return Err(abi_error(format!(
+                        "expected {} bytes for {}, got {}",
+                        len,
+                        self,
+                        bytes.len()
+                    )));
+                }
+                let (high, low) = bytes.split_at(len.saturating_sub(8));
+                if high.iter().any(|b| *b != 0) {
+                    return Err(abi_error(format!("{} value overflows uint64", self)));
+                }
+                Ok(AbiValue::Uint(
+                    low.iter().fold(0, |n, b| (n << 8) | *b as u64),
+                ))
+            }
+            AbiType::Byte => match bytes {
+                [b] => Ok(AbiValue::Byte(*b)),
+                _ => Err(abi_error(format!("expected 1 byte, got {}", bytes.len()))),
+            },
+            AbiType::Bool => match bytes {
+                [0x80] => Ok(AbiValue::Bool(true)),
+                [0] => Ok(AbiValue::Bool(false)),
+                _ => Err(abi_error(format!("invalid bool encoding: {:?}", bytes))),
+            },
+            AbiType::Address => {
+                if bytes.len() != 32 {
+                    return Err(abi_error(format!(
+                        "expected 32 bytes for address, got {}",
+                        bytes.len()
+                    )));
+                }
+                let mut address = [0; 32];
+                address.copy_from_slice(bytes);
+                Ok(AbiValue::Address(Address(address)))
+            }
+            AbiType::String => {
+                let (len, content) = decode_len(bytes)?;
+                if content.len() != len {
+                    return Err(abi_error(format!(
+                        "expected {} string bytes, got {}",
+                        len,
+                        content.len()
+                    )));
+                }
+                String::from_utf8(content.to_vec())
+                    .map(AbiValue::String)
+                    .map_err(|e| abi_error(e.to_string()))
+            }
+            AbiType::StaticArray(t, len) => Ok(AbiValue::Array(decode_tuple(
+                &vec![t.as_ref().clone(); *len],
+                bytes,
+            )?)),
+            AbiType::DynamicArray(t) => {
+                let (len, elements) = decode_len(bytes)?;
+                Ok(AbiValue::Array(decode_tuple(
+                    &vec![t.as_ref().clone(); len],
+                    elements,
+                )?))
+            }
+            AbiType::Tuple(types) => Ok(AbiValue::Tuple(decode_tuple(types, bytes)?)),
+        }
+    }
+
+    /// Length of the encoding of a static type.
+    fn static_len(&self) -> usize {
+        match self {
+            AbiType::Uint(bits) => *bits as usize / 8,
+            AbiType::Byte | AbiType::Bool => 1,
+            AbiType::Address => 32,
+            AbiType::StaticArray(t, len) if **t == AbiType::Bool => len.div_ceil(8),
+            AbiType::StaticArray(t, len) => t.static_len() * len,
+            AbiType::Tuple(types) => {
+                let mut len = 0;
+                let mut i = 0;
+                while i < types.len() {
+                    i += bool_run(&types[i..]).max(1);
+                    len += types[i - 1].static_len();
+                }
+                len
+            }
+            AbiType::String | AbiType::DynamicArray(_) => 0,
+        }
+    }
+}
+
+/// Number of consecutive bools (up to 8) at the start of `types`, packed in a byte.
+fn bool_run(types: &[AbiType]) -> usize {
+    types
+        .iter()
+        .take(8)
+        .take_while(|t| **t == AbiType::Bool)
+        .count()
+}
+
+/// Encodes the elements of a tuple: the static elements and offsets of the dynamic ones (heads),
+/// followed by the dynamic elements (tails). Consecutive bools are packed in a byte.
+fn encode_tuple(types: &[AbiType], values: &[AbiValue]) -> Result<Vec<u8>, TransactionError> {
+    if types.len() != values.len() {
+        return Err(abi_error(format!(
+            "expected {} tuple elements, got {}",
+            types.len(),
+            values.len()
+        )));
+    }
+    let mut heads: Vec<Vec<u8>> = vec![];
+    let mut tails: Vec<Vec<u8>> = vec![];
+    // Whether each head is the offset of its tail.
+    let mut dynamic: Vec<bool> = vec![];
+    let mut i = 0;
+    while i < types.len() {
+        if types[i] == AbiType::Bool {
+            let count = bool_run(&types[i..]);
+            let mut byte = 0u8;
+            for (bit, value) in values[i..i + count].iter().enumerate() {
+                match value {
+                    AbiValue::Bool(true) => byte |= 0x80 >> bit,
+                    AbiValue::Bool(false) => {}
+                    value => return Err(abi_error(format!("{:?} is not a bool", value))),
+                }
+            }
+            heads.push(vec![byte]);
+            tails.push(vec![]);
+            dynamic.push(false);
+            i += count;
+        } else if types[i].is_dynamic() {
+            heads.push(vec![0, 0]);
+            tails.push(types[i].encode(&values[i])?);
+            dynamic.push(true);
+            i += 1;
+        } else {
+            heads.push(types[i].encode(&values[i])?);
+            tails.push(vec![]);
+            dynamic.push(false);
+            i += 1;
+        }
+    }
+    let mut offset: usize = heads.iter().map(Vec::len).sum();
+    for (head, (tail, dynamic)) in heads.iter_mut().zip(tails.iter().zip(&dynamic)) {
+        if *dynamic {
+            *head = encode_len(offset)?;
+        }
+        offset += tail.len();
+    }
+    Ok(heads.into_iter().chain(tails).flatten().collect())
+}
+
+/// Decodes the elements of a tuple encoded by [encode_tuple].
+fn decode_tuple(types: &[AbiType], bytes: &[u8]) -> Result<Vec<AbiValue>, TransactionError> {
+    let mut values: Vec<Option<AbiValue>> = vec![None; types.len()];
+    // Element index and offset of the dynamic elements.
+    let mut offsets: Vec<(usize, usize)> = vec![];
+    let mut pos = 0;
+    let mut i = 0;
+    while i < types.len() {
+        if types[i] == AbiType::Bool {
+            let count = bool_run(&types[i..]);
+            let byte = *bytes
+                .get(pos)
+                .ok_or_else(|| abi_error("tuple encoding too short".to_owned()))?;
+            for bit in 0..count {
+                values[i + bit] = Some(AbiValue::Bool(byte & (0x80 >> bit) != 0));
+            }
+            pos += 1;
+            i += count;
+        } else if types[i].is_dynamic() {
+            let (offset, _) = decode_len(bytes.get(pos..).unwrap_or_default())?;
+            offsets.push((i, offset));
+            pos += 2;
+            i += 1;
+        } else {
+            let len = types[i].static_len();
+            let element = bytes
+                .get(pos..pos + len)
+                .ok_or_else(|| abi_error("tuple encoding too short".to_owned()))?;
+            values[i] = Some(types[i].decode(element)?);
+            pos += len;
+            i += 1;
+        }
+    }
+    for (k, (i, start)) in offsets.iter().enumerate() {
+        let end = offsets.get(k + 1).map_or(bytes.len(), |(_, end)| *end);
+        if *start < pos || *start > end || end > bytes.len() {
+            return Err(abi_error(format!(
+                "invalid tuple element offset: {}",
+                start
+            )));
+        }
+        values[*i] = Some(types[*i].decode(&bytes[*start..end])?);
+        pos = end;
+    }
+    if pos != bytes.len() {
+        return Err(abi_error(format!(
+            "{} trailing bytes after tuple",
+            bytes.len() - pos
+        )));
+    }
+    Ok(values.into_iter().flatten().collect())
+}
+
+fn encode_len(len: usize) -> Result<Vec<u8>, TransactionError> {
+    if len > u16::MAX as usize {
+        return Err(abi_error(format!("length {} exceeds uint16", len)));
+    }
+    Ok((len as u16).to_be_bytes().to_vec())
+}
+
+/// Reads a uint16 length prefix, returning it and the bytes following it.
+fn decode_len(bytes: &[u8]) -> Result<(usize, &[u8]), TransactionError> {
+    match bytes {
+        [high, low, rest @ ..] => Ok((u16::from_be_bytes([*high, *low]) as usize, rest)),
+        _ => Err(abi_error("missing uint16 length".to_owned())),
+    }
+}
+
+impl Display for AbiType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiType::Uint(bits) => write!(f, "uint{}", bits),
+            AbiType::Byte => write!(f, "byte"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Address => write!(f, "address"),
+            AbiType::String => write!(f, "string"),
+            AbiType::StaticArray(t, len) => write!(f, "{}[{}]", t, len),
+            AbiType::DynamicArray(t) => write!(f, "{}[]", t),
+            AbiType::Tuple(types) => write!(
+                f,
+                "({})",
+                types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl FromStr for AbiType {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(element) = s.strip_suffix("[]") {
+            return Ok(AbiType::DynamicArray(Box::new(element.parse()?)));
+        }
+        if let (Some(open), true) = (s.rfind('['), s.ends_with(']')) {
+            let len = s[open + 1..s.len() - 1]
+                .parse()
+                .map_err(|_| abi_error(format!("invalid array length in {}", s)))?;
+            return Ok(AbiType::StaticArray(Box::new(s[..open].parse()?), len));
+        }
+        if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+            return Ok(AbiType::Tuple(
+                split_types(inner)?
+                    .into_iter()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+            ));
+        }
+        match s {
+            "byte" => Ok(AbiType::Byte),
+            "bool" => Ok(AbiType::Bool),
+            "address" => Ok(AbiType::Address),
+            "string" => Ok(AbiType::String),
+            _ => match s.strip_prefix("uint").map(str::parse::<u16>) {
+                Some(Ok(bits)) if bits > 0 && bits <= 512 && bits % 8 == 0 => {
+                    Ok(AbiType::Uint(bits))
+                }
+                _ => Err(abi_error(format!("unsupported type: {}", s))),
+            },
+        }
+    }
+}
+
+/// Splits the comma separated types of a tuple, ignoring the commas of nested tuples.
+fn split_types(s: &str) -> Result<Vec<&str>, TransactionError> {
+    if s.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut types = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Err(abi_error(format!("unbalanced parentheses: {}", s))),
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(abi_error(format!("unbalanced parentheses: {}", s)));
+    }
+    types.push(&s[start..]);
+    Ok(types)
+}
+
+/// An ABI method, e.g. `add(uint64,uint64)uint64`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AbiMethod {
+    pub name: String,
+    pub args: Vec<AbiType>,
+    /// `None` for `void` methods.
+    pub returns: Option<AbiType>,
+}
+
+impl AbiMethod {
+    /// Parses a method signature, e.g. `add(uint64,uint64)uint64`.
+    pub fn from_signature(signature: &str) -> Result<AbiMethod, TransactionError> {
+        let open = signature
+            .find('(')
+            .ok_or_else(|| abi_error(format!("invalid method signature: {}", signature)))?;
+        let args_end = open + matching_paren(&signature[open..])?;
+        let args = match AbiType::from_str(&signature[open..=args_end])? {
+            AbiType::Tuple(args) => args,
+            _ => unreachable!("parenthesized types are tuples"),
+        };
+        let returns = match &signature[args_end + 1..] {
+            "void" => None,
+            returns => Some(returns.parse()?),
+        };
+        Ok(AbiMethod {
+            name: signature[..open].to_owned(),
+            args,
+            returns,
+        })
+    }
+
+    pub fn signature(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.name,
+            AbiType::Tuple(self.args.clone()),
+            self.returns
+                .as_ref()
+                .map_or("void".to_owned(), |t| t.to_string())
+        )
+    }
+
+    /// The first 4 bytes of the SHA-512/256 hash of the signature.
+    pub fn selector(&self) -> [u8; 4] {
+        method_selector(&self.signature())
+    }
+
+    /// The app arguments calling the method with `args`: the selector followed by the encoded
+    /// arguments. Arguments from the 15th are encoded together as a tuple.
+    pub fn app_arguments(&self, args: &[AbiValue]) -> Result<Vec<Vec<u8>>, TransactionError> {
+        if args.len() != self.args.len() {
+            return Err(abi_error(format!(
+                "{} expects {} arguments, got {}",
+                self.name,
+                self.args.len(),
+                args.len()
+            )));
+        }
+        let mut app_arguments = vec![self.selector().to_vec()];
+        if args.len() <= MAX_SEPARATE_ARGS {
+            for (t, value) in self.args.iter().zip(args) {
+                app_arguments.push(t.encode(value)?);
+            }
+        } else {
+            let separate = MAX_SEPARATE_ARGS - 1;
+            for (t, value) in self.args[..separate].iter().zip(args) {
+                app_arguments.push(t.encode(value)?);
+            }
+            app_arguments.push(encode_tuple(&self.args[separate..], &args[separate..])?);
+        }
+        Ok(app_arguments)
+    }
+}
+
+/// The selector of a method, from its signature (e.g. `add(uint64,uint64)uint64`): the first 4
+/// bytes of the SHA-512/256 hash of the signature.
+///
+/// The signature is hashed as is, so it has to be canonical, e.g. without spaces. Use
+/// [AbiMethod::from_signature] to validate it.
+pub fn method_selector(signature: &str) -> [u8; 4] {
+    let hash = sha2::Sha512Trunc256::digest(signature.as_bytes());
+    let mut selector = [0; 4];
+    selector.copy_from_slice(&hash[..4]);
+    selector
+}
+
+/// Decodes the value returned by an ABI method call from the logs of its app call transaction:
+/// the last log prefixed with [RETURN_PREFIX].
+pub fn decode_abi_return(
+    logs: &[Vec<u8>],
+    return_type: &AbiType,
+) -> Result<AbiValue, TransactionError> {
+    let log = logs
+        .iter()
+        .rev()
+        .find(|log| log.starts_with(&RETURN_PREFIX))
+        .ok_or_else(|| abi_error("no return value in logs".to_owned()))?;
+    return_type.decode(&log[RETURN_PREFIX.len()..])
+}
+
+/// Length of the parenthesized group at the start of `s`, up to its closing parenthesis.
+fn matching_paren(s: &str) -> Result<usize, TransactionError> {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(abi_error(format!("unbalanced parentheses: {}", s)))
+}
+
+fn abi_error(message: String) -> TransactionError {
+    TransactionError::Abi(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::HEXLOWER;
+
+    fn hex(bytes: &[u8]) -> String {
+        HEXLOWER.encode(bytes)
+    }
+
+    fn encode(t: &str, value: AbiValue) -> String {
+        hex(&t.parse::<AbiType>().unwrap().encode(&value).unwrap())
+    }
+
+    #[test]
+    fn test_method_selector() {
+        let add = AbiMethod::from_signature("add(uint64,uint64)uint64").unwrap();
+
+        assert_eq!("add", add.name);
+        assert_eq!(vec![AbiType::Uint(64), AbiType::Uint(64)], add.args);
+        assert_eq!(Some(AbiType::Uint(64)), add.returns);
+        assert_eq!("fe6bdf69", hex(&add.selector()));
+        // ARC-4 example
+        assert_eq!(
+            "8aa3b61f",
+            hex(&AbiMethod::from_signature("add(uint64,uint64)uint128")
+                .unwrap()
+                .selector())
+        );
+    }
+
+    #[test]
+    fn test_standalone_method_selector() {
+        assert_eq!(
+            "fe6bdf69",
+            hex(&method_selector("add(uint64,uint64)uint64"))
+        );
+        assert_eq!(
+            "8aa3b61f",
+            hex(&method_selector("add(uint64,uint64)uint128"))
+        );
+        let signature = "transfer(address,(uint64,byte[]),string)void";
+        assert_eq!(
+            AbiMethod::from_signature(signature).unwrap().selector(),
+            method_selector(signature)
+        );
+    }
+
+    #[test]
+    fn test_add_app_arguments() {
+        let add = AbiMethod::from_signature("add(uint64,uint64)uint64").unwrap();
+
+        let args = add
+            .app_arguments(&[AbiValue::Uint(1), AbiValue::Uint(2)])
+            .unwrap();
+
+        assert_eq!(
+            vec!["fe6bdf69", "0000000000000001", "0000000000000002"],
+            args.iter().map(|a| hex(a)).collect::<Vec<_>>()
+        );
+        assert!(add.app_arguments(&[AbiValue::Uint(1)]).is_err());
+    }
+
+    #[test]
+    fn test_app_arguments_pack_args_from_15th() {
+        let signature = format!("many({})void", vec!["uint8"; 16].join(","));
+        let method = AbiMethod::from_signature(&signature).unwrap();
+        let args: Vec<AbiValue> = (0..16).map(AbiValue::Uint).collect();
+
+        let app_arguments = method.app_arguments(&args).unwrap();
+
+        assert_eq!(16, app_arguments.len());
+        assert_eq!("0d", hex(&app_arguments[14]));
+        assert_eq!("0e0f", hex(&app_arguments[15]));
+    }
+
+    #[test]
+    fn test_encode_types() {
+        assert_eq!("0a", encode("uint8", AbiValue::Uint(10)));
+        assert_eq!(
+            "00000000000000000000000000000001",
+            encode("uint128", AbiValue::Uint(1))
+        );
+        assert_eq!("80", encode("bool", AbiValue::Bool(true)));
+        assert_eq!(
+            "000461736466",
+            encode("string", AbiValue::String("asdf".to_owned()))
+        );
+        assert_eq!(
+            "a0",
+            encode(
+                "bool[3]",
+                AbiValue::Array(vec![
+                    AbiValue::Bool(true),
+                    AbiValue::Bool(false),
+                    AbiValue::Bool(true)
+                ])
+            )
+        );
+        assert_eq!(
+            "00020102",
+            encode(
+                "byte[]",
+                AbiValue::Array(vec![AbiValue::Byte(1), AbiValue::Byte(2)])
+            )
+        );
+        // Static head (uint16, offset of byte[], bool) followed by the byte[] tail.
+        assert_eq!(
+            "000a0005800003010203",
+            encode(
+                "(uint16,byte[],bool)",
+                AbiValue::Tuple(vec![
+                    AbiValue::Uint(10),
+                    AbiValue::Array(vec![
+                        AbiValue::Byte(1),
+                        AbiValue::Byte(2),
+                        AbiValue::Byte(3)
+                    ]),
+                    AbiValue::Bool(true),
+                ])
+            )
+        );
+        assert!("uint8"
+            .parse::<AbiType>()
+            .unwrap()
+            .encode(&AbiValue::Uint(256))
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_types() {
+        for (t, value) in [
+            ("uint8", AbiValue::Uint(10)),
+            ("uint128", AbiValue::Uint(1)),
+            ("string", AbiValue::String("asdf".to_owned())),
+            ("address", AbiValue::Address(Address([3; 32]))),
+            (
+                "bool[3]",
+                AbiValue::Array(vec![
+                    AbiValue::Bool(true),
+                    AbiValue::Bool(false),
+                    AbiValue::Bool(true),
+                ]),
+            ),
+            (
+                "(uint16,byte[],bool,string)",
+                AbiValue::Tuple(vec![
+                    AbiValue::Uint(10),
+                    AbiValue::Array(vec![AbiValue::Byte(1), AbiValue::Byte(2)]),
+                    AbiValue::Bool(true),
+                    AbiValue::String("a".to_owned()),
+                ]),
+            ),
+        ] {
+            let t: AbiType = t.parse().unwrap();
+            assert_eq!(value, t.decode(&t.encode(&value).unwrap()).unwrap());
+        }
+        assert!(AbiType::Uint(64).decode(&[0; 4]).is_err());
+        assert!(AbiType::Bool.decode(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_decode_abi_return() {
+        // Logs of a confirmed `add(uint64,uint64)uint64` call with 7 and 8.
+        let logs: Vec<Vec<u8>> = ["YWRkaW5n", "FR98dQAAAAAAAAAP"]
+            .iter()
+            .map(|log| data_encoding::BASE64.decode(log.as_bytes()).unwrap())
+            .collect();
+
+        assert_eq!(
+            AbiValue::Uint(15),
+            decode_abi_return(&logs, &AbiType::Uint(64)).unwrap()
+        );
+        assert!(decode_abi_return(&logs[..1], &AbiType::Uint(64)).is_err());
+    }
+
+    #[test]
+    fn test_parse_signature_round_trip() {
+        let signature = "swap((uint64,address),string[2],bool[])void";
+
+        let method = AbiMethod::from_signature(signature).unwrap();
+
+        assert_eq!(None, method.returns);
+        assert_eq!(signature, method.signature());
+    }
+}
@@ -2,9 +2,11 @@ use std::convert::TryInto;
 
 use crate::auction::{Bid, SignedBid};
 use crate::error::TransactionError;
-use crate::transaction::{SignedTransaction, Transaction, TransactionSignature};
+use crate::transaction::{SignedTransaction, Transaction, TransactionSignature, TransactionType};
+use crate::TxnBuilder;
 use algonaut_core::{
-    Address, CompiledTeal, MultisigAddress, MultisigSignature, MultisigSubsig, ToMsgPack,
+    Address, CompiledTeal, MultisigAddress, MultisigSignature, MultisigSubsig,
+    SuggestedTransactionParams, ToMsgPack,
 };
 use algonaut_crypto::{mnemonic, Signature};
 use rand::rngs::OsRng;
@@ -119,6 +121,20 @@ impl Account {
         })
     }
 
+    /// Builds a transaction with `params` (see [TxnBuilder::with]) and signs it, for the common
+    /// case of a transaction signed by its sender alone.
+    ///
+    /// The transaction is built with [TxnBuilder::try_build]: its errors are returned before
+    /// signing.
+    pub fn build_and_sign(
+        &self,
+        params: SuggestedTransactionParams,
+        txn_type: TransactionType,
+    ) -> Result<SignedTransaction, TransactionError> {
+        let transaction = TxnBuilder::with(params, txn_type).try_build()?;
+        self.sign_transaction(&transaction)
+    }
+
     /// Sign transaction and generate a multi signature SignedTransaction
     pub fn sign_multisig_transaction(
         &self,
@@ -242,7 +258,10 @@ impl Account {
 #[cfg(test)]
 mod tests {
     use crate::account::Account;
-    use algonaut_core::Address;
+    use crate::error::TransactionError;
+    use crate::{Pay, TxnBuilder};
+    use algonaut_core::{Address, MicroAlgos, Round, SuggestedTransactionParams};
+    use algonaut_crypto::mnemonic::RedactedMnemonic;
     use algonaut_crypto::{mnemonic, Signature};
     use data_encoding::BASE64;
     use rand::Rng;
@@ -278,6 +297,39 @@ mod tests {
         assert_eq!(account.mnemonic(), mnemonic);
     }
 
+    #[test]
+    fn test_mnemonic_errors_dont_contain_words() {
+        let mnemonic = "actress tongue harbor tray suspect odor load topple vocal avoid ignore apple lunch unknown tissue museum once switch captain place lemon sail outdoor absent creek";
+        let invalid = [
+            mnemonic.replace("creek", "cream"),
+            mnemonic.replace("actress", "xyzzy"),
+            mnemonic.replace(" creek", ""),
+        ];
+
+        for invalid in &invalid {
+            let err = Account::from_mnemonic(invalid).err().unwrap();
+            let formatted = format!("{} {:?}", err, err);
+            for word in invalid.split(' ') {
+                assert!(
+                    !formatted.contains(word),
+                    "{} leaked in: {}",
+                    word,
+                    formatted
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_redacted_mnemonic() {
+        let account = Account::generate();
+        let redacted = RedactedMnemonic::new(account.mnemonic());
+
+        assert_eq!("<redacted 25-word mnemonic>", redacted.to_string());
+        assert_eq!("<redacted 25-word mnemonic>", format!("{:?}", redacted));
+        assert_eq!(account.seed(), redacted.to_key().unwrap());
+    }
+
     #[test]
     fn test_sign_bytes() {
         let mut b = rand::thread_rng().gen::<[u8; 15]>();
@@ -316,4 +368,23 @@ mod tests {
         let account = Account::from_seed(seed);
         assert_eq!(mnemonic::to_key(&account.mnemonic()).unwrap(), seed);
     }
+
+    #[test]
+    fn test_build_and_sign() {
+        let account = Account::generate();
+        let params = SuggestedTransactionParams::testnet(Round(100));
+        let payment = || Pay::new(account.address(), account.address(), MicroAlgos(5)).build();
+
+        let signed = account.build_and_sign(params.clone(), payment()).unwrap();
+
+        let built = TxnBuilder::with(params.clone(), payment()).build();
+        assert_eq!(account.sign_transaction(&built).unwrap(), signed);
+        let close_to_self = Pay::new(account.address(), account.address(), MicroAlgos(5))
+            .close_remainder_to(account.address())
+            .build();
+        assert!(matches!(
+            account.build_and_sign(params, close_to_self),
+            Err(TransactionError::ContradictoryFields(_))
+        ));
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
 
 use algonaut_core::{
@@ -6,15 +7,16 @@ use algonaut_core::{
 };
 use algonaut_crypto::{HashDigest, Signature};
 use num_traits::Num;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Serialize};
 
 use crate::{
+    auction::Bid,
     error::TransactionError,
     transaction::{
         ApplicationCallOnComplete, ApplicationCallTransaction, AssetAcceptTransaction,
         AssetClawbackTransaction, AssetConfigurationTransaction, AssetFreezeTransaction,
-        AssetParams, AssetTransferTransaction, KeyRegistration, Payment, StateSchema,
-        TransactionSignature,
+        AssetParams, AssetTransferTransaction, KeyRegistration, Payment, RawTransaction, RawValue,
+        StateSchema, TransactionSignature,
     },
     tx_group::TxGroup,
     SignedTransaction, Transaction, TransactionType,
@@ -24,7 +26,11 @@ use crate::{
 // - Fields have to be sorted alphabetically.
 // - Keys must be excluded if they've no value.
 // The signature validation fails otherwise.
+//
+// The derives generate inherent `serialize` / `deserialize` functions, wrapped by the trait impls
+// below: decoding captures the fields that aren't modeled.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(remote = "Self")]
 pub struct ApiTransaction {
     #[serde(rename = "aamt", skip_serializing_if = "Option::is_none")]
     pub asset_amount: Option<u64>,
@@ -111,8 +117,8 @@ pub struct ApiTransaction {
     #[serde(rename = "gen", skip_serializing_if = "Option::is_none")]
     pub genesis_id: Option<String>,
 
-    #[serde(rename = "gh")]
-    pub genesis_hash: HashDigest,
+    #[serde(rename = "gh", skip_serializing_if = "Option::is_none")]
+    pub genesis_hash: Option<HashDigest>,
 
     #[serde(rename = "grp", skip_serializing_if = "Option::is_none")]
     pub group: Option<HashDigest>,
@@ -163,6 +169,11 @@ pub struct ApiTransaction {
 
     #[serde(rename = "xaid", skip_serializing_if = "Option::is_none")]
     pub xfer: Option<u64>,
+
+    /// Fields of a [RawTransaction](crate::transaction::RawTransaction), merged in by
+    /// [serialize_api_transaction]. When decoding, the fields not modeled above.
+    #[serde(skip)]
+    pub raw_fields: BTreeMap<String, RawValue>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
@@ -175,14 +186,20 @@ impl From<Transaction> for ApiTransaction {
             fee: t.fee,
             first_valid: t.first_valid,
             genesis_id: t.genesis_id.clone(),
-            genesis_hash: t.genesis_hash,
+            genesis_hash: Some(t.genesis_hash),
             group: t.group,
             last_valid: t.last_valid,
             lease: t.lease,
             note: t.note.clone(),
             rekey_to: t.rekey_to,
             sender: t.sender(),
-            type_: to_api_transaction_type(&t.txn_type).to_owned(),
+            type_: match &t.txn_type {
+                TransactionType::RawTransaction(raw) => raw.type_.clone(),
+                txn_type => txn_type
+                    .tx_type()
+                    .map(|tx_type| tx_type.as_str().to_owned())
+                    .unwrap_or_default(),
+            },
             ///////////////
             asset_amount: None,
             asset_close_to: None,
@@ -214,12 +231,13 @@ impl From<Transaction> for ApiTransaction {
             xfer: None,
             nonparticipating: None,
             extra_pages: None,
+            raw_fields: BTreeMap::new(),
         };
 
         match &t.txn_type {
             TransactionType::Payment(payment) => {
                 api_t.receiver = Some(payment.receiver);
-                api_t.amount = Some(payment.amount.0);
+                api_t.amount = as_api_option(payment.amount.0);
                 api_t.close_reminder_to = payment.close_remainder_to;
             }
             TransactionType::KeyRegistration(reg) => {
@@ -275,6 +293,9 @@ impl From<Transaction> for ApiTransaction {
                     call.to_owned().local_state_schema.and_then(|s| s.into());
                 api_t.extra_pages = call.extra_pages.and_then(as_api_option);
             }
+            TransactionType::RawTransaction(raw) => {
+                api_t.raw_fields = raw.fields.clone();
+            }
         }
         api_t
     }
@@ -290,9 +311,7 @@ impl TryFrom<ApiTransaction> for Transaction {
                 receiver: api_t.receiver.ok_or_else(|| {
                     TransactionError::Deserialization("receiver missing".to_owned())
                 })?,
-                amount: MicroAlgos(api_t.amount.ok_or_else(|| {
-                    TransactionError::Deserialization("amount missing".to_owned())
-                })?),
+                amount: MicroAlgos(from_api_option(api_t.amount)),
                 close_remainder_to: api_t.close_reminder_to,
             }),
             "keyreg" => TransactionType::KeyRegistration(KeyRegistration {
@@ -307,11 +326,11 @@ impl TryFrom<ApiTransaction> for Transaction {
             "acfg" => {
                 TransactionType::AssetConfigurationTransaction(AssetConfigurationTransaction {
                     sender: api_t.sender,
-                    params: None, // TODO
+                    params: api_t.asset_params.clone().map(|p| p.into()),
                     config_asset: api_t.config_asset,
                 })
             }
-            "axfer" => parse_asset_transfer_transaction(&api_t),
+            "axfer" => parse_asset_transfer_transaction(&api_t)?,
             "afrz" => TransactionType::AssetFreezeTransaction(AssetFreezeTransaction {
                 sender: api_t.sender,
                 freeze_account: api_t.freeze_account.ok_or_else(|| {
@@ -327,32 +346,35 @@ impl TryFrom<ApiTransaction> for Transaction {
             "appl" => TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
                 sender: api_t.sender,
                 app_id: api_t.app_id,
-                on_complete: match api_t.on_complete {
-                    Some(oc) => int_to_application_call_on_complete(oc)?,
-                    None => return Err(TransactionError::Deserialization("TODO confirm that receiving non existent on_complete key from API means 0/NoOp OnComplete".to_owned())),
-                },
+                on_complete: int_to_application_call_on_complete(from_api_option(
+                    api_t.on_complete,
+                ))?,
                 accounts: api_t.accounts,
                 approval_program: api_t.approval_program.map(CompiledTeal),
-                app_arguments: api_t.app_arguments.map(|args| args.into_iter().map(|a| a.0).collect()),
+                app_arguments: api_t
+                    .app_arguments
+                    .map(|args| args.into_iter().map(|a| a.0).collect()),
                 clear_state_program: api_t.clear_state_program.map(CompiledTeal),
                 foreign_apps: api_t.foreign_apps,
                 foreign_assets: api_t.foreign_assets,
                 global_state_schema: api_t.global_state_schema.map(|s| s.into()),
                 local_state_schema: api_t.local_state_schema.map(|s| s.into()),
-                extra_pages: api_t.extra_pages
+                extra_pages: api_t.extra_pages,
+            }),
+            // Types the SDK doesn't know yet are kept raw, with their specific fields.
+            unknown_type => TransactionType::RawTransaction(RawTransaction {
+                sender: api_t.sender,
+                type_: unknown_type.to_owned(),
+                fields: unknown_type_fields(&api_t)?,
             }),
-            unsupported_type => {
-                return Err(TransactionError::Deserialization(format!(
-                    "Not supported transaction type: {}",
-                    unsupported_type
-                )))
-            }
         };
         Ok(Transaction {
             fee: api_t.fee,
             first_valid: api_t.first_valid,
             genesis_id: api_t.genesis_id,
-            genesis_hash: api_t.genesis_hash,
+            genesis_hash: api_t.genesis_hash.ok_or_else(|| {
+                TransactionError::Deserialization("genesis hash missing".to_owned())
+            })?,
             group: api_t.group,
             last_valid: api_t.last_valid,
             lease: api_t.lease,
@@ -363,39 +385,42 @@ impl TryFrom<ApiTransaction> for Transaction {
     }
 }
 
-fn parse_asset_transfer_transaction(api_t: &ApiTransaction) -> TransactionType {
-    match (
-        api_t.xfer,
-        api_t.asset_sender,
-        api_t.asset_receiver,
-        api_t.asset_amount,
-    ) {
-        (Some(xfer), Some(asset_sender), Some(asset_receiver), Some(asset_amount)) => {
+fn parse_asset_transfer_transaction(
+    api_t: &ApiTransaction,
+) -> Result<TransactionType, TransactionError> {
+    let xfer = api_t
+        .xfer
+        .ok_or_else(|| TransactionError::Deserialization("xfer missing".to_owned()))?;
+    // Zero amounts are omitted, so an opt-in is a transfer without amount to the sender.
+    match (api_t.asset_sender, api_t.asset_receiver, api_t.asset_amount) {
+        (Some(asset_sender), Some(asset_receiver), asset_amount) => Ok(
             TransactionType::AssetClawbackTransaction(AssetClawbackTransaction {
                 sender: api_t.sender,
                 xfer,
-                asset_amount,
+                asset_amount: from_api_option(asset_amount),
                 asset_sender,
                 asset_receiver,
                 asset_close_to: api_t.asset_close_to,
-            })
-        }
-        (Some(xfer), None, Some(asset_receiver), Some(asset_amount)) => {
+            }),
+        ),
+        (None, Some(asset_receiver), None) if asset_receiver == api_t.sender => Ok(
+            TransactionType::AssetAcceptTransaction(AssetAcceptTransaction {
+                sender: api_t.sender,
+                xfer,
+            }),
+        ),
+        (None, Some(asset_receiver), asset_amount) => Ok(
             TransactionType::AssetTransferTransaction(AssetTransferTransaction {
                 sender: api_t.sender,
                 xfer,
-                amount: asset_amount,
+                amount: from_api_option(asset_amount),
                 receiver: asset_receiver,
                 close_to: api_t.asset_close_to,
-            })
-        }
-        (Some(xfer), None, None, None) => {
-            TransactionType::AssetAcceptTransaction(AssetAcceptTransaction {
-                sender: api_t.sender,
-                xfer,
-            })
-        }
-        _ => panic!(),
+            }),
+        ),
+        _ => Err(TransactionError::Deserialization(
+            "asset receiver missing".to_owned(),
+        )),
     }
 }
 
@@ -403,9 +428,10 @@ impl TryFrom<ApiSignedTransaction> for SignedTransaction {
     type Error = TransactionError;
 
     fn try_from(api_t: ApiSignedTransaction) -> Result<Self, Self::Error> {
+        let transaction: Transaction = api_t.transaction.clone().try_into()?;
         Ok(SignedTransaction {
-            transaction: api_t.transaction.clone().try_into()?,
-            transaction_id: api_t.transaction_id.clone(),
+            transaction_id: transaction.id()?,
+            transaction,
             sig: transaction_signature(&api_t)?,
         })
     }
@@ -442,7 +468,7 @@ pub struct ApiAssetParams {
     #[serde(rename = "dc")]
     pub decimals: Option<u32>,
 
-    #[serde(rename = "df", skip_serializing)]
+    #[serde(rename = "df", skip_serializing_if = "Option::is_none")]
     pub default_frozen: Option<bool>,
 
     #[serde(rename = "f", skip_serializing_if = "Option::is_none")]
@@ -497,19 +523,6 @@ impl From<ApiAssetParams> for AssetParams {
     }
 }
 
-fn to_api_transaction_type<'a>(type_: &TransactionType) -> &'a str {
-    match type_ {
-        TransactionType::Payment(_) => "pay",
-        TransactionType::KeyRegistration(_) => "keyreg",
-        TransactionType::AssetConfigurationTransaction(_) => "acfg",
-        TransactionType::AssetTransferTransaction(_) => "axfer",
-        TransactionType::AssetAcceptTransaction(_) => "axfer",
-        TransactionType::AssetClawbackTransaction(_) => "axfer",
-        TransactionType::AssetFreezeTransaction(_) => "afrz",
-        TransactionType::ApplicationCallTransaction(_) => "appl",
-    }
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApiSignedTransaction {
     #[serde(rename = "lsig", skip_serializing_if = "Option::is_none")]
@@ -521,13 +534,83 @@ pub struct ApiSignedTransaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sig: Option<Signature>,
 
-    #[serde(rename = "txn")]
+    #[serde(rename = "txn", serialize_with = "serialize_api_transaction")]
     pub transaction: ApiTransaction,
 
     #[serde(skip)]
     pub transaction_id: String,
 }
 
+/// A signed transaction as stored in a block's payset: the genesis id and hash are
+/// omitted from the transaction and flagged with `hgi` / `hgh` instead. It's stored alongside the
+/// apply data, the effects of the transaction computed when it was evaluated.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiSignedTransactionInBlock {
+    #[serde(rename = "aca", skip_serializing_if = "Option::is_none")]
+    pub asset_closing_amount: Option<u64>,
+
+    #[serde(rename = "apid", skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<u64>,
+
+    #[serde(rename = "ca", skip_serializing_if = "Option::is_none")]
+    pub closing_amount: Option<u64>,
+
+    #[serde(rename = "caid", skip_serializing_if = "Option::is_none")]
+    pub config_asset: Option<u64>,
+
+    #[serde(rename = "hgh", skip_serializing_if = "Option::is_none")]
+    pub has_genesis_hash: Option<bool>,
+
+    #[serde(rename = "hgi", skip_serializing_if = "Option::is_none")]
+    pub has_genesis_id: Option<bool>,
+
+    #[serde(rename = "lsig", skip_serializing_if = "Option::is_none")]
+    pub lsig: Option<ApiSignedLogic>,
+
+    #[serde(rename = "msig", skip_serializing_if = "Option::is_none")]
+    pub msig: Option<MultisigSignature>,
+
+    #[serde(rename = "rc", skip_serializing_if = "Option::is_none")]
+    pub close_rewards: Option<u64>,
+
+    #[serde(rename = "rr", skip_serializing_if = "Option::is_none")]
+    pub receiver_rewards: Option<u64>,
+
+    #[serde(rename = "rs", skip_serializing_if = "Option::is_none")]
+    pub sender_rewards: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sig: Option<Signature>,
+
+    #[serde(rename = "txn", serialize_with = "serialize_api_transaction")]
+    pub transaction: ApiTransaction,
+}
+
+impl ApiSignedTransactionInBlock {
+    /// Restores the fields the block omitted and converts to a `SignedTransaction`.
+    pub fn into_signed_transaction(
+        self,
+        genesis_id: Option<&str>,
+        genesis_hash: Option<HashDigest>,
+    ) -> Result<SignedTransaction, TransactionError> {
+        let mut transaction = self.transaction;
+        if self.has_genesis_id == Some(true) {
+            transaction.genesis_id = genesis_id.map(|id| id.to_owned());
+        }
+        if self.has_genesis_hash == Some(true) {
+            transaction.genesis_hash = genesis_hash;
+        }
+        let api_t = ApiSignedTransaction {
+            lsig: self.lsig,
+            msig: self.msig,
+            sig: self.sig,
+            transaction,
+            transaction_id: String::new(),
+        };
+        api_t.try_into()
+    }
+}
+
 impl From<SignedTransaction> for ApiSignedTransaction {
     fn from(t: SignedTransaction) -> Self {
         let (sig, msig, lsig) = match t.sig {
@@ -578,11 +661,193 @@ impl From<ApiStateSchema> for StateSchema {
     }
 }
 
-impl ToMsgPack for ApiTransaction {}
-impl ToMsgPack for ApiSignedTransaction {}
-impl ToMsgPack for Transaction {}
-impl ToMsgPack for SignedTransaction {}
-impl ToMsgPack for TxGroup {}
+impl Serialize for ApiTransaction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ApiTransaction::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ApiTransaction {
+    /// Decodes the modeled fields directly and keeps the others as [raw_fields](Self::raw_fields),
+    /// without buffering the transaction.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut raw_fields = BTreeMap::new();
+        let mut api_t = ApiTransaction::deserialize(CapturingDeserializer {
+            inner: deserializer,
+            raw_fields: &mut raw_fields,
+        })?;
+        api_t.raw_fields = raw_fields;
+        Ok(api_t)
+    }
+}
+
+/// Passes a struct's fields through to its (derived) visitor, capturing the fields the struct
+/// doesn't declare in `raw_fields`.
+struct CapturingDeserializer<'a, D> {
+    inner: D,
+    raw_fields: &'a mut BTreeMap<String, RawValue>,
+}
+
+impl<'a, 'de, D: serde::Deserializer<'de>> serde::Deserializer<'de>
+    for CapturingDeserializer<'a, D>
+{
+    type Error = D::Error;
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.inner.deserialize_struct(
+            name,
+            fields,
+            CapturingVisitor {
+                visitor,
+                fields,
+                raw_fields: self.raw_fields,
+            },
+        )
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.inner.deserialize_any(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct CapturingVisitor<'a, V> {
+    visitor: V,
+    fields: &'static [&'static str],
+    raw_fields: &'a mut BTreeMap<String, RawValue>,
+}
+
+impl<'a, 'de, V: de::Visitor<'de>> de::Visitor<'de> for CapturingVisitor<'a, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_seq(seq)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_map(CapturingMap {
+            map,
+            fields: self.fields,
+            raw_fields: self.raw_fields,
+        })
+    }
+}
+
+struct CapturingMap<'a, A> {
+    map: A,
+    fields: &'static [&'static str],
+    raw_fields: &'a mut BTreeMap<String, RawValue>,
+}
+
+impl<'a, 'de, A: de::MapAccess<'de>> de::MapAccess<'de> for CapturingMap<'a, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        while let Some(key) = self.map.next_key::<String>()? {
+            if self.fields.contains(&key.as_str()) {
+                return seed
+                    .deserialize(de::IntoDeserializer::<Self::Error>::into_deserializer(key))
+                    .map(Some);
+            }
+            let value = self.map.next_value()?;
+            self.raw_fields.insert(key, value);
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.map.next_value_seed(seed)
+    }
+}
+
+/// The type specific fields of a transaction of unknown type: all the fields but the common ones,
+/// including those that happen to share a name with a modeled field, which are encoded back.
+///
+/// Only called for unknown types: the transactions of known types are decoded in one pass.
+fn unknown_type_fields(
+    api_t: &ApiTransaction,
+) -> Result<BTreeMap<String, RawValue>, TransactionError> {
+    const COMMON_FIELDS: [&str; 11] = [
+        "fee", "fv", "gen", "gh", "grp", "lv", "lx", "note", "rekey", "snd", "type",
+    ];
+    let encoded = rmp_serde::to_vec_named(api_t)?;
+    let mut fields: BTreeMap<String, RawValue> = rmp_serde::from_read_ref(&encoded)
+        .map_err(|e| TransactionError::Deserialization(e.to_string()))?;
+    fields.retain(|key, _| !COMMON_FIELDS.contains(&key.as_str()));
+    fields.extend(api_t.raw_fields.clone());
+    Ok(fields)
+}
+
+/// Serializes the transaction, merging the raw fields (if any) into the canonical (sorted) encoding.
+pub fn serialize_api_transaction<S>(t: &ApiTransaction, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if t.raw_fields.is_empty() {
+        return t.serialize(serializer);
+    }
+    let encoded = rmp_serde::to_vec_named(t).map_err(serde::ser::Error::custom)?;
+    let mut fields: BTreeMap<String, RawValue> =
+        rmp_serde::from_read_ref(&encoded).map_err(serde::ser::Error::custom)?;
+    for (key, value) in &t.raw_fields {
+        fields.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+    fields.serialize(serializer)
+}
+
+/// The SDK's types are serialized with their keys in order: they're encoded without sorting them
+/// again through the trait's default.
+macro_rules! impl_to_msg_pack_sorted {
+    ($($t:ty),*) => {
+        $(
+            impl ToMsgPack for $t {
+                fn to_msg_pack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+                    rmp_serde::to_vec_named(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_msg_pack_sorted!(
+    ApiTransaction,
+    ApiSignedTransaction,
+    Transaction,
+    SignedTransaction,
+    TxGroup,
+    Bid
+);
 
 /// Convenience to serialize Transaction directly to msg pack
 impl Serialize for Transaction {
@@ -591,7 +856,7 @@ impl Serialize for Transaction {
         S: serde::Serializer,
     {
         let api_transaction: ApiTransaction = self.to_owned().into();
-        api_transaction.serialize(serializer)
+        serialize_api_transaction(&api_transaction, serializer)
     }
 }
 
@@ -733,6 +998,28 @@ fn int_to_application_call_on_complete(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_deserialize_api_transaction_captures_unmodeled_fields() {
+        let mut fields = BTreeMap::new();
+        fields.insert("amt".to_owned(), RawValue::Int(5));
+        fields.insert("fee".to_owned(), RawValue::Int(1_000));
+        fields.insert("fv".to_owned(), RawValue::Int(1));
+        fields.insert("lv".to_owned(), RawValue::Int(1_001));
+        fields.insert("snd".to_owned(), RawValue::Bytes(vec![1; 32]));
+        fields.insert("type".to_owned(), RawValue::String("pay".to_owned()));
+        fields.insert("zzz".to_owned(), RawValue::Bool(true));
+
+        let api_t: ApiTransaction =
+            rmp_serde::from_slice(&rmp_serde::to_vec_named(&fields).unwrap()).unwrap();
+
+        assert_eq!(Some(5), api_t.amount);
+        assert_eq!(Address([1; 32]), api_t.sender);
+        assert_eq!(
+            vec![("zzz".to_owned(), RawValue::Bool(true))],
+            api_t.raw_fields.into_iter().collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_serialize_signed_logic_contract_account() {
         let program = CompiledTeal(vec![
@@ -0,0 +1,125 @@
+//! Helpers to fill asset params following the [ARC-3](https://github.com/algorandfoundation/ARCs/blob/main/ARCs/arc-0003.md)
+//! and [ARC-19](https://github.com/algorandfoundation/ARCs/blob/main/ARCs/arc-0019.md) conventions.
+
+use crate::error::TransactionError;
+use algonaut_core::Address;
+use data_encoding::BASE32_NOPAD;
+use sha2::Digest;
+
+const CID_VERSION: u8 = 1;
+const SHA2_256: u8 = 0x12;
+const SHA2_256_LEN: u8 = 32;
+
+/// ARC-3 metadata hash: the SHA-256 digest of the JSON metadata file, to be used as the
+/// asset's `meta_data_hash`.
+pub fn arc3_metadata_hash(json: &str) -> [u8; 32] {
+    sha2::Sha256::digest(json.as_bytes()).into()
+}
+
+/// Multicodec of the content addressed by an ARC-19 CID.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IpfsCodec {
+    Raw,
+    DagPb,
+}
+
+impl IpfsCodec {
+    fn code(&self) -> u8 {
+        match self {
+            IpfsCodec::Raw => 0x55,
+            IpfsCodec::DagPb => 0x70,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            IpfsCodec::Raw => "raw",
+            IpfsCodec::DagPb => "dag-pb",
+        }
+    }
+}
+
+/// ARC-19 template URL, to be used as the asset's `url`. The CID's digest is stored in the reserve
+/// address, see [arc19_reserve_address].
+pub fn arc19_template_url(codec: IpfsCodec) -> String {
+    format!(
+        "template-ipfs://{{ipfscid:{}:{}:reserve:sha2-256}}",
+        CID_VERSION,
+        codec.name()
+    )
+}
+
+/// Reserve address encoding the SHA-256 digest of a base32 CIDv1 (e.g. `bafkrei...`).
+pub fn arc19_reserve_address(cid: &str) -> Result<Address, TransactionError> {
+    let encoded = cid
+        .strip_prefix('b')
+        .ok_or_else(|| invalid_cid("only base32 CIDv1 are supported"))?;
+    let bytes = BASE32_NOPAD
+        .decode(encoded.to_uppercase().as_bytes())
+        .map_err(|e| invalid_cid(&e.to_string()))?;
+    match bytes.as_slice() {
+        [CID_VERSION, codec, SHA2_256, SHA2_256_LEN, digest @ ..]
+            if digest.len() == SHA2_256_LEN as usize
+                && [IpfsCodec::Raw, IpfsCodec::DagPb]
+                    .iter()
+                    .any(|c| c.code() == *codec) =>
+        {
+            let mut reserve = [0; 32];
+            reserve.copy_from_slice(digest);
+            Ok(Address::new(reserve))
+        }
+        _ => Err(invalid_cid(
+            "expected a sha2-256 CIDv1 with raw or dag-pb codec",
+        )),
+    }
+}
+
+/// Base32 CIDv1 resolved from an ARC-19 reserve address.
+pub fn arc19_cid(codec: IpfsCodec, reserve: &Address) -> String {
+    let mut bytes = vec![CID_VERSION, codec.code(), SHA2_256, SHA2_256_LEN];
+    bytes.extend_from_slice(&reserve.0);
+    format!("b{}", BASE32_NOPAD.encode(&bytes).to_lowercase())
+}
+
+fn invalid_cid(reason: &str) -> TransactionError {
+    TransactionError::Deserialization(format!("Invalid CID: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Raw CIDv1 of the bytes "hello".
+    const HELLO_CID: &str = "bafkreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq";
+
+    #[test]
+    fn test_arc3_metadata_hash() {
+        let metadata = r#"{"name":"My Song","description":"My first and best song!","image":"https://s3.amazonaws.com/your-bucket/song/cover/mysong.png","image_integrity":"sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=","image_mimetype":"image/png","properties":{"simple_property":"example value"}}"#;
+
+        assert_eq!(
+            "5f4598d629e9d0b05eb5159a0398c4d734a068d0064157ea081a36a90e749f92",
+            data_encoding::HEXLOWER.encode(&arc3_metadata_hash(metadata))
+        );
+    }
+
+    #[test]
+    fn test_arc19_template_url() {
+        assert_eq!(
+            "template-ipfs://{ipfscid:1:raw:reserve:sha2-256}",
+            arc19_template_url(IpfsCodec::Raw)
+        );
+    }
+
+    #[test]
+    fn test_arc19_reserve_address_round_trip() {
+        let reserve = arc19_reserve_address(HELLO_CID).unwrap();
+
+        assert_eq!(arc3_metadata_hash("hello"), reserve.0);
+        assert_eq!(HELLO_CID, arc19_cid(IpfsCodec::Raw, &reserve));
+    }
+
+    #[test]
+    fn test_arc19_rejects_cid_v0() {
+        assert!(arc19_reserve_address("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u").is_err());
+    }
+}
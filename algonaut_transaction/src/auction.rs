@@ -1,4 +1,4 @@
-use algonaut_core::{Address, ToMsgPack};
+use algonaut_core::Address;
 use algonaut_crypto::Signature;
 use serde::{Deserialize, Serialize};
 
@@ -35,5 +35,3 @@ pub struct SignedBid {
     /// A signature by the bidder, as identified in the bid ([Bid.bidder_key]) over the hash of the Bid.
     pub sig: Signature,
 }
-
-impl ToMsgPack for Bid {}
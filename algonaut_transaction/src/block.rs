@@ -0,0 +1,734 @@
+use crate::api_model::ApiSignedTransactionInBlock;
+use crate::error::TransactionError;
+use crate::{SignedTransaction, TxType};
+use algonaut_core::MicroAlgos;
+use algonaut_crypto::HashDigest;
+use rmp::Marker;
+use serde::Deserialize;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// A signed transaction of a block, with the apply data recorded when it was evaluated.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SignedTransactionInBlock {
+    pub signed_transaction: SignedTransaction,
+    /// Id of the asset created by the transaction, if it's an asset creation.
+    pub created_asset_id: Option<u64>,
+    /// Id of the application created by the transaction, if it's an application creation.
+    pub created_application_id: Option<u64>,
+    pub sender_rewards: MicroAlgos,
+    pub receiver_rewards: MicroAlgos,
+    /// Rewards paid to the close remainder to account.
+    pub close_rewards: MicroAlgos,
+    /// Amount of algos sent to the close remainder to account.
+    pub closing_amount: MicroAlgos,
+    /// Amount of the asset sent to the asset close to account.
+    pub asset_closing_amount: u64,
+}
+
+/// Decodes the signed transactions of a msgpack-encoded block response
+/// (`/v2/blocks/{round}?format=msgpack`) as its bytes arrive.
+///
+/// The bytes already decoded are dropped when more are pushed, so only the transaction being
+/// decoded (and the chunk it arrived in) is buffered, never the whole response.
+#[derive(Debug)]
+pub struct BlockTransactionsDecoder {
+    buf: Vec<u8>,
+    pos: usize,
+    state: State,
+    genesis_id: Option<String>,
+    genesis_hash: Option<HashDigest>,
+}
+
+/// Where the decoder is in the response. The counts are the map keys or payset entries left.
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Response,
+    ResponseKey(u32),
+    ResponseValue(u32),
+    Block,
+    BlockKey(u32),
+    BlockValue(BlockField, u32),
+    Payset,
+    Transactions(u32),
+    Done,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BlockField {
+    GenesisId,
+    GenesisHash,
+    Other,
+}
+
+impl BlockTransactionsDecoder {
+    pub fn new() -> BlockTransactionsDecoder {
+        BlockTransactionsDecoder::with_bytes(vec![])
+    }
+
+    fn with_bytes(buf: Vec<u8>) -> BlockTransactionsDecoder {
+        BlockTransactionsDecoder {
+            buf,
+            pos: 0,
+            state: State::Response,
+            genesis_id: None,
+            genesis_hash: None,
+        }
+    }
+
+    /// Appends the next bytes of the response.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Number of bytes currently buffered.
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Decodes the next transaction with its apply data (created ids, rewards...).
+    ///
+    /// `None` if more bytes have to be pushed first, or if the payset is over (see
+    /// [is_done](Self::is_done)). After an error, the decoder is done.
+    pub fn next_transaction(
+        &mut self,
+    ) -> Result<Option<SignedTransactionInBlock>, TransactionError> {
+        let next = match self.next_entry() {
+            Ok(Some(entry)) => self.decode_entry(entry).map(Some),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+        if next.is_err() {
+            self.state = State::Done;
+        }
+        next
+    }
+
+    /// Whether all the transactions of the block have been decoded.
+    pub fn is_done(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+
+    /// Checks that all the transactions were decoded, once all the bytes have been pushed.
+    pub fn finish(&self) -> Result<(), TransactionError> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(TransactionError::Deserialization(
+                "unexpected end of the block response".to_owned(),
+            ))
+        }
+    }
+
+    fn remaining(&self) -> u32 {
+        match self.state {
+            State::Transactions(remaining) => remaining,
+            _ => 0,
+        }
+    }
+
+    /// The position of the next (complete) payset entry, `None` if more bytes are needed or the
+    /// payset is over.
+    fn next_entry(&mut self) -> Result<Option<Range<usize>>, TransactionError> {
+        if !self.advance()? {
+            return Ok(None);
+        }
+        let remaining = match self.state {
+            State::Transactions(remaining) => remaining,
+            _ => return Ok(None),
+        };
+        let entry = self.value()?;
+        if entry.is_some() {
+            self.state = State::Transactions(remaining - 1);
+        }
+        Ok(entry)
+    }
+
+    /// Reads the response up to the next payset entry, `false` if more bytes are needed first.
+    fn advance(&mut self) -> Result<bool, TransactionError> {
+        loop {
+            let state = self.state;
+            self.state = match state {
+                State::Response => {
+                    match self.container_len(|rd: &mut &[u8]| rmp::decode::read_map_len(rd))? {
+                        Some(keys) => State::ResponseKey(keys),
+                        None => return Ok(false),
+                    }
+                }
+                State::ResponseKey(0) => {
+                    return Err(TransactionError::Deserialization(
+                        "block missing in response".to_owned(),
+                    ))
+                }
+                State::ResponseKey(keys) => match self.value()? {
+                    Some(key) => match self.decode::<&str>(key)? {
+                        "block" => State::Block,
+                        _ => State::ResponseValue(keys - 1),
+                    },
+                    None => return Ok(false),
+                },
+                State::ResponseValue(keys) => match self.value()? {
+                    Some(_) => State::ResponseKey(keys),
+                    None => return Ok(false),
+                },
+                State::Block => {
+                    match self.container_len(|rd: &mut &[u8]| rmp::decode::read_map_len(rd))? {
+                        Some(keys) => State::BlockKey(keys),
+                        None => return Ok(false),
+                    }
+                }
+                // Empty blocks omit the payset.
+                State::BlockKey(0) => State::Done,
+                State::BlockKey(keys) => match self.value()? {
+                    Some(key) => match self.decode::<&str>(key)? {
+                        "txns" => State::Payset,
+                        "gen" => State::BlockValue(BlockField::GenesisId, keys - 1),
+                        "gh" => State::BlockValue(BlockField::GenesisHash, keys - 1),
+                        _ => State::BlockValue(BlockField::Other, keys - 1),
+                    },
+                    None => return Ok(false),
+                },
+                State::BlockValue(field, keys) => match self.value()? {
+                    Some(value) => {
+                        match field {
+                            BlockField::GenesisId => self.genesis_id = Some(self.decode(value)?),
+                            BlockField::GenesisHash => {
+                                self.genesis_hash = Some(self.decode(value)?)
+                            }
+                            BlockField::Other => {}
+                        }
+                        State::BlockKey(keys)
+                    }
+                    None => return Ok(false),
+                },
+                State::Payset => {
+                    match self.container_len(|rd: &mut &[u8]| rmp::decode::read_array_len(rd))? {
+                        Some(transactions) => State::Transactions(transactions),
+                        None => return Ok(false),
+                    }
+                }
+                State::Transactions(0) => State::Done,
+                State::Transactions(_) | State::Done => return Ok(true),
+            };
+        }
+    }
+
+    /// Reads the header of the map or array at the position, `None` if it's incomplete.
+    fn container_len<F, E>(&mut self, read_len: F) -> Result<Option<u32>, TransactionError>
+    where
+        F: FnOnce(&mut &[u8]) -> Result<u32, E>,
+        E: Display,
+    {
+        let rest = &self.buf[self.pos..];
+        match item_head(rest)? {
+            Some((head, _)) if head <= rest.len() => {
+                let len = read_len(&mut &rest[..head]).map_err(deserialization)?;
+                self.pos += head;
+                Ok(Some(len))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads the value at the position, `None` if it's incomplete.
+    fn value(&mut self) -> Result<Option<Range<usize>>, TransactionError> {
+        Ok(value_len(&self.buf[self.pos..])?.map(|len| {
+            self.pos += len;
+            self.pos - len..self.pos
+        }))
+    }
+
+    fn decode<'a, T: Deserialize<'a>>(
+        &'a self,
+        range: Range<usize>,
+    ) -> Result<T, TransactionError> {
+        rmp_serde::from_read_ref(&self.buf[range]).map_err(deserialization)
+    }
+
+    fn decode_entry(
+        &self,
+        entry: Range<usize>,
+    ) -> Result<SignedTransactionInBlock, TransactionError> {
+        let api_t: ApiSignedTransactionInBlock = self.decode(entry)?;
+        Ok(SignedTransactionInBlock {
+            created_asset_id: api_t.config_asset,
+            created_application_id: api_t.application_id,
+            sender_rewards: MicroAlgos(api_t.sender_rewards.unwrap_or_default()),
+            receiver_rewards: MicroAlgos(api_t.receiver_rewards.unwrap_or_default()),
+            close_rewards: MicroAlgos(api_t.close_rewards.unwrap_or_default()),
+            closing_amount: MicroAlgos(api_t.closing_amount.unwrap_or_default()),
+            asset_closing_amount: api_t.asset_closing_amount.unwrap_or_default(),
+            signed_transaction: api_t
+                .into_signed_transaction(self.genesis_id.as_deref(), self.genesis_hash)?,
+        })
+    }
+
+    /// The type of the transaction of a payset entry, `None` if not known to the SDK.
+    fn entry_type(&self, entry: Range<usize>) -> Result<Option<TxType>, TransactionError> {
+        #[derive(Deserialize)]
+        struct TypeField<'a> {
+            #[serde(rename = "type", borrow)]
+            type_: &'a str,
+        }
+        #[derive(Deserialize)]
+        struct InBlockTypeField<'a> {
+            #[serde(borrow)]
+            txn: TypeField<'a>,
+        }
+
+        let field: InBlockTypeField = self.decode(entry)?;
+        Ok(field.txn.type_.parse().ok())
+    }
+}
+
+impl Default for BlockTransactionsDecoder {
+    fn default() -> Self {
+        BlockTransactionsDecoder::new()
+    }
+}
+
+/// Lazily decodes the signed transactions of a msgpack-encoded block response held in memory.
+///
+/// Each transaction is decoded when the iterator reaches it, so the parsed payset is never
+/// materialized as a whole, but the response is: to not hold it entirely, e.g. for large archival
+/// blocks, push its bytes to a [BlockTransactionsDecoder] as they arrive.
+#[derive(Debug)]
+pub struct BlockTransactions {
+    decoder: BlockTransactionsDecoder,
+}
+
+impl BlockTransactions {
+    /// Reads the block header up to the payset.
+    pub fn new(bytes: Vec<u8>) -> Result<BlockTransactions, TransactionError> {
+        let mut decoder = BlockTransactionsDecoder::with_bytes(bytes);
+        if !decoder.advance()? {
+            decoder.finish()?;
+        }
+        Ok(BlockTransactions { decoder })
+    }
+
+    /// Number of transactions not yet decoded.
+    pub fn remaining(&self) -> usize {
+        self.decoder.remaining() as usize
+    }
+
+    /// Decodes the remaining transactions with their apply data (created ids, rewards...).
+    ///
+    /// Unlike iterating, this materializes the whole (remaining) payset.
+    pub fn signed_transactions(
+        mut self,
+    ) -> Result<Vec<SignedTransactionInBlock>, TransactionError> {
+        let mut transactions = Vec::with_capacity(self.remaining());
+        while let Some(transaction) = self.decoder.next_transaction()? {
+            transactions.push(transaction);
+        }
+        self.decoder.finish()?;
+        Ok(transactions)
+    }
+
+    /// Only the transactions of type `tx_type`, e.g. the payments of the block.
+    ///
+    /// The type of each transaction is read before decoding it, so the other transactions are
+    /// skipped without being decoded.
+    pub fn transactions_of_type(
+        mut self,
+        tx_type: TxType,
+    ) -> impl Iterator<Item = Result<SignedTransaction, TransactionError>> {
+        std::iter::from_fn(move || loop {
+            let next = match self.next_entry() {
+                Ok(Some(entry)) => match self.decoder.entry_type(entry.clone()) {
+                    Ok(Some(next_type)) if next_type == tx_type => self
+                        .decoder
+                        .decode_entry(entry)
+                        .map(|t| t.signed_transaction),
+                    Ok(_) => continue,
+                    Err(e) => Err(e),
+                },
+                Ok(None) => return None,
+                Err(e) => Err(e),
+            };
+            if next.is_err() {
+                self.decoder.state = State::Done;
+            }
+            return Some(next);
+        })
+    }
+
+    /// The next payset entry, failing if the response ends before the payset.
+    fn next_entry(&mut self) -> Result<Option<Range<usize>>, TransactionError> {
+        let entry = self.decoder.next_entry()?;
+        if entry.is_none() {
+            self.decoder.finish()?;
+        }
+        Ok(entry)
+    }
+}
+
+impl Iterator for BlockTransactions {
+    type Item = Result<SignedTransaction, TransactionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match self.decoder.next_transaction() {
+            Ok(Some(transaction)) => Ok(transaction.signed_transaction),
+            Ok(None) => match self.decoder.finish() {
+                Ok(()) => return None,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        if next.is_err() {
+            self.decoder.state = State::Done;
+        }
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining()))
+    }
+}
+
+/// The length of the msgpack item at the start of `buf`, without its nested values, and the
+/// number of nested values. `None` if `buf` ends before the length is known.
+fn item_head(buf: &[u8]) -> Result<Option<(usize, u64)>, TransactionError> {
+    let marker = match buf.first() {
+        Some(&byte) => Marker::from_u8(byte),
+        None => return Ok(None),
+    };
+    let len = |size: usize| {
+        buf.get(1..1 + size)
+            .map(|bytes| bytes.iter().fold(0, |len, &b| len << 8 | b as usize))
+    };
+    Ok(match marker {
+        Marker::FixPos(_) | Marker::FixNeg(_) | Marker::Null | Marker::True | Marker::False => {
+            Some((1, 0))
+        }
+        Marker::U8 | Marker::I8 => Some((2, 0)),
+        Marker::U16 | Marker::I16 | Marker::FixExt1 => Some((3, 0)),
+        Marker::FixExt2 => Some((4, 0)),
+        Marker::U32 | Marker::I32 | Marker::F32 => Some((5, 0)),
+        Marker::FixExt4 => Some((6, 0)),
+        Marker::U64 | Marker::I64 | Marker::F64 => Some((9, 0)),
+        Marker::FixExt8 => Some((10, 0)),
+        Marker::FixExt16 => Some((18, 0)),
+        Marker::FixStr(n) => Some((1 + n as usize, 0)),
+        Marker::Str8 | Marker::Bin8 => len(1).map(|n| (2 + n, 0)),
+        Marker::Str16 | Marker::Bin16 => len(2).map(|n| (3 + n, 0)),
+        Marker::Str32 | Marker::Bin32 => len(4).map(|n| (5 + n, 0)),
+        Marker::Ext8 => len(1).map(|n| (3 + n, 0)),
+        Marker::Ext16 => len(2).map(|n| (4 + n, 0)),
+        Marker::Ext32 => len(4).map(|n| (6 + n, 0)),
+        Marker::FixArray(n) => Some((1, n.into())),
+        Marker::Array16 => len(2).map(|n| (3, n as u64)),
+        Marker::Array32 => len(4).map(|n| (5, n as u64)),
+        Marker::FixMap(n) => Some((1, 2 * u64::from(n))),
+        Marker::Map16 => len(2).map(|n| (3, 2 * n as u64)),
+        Marker::Map32 => len(4).map(|n| (5, 2 * n as u64)),
+        Marker::Reserved => {
+            return Err(TransactionError::Deserialization(
+                "reserved msgpack marker".to_owned(),
+            ))
+        }
+    })
+}
+
+/// The length of the msgpack value at the start of `buf`, `None` if `buf` ends before it does.
+fn value_len(buf: &[u8]) -> Result<Option<usize>, TransactionError> {
+    let (mut len, mut pending) = (0, 1u64);
+    while pending > 0 {
+        match item_head(&buf[len..])? {
+            Some((head, nested)) if len + head <= buf.len() => {
+                len += head;
+                pending = pending - 1 + nested;
+            }
+            _ => return Ok(None),
+        }
+    }
+    Ok(Some(len))
+}
+
+fn deserialization<E: Display>(error: E) -> TransactionError {
+    TransactionError::Deserialization(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::Account;
+    use crate::api_model::ApiSignedTransaction;
+    use crate::{
+        AcceptAsset, CreateAsset, Pay, Raw, RawValue, RegisterKey, TransactionType, TxnBuilder,
+    };
+    use algonaut_core::{MicroAlgos, Round};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct TestBlock {
+        block: TestBlockHeader,
+        cert: u64,
+    }
+
+    #[derive(Serialize)]
+    struct TestBlockHeader {
+        #[serde(rename = "gen")]
+        genesis_id: String,
+        #[serde(rename = "gh")]
+        genesis_hash: HashDigest,
+        #[serde(rename = "rnd")]
+        round: u64,
+        #[serde(rename = "txns", skip_serializing_if = "Vec::is_empty")]
+        transactions: Vec<ApiSignedTransactionInBlock>,
+    }
+
+    fn in_block(signed: SignedTransaction) -> ApiSignedTransactionInBlock {
+        let api_t: ApiSignedTransaction = signed.into();
+        let mut transaction = api_t.transaction;
+        transaction.genesis_id = None;
+        transaction.genesis_hash = None;
+        ApiSignedTransactionInBlock {
+            asset_closing_amount: None,
+            application_id: None,
+            closing_amount: None,
+            config_asset: None,
+            has_genesis_hash: Some(true),
+            has_genesis_id: Some(true),
+            lsig: api_t.lsig,
+            msig: api_t.msig,
+            close_rewards: None,
+            receiver_rewards: None,
+            sender_rewards: None,
+            sig: api_t.sig,
+            transaction,
+        }
+    }
+
+    fn signed_by(sender: &Account, txn_type: TransactionType) -> SignedTransaction {
+        let t = TxnBuilder::new(
+            MicroAlgos(1_000),
+            Round(1),
+            Round(1_000),
+            HashDigest([7; 32]),
+            txn_type,
+        )
+        .genesis_id("testnet-v1.0".to_owned())
+        .build();
+        sender.sign_transaction(&t).unwrap()
+    }
+
+    fn block(transactions: Vec<ApiSignedTransactionInBlock>) -> Vec<u8> {
+        rmp_serde::to_vec_named(&TestBlock {
+            block: TestBlockHeader {
+                genesis_id: "testnet-v1.0".to_owned(),
+                genesis_hash: HashDigest([7; 32]),
+                round: 1,
+                transactions,
+            },
+            cert: 0,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decodes_large_block_incrementally() {
+        let sender = Account::generate();
+        let receiver = Account::generate();
+        let signed: Vec<SignedTransaction> = (0..2_000)
+            .map(|amount| {
+                signed_by(
+                    &sender,
+                    Pay::new(sender.address(), receiver.address(), MicroAlgos(amount)).build(),
+                )
+            })
+            .collect();
+
+        let mut transactions =
+            BlockTransactions::new(block(signed.iter().cloned().map(in_block).collect())).unwrap();
+
+        assert_eq!(2_000, transactions.remaining());
+        let first = transactions.next().unwrap().unwrap();
+        assert_eq!(1_999, transactions.remaining());
+        assert_eq!(signed[0].transaction_id, first.transaction_id);
+        assert_eq!(HashDigest([7; 32]), first.transaction.genesis_hash);
+
+        let rest: Vec<SignedTransaction> = transactions.map(|t| t.unwrap()).collect();
+        assert_eq!(1_999, rest.len());
+        assert_eq!(
+            signed[1_999].transaction_id,
+            rest.last().unwrap().transaction_id
+        );
+    }
+
+    #[test]
+    fn test_decoder_buffers_one_transaction_at_a_time() {
+        let sender = Account::generate();
+        let signed: Vec<SignedTransaction> = (0..2_000)
+            .map(|amount| {
+                signed_by(
+                    &sender,
+                    Pay::new(sender.address(), sender.address(), MicroAlgos(amount)).build(),
+                )
+            })
+            .collect();
+        let entries: Vec<ApiSignedTransactionInBlock> =
+            signed.iter().cloned().map(in_block).collect();
+        let largest_entry = entries
+            .iter()
+            .map(|entry| rmp_serde::to_vec_named(entry).unwrap().len())
+            .max()
+            .unwrap();
+        let bytes = block(entries);
+        let chunk_size = 256;
+
+        let mut decoder = BlockTransactionsDecoder::new();
+        let mut decoded = vec![];
+        let mut max_buffered = 0;
+        for chunk in bytes.chunks(chunk_size) {
+            decoder.push(chunk);
+            max_buffered = max_buffered.max(decoder.buffered());
+            while let Some(transaction) = decoder.next_transaction().unwrap() {
+                decoded.push(transaction.signed_transaction);
+            }
+        }
+        decoder.finish().unwrap();
+
+        assert_eq!(signed, decoded);
+        assert!(max_buffered < largest_entry + chunk_size);
+        assert!(max_buffered * 100 < bytes.len());
+    }
+
+    #[test]
+    fn test_decoder_fails_on_truncated_response() {
+        let sender = Account::generate();
+        let bytes = block(vec![in_block(signed_by(
+            &sender,
+            Pay::new(sender.address(), sender.address(), MicroAlgos(1)).build(),
+        ))]);
+
+        let mut decoder = BlockTransactionsDecoder::new();
+        decoder.push(&bytes[..bytes.len() / 2]);
+
+        assert!(decoder.next_transaction().unwrap().is_none());
+        assert!(!decoder.is_done());
+        assert!(decoder.finish().is_err());
+        assert!(BlockTransactions::new(bytes[..bytes.len() / 2].to_vec())
+            .unwrap()
+            .next()
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn test_transactions_of_type() {
+        let sender = Account::generate();
+        let signed = [
+            signed_by(&sender, AcceptAsset::new(sender.address(), 5).build()),
+            signed_by(
+                &sender,
+                Pay::new(sender.address(), sender.address(), MicroAlgos(1)).build(),
+            ),
+            signed_by(&sender, RegisterKey::offline(sender.address()).build()),
+            signed_by(
+                &sender,
+                Pay::new(sender.address(), sender.address(), MicroAlgos(2)).build(),
+            ),
+        ];
+        let bytes = block(signed.iter().cloned().map(in_block).collect());
+
+        let payments: Vec<SignedTransaction> = BlockTransactions::new(bytes.clone())
+            .unwrap()
+            .transactions_of_type(TxType::Payment)
+            .map(|t| t.unwrap())
+            .collect();
+        let app_calls = BlockTransactions::new(bytes)
+            .unwrap()
+            .transactions_of_type(TxType::ApplicationCall);
+
+        assert_eq!(
+            vec![&signed[1].transaction_id, &signed[3].transaction_id],
+            payments
+                .iter()
+                .map(|t| &t.transaction_id)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, app_calls.count());
+    }
+
+    #[test]
+    fn test_decodes_unknown_transaction_type() {
+        let sender = Account::generate();
+        // A type from the future, with a field that's also used by payments.
+        let unknown = Raw::new(sender.address(), "zzz")
+            .field("amt", RawValue::Int(5))
+            .field("zzzkey", RawValue::Bytes(vec![1, 2, 3]))
+            .build();
+        let signed = [
+            signed_by(
+                &sender,
+                Pay::new(sender.address(), sender.address(), MicroAlgos(1)).build(),
+            ),
+            signed_by(&sender, unknown.clone()),
+            signed_by(
+                &sender,
+                Pay::new(sender.address(), sender.address(), MicroAlgos(2)).build(),
+            ),
+        ];
+        let bytes = block(signed.iter().cloned().map(in_block).collect());
+
+        let decoded: Vec<SignedTransaction> = BlockTransactions::new(bytes)
+            .unwrap()
+            .map(|t| t.unwrap())
+            .collect();
+
+        assert_eq!(signed.to_vec(), decoded);
+        assert_eq!(unknown, decoded[1].transaction.txn_type);
+        assert_eq!(None, decoded[1].transaction.txn_type.tx_type());
+    }
+
+    #[test]
+    fn test_signed_transactions_with_apply_data() {
+        let sender = Account::generate();
+        let receiver = Account::generate();
+        let signed = [
+            signed_by(
+                &sender,
+                CreateAsset::new(sender.address(), 1_000, 0, false)
+                    .unit_name("TST".to_owned())
+                    .build(),
+            ),
+            signed_by(
+                &sender,
+                Pay::new(sender.address(), receiver.address(), MicroAlgos(5)).build(),
+            ),
+        ];
+        let mut created = in_block(signed[0].clone());
+        created.config_asset = Some(42);
+        let mut payment = in_block(signed[1].clone());
+        payment.sender_rewards = Some(3);
+        payment.receiver_rewards = Some(4);
+
+        let decoded = BlockTransactions::new(block(vec![created, payment]))
+            .unwrap()
+            .signed_transactions()
+            .unwrap();
+
+        assert_eq!(2, decoded.len());
+        assert_eq!(signed[0], decoded[0].signed_transaction);
+        assert_eq!(Some(42), decoded[0].created_asset_id);
+        assert_eq!(None, decoded[0].created_application_id);
+        assert_eq!(MicroAlgos(0), decoded[0].sender_rewards);
+        assert_eq!(signed[1], decoded[1].signed_transaction);
+        assert_eq!(None, decoded[1].created_asset_id);
+        assert_eq!(MicroAlgos(3), decoded[1].sender_rewards);
+        assert_eq!(MicroAlgos(4), decoded[1].receiver_rewards);
+        assert_eq!(MicroAlgos(0), decoded[1].closing_amount);
+    }
+
+    #[test]
+    fn test_empty_block_has_no_transactions() {
+        let mut transactions = BlockTransactions::new(block(vec![])).unwrap();
+
+        assert_eq!(0, transactions.remaining());
+        assert!(transactions.next().is_none());
+    }
+}
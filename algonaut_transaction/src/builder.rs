@@ -1,12 +1,22 @@
+use crate::error::TransactionError;
 use crate::transaction::{
     ApplicationCallOnComplete, ApplicationCallTransaction, AssetAcceptTransaction,
     AssetClawbackTransaction, AssetConfigurationTransaction, AssetFreezeTransaction, AssetParams,
-    AssetTransferTransaction, KeyRegistration, Payment, StateSchema, Transaction, TransactionType,
+    AssetTransferTransaction, KeyRegistration, Payment, RawTransaction, RawValue, StateSchema,
+    Transaction, TransactionType,
 };
 use algonaut_core::{
-    Address, CompiledTeal, MicroAlgos, Round, SuggestedTransactionParams, VotePk, VrfPk,
+    Address, CompiledTeal, MicroAlgos, MultisigAddress, ParamsSnapshot, Round,
+    SuggestedTransactionParams, VotePk, VrfPk, MAX_NOTE_LEN, MAX_TX_VALIDITY_WINDOW,
 };
 use algonaut_crypto::HashDigest;
+use std::collections::BTreeMap;
+
+/// Not a valid asset role address: roles are cleared by leaving them out of the update.
+const ZERO_ADDRESS: Address = Address([0; 32]);
+
+/// Maximum number of additional 2048 byte pages that can be allocated to an application's programs.
+const MAX_EXTRA_PROGRAM_PAGES: u32 = 3;
 
 /// A builder for [Transaction].
 pub struct TxnBuilder {
@@ -20,20 +30,44 @@ pub struct TxnBuilder {
     lease: Option<HashDigest>,
     note: Option<Vec<u8>>,
     rekey_to: Option<Address>,
+    min_fee: Option<MicroAlgos>,
+    allow_below_min_fee: bool,
 }
 
 impl TxnBuilder {
     /// Convenience to initialize builder with suggested transaction params
     /// Also sets the fee to max(params.fee, params.min_fee)
+    ///
+    /// A flat [fee](Self::fee) set afterwards is checked against `params.min_fee` by
+    /// [try_build](Self::try_build).
     pub fn with(params: SuggestedTransactionParams, txn_type: TransactionType) -> Self {
-        Self::new(
+        let mut builder = Self::new(
             params.fee.max(params.min_fee),
             params.first_valid,
             params.last_valid,
             params.genesis_hash,
             txn_type,
         )
-        .genesis_id(params.genesis_id)
+        .genesis_id(params.genesis_id);
+        builder.min_fee = Some(params.min_fee);
+        builder
+    }
+
+    /// Initializes the builder with the params of a snapshot, see [ParamsSnapshot].
+    ///
+    /// Like with [with](Self::with), a flat fee set afterwards is checked against the snapshot's
+    /// `min_fee` by [try_build](Self::try_build).
+    pub fn with_snapshot(snapshot: &ParamsSnapshot, txn_type: TransactionType) -> Self {
+        let mut builder = Self::new(
+            snapshot.fee(),
+            snapshot.first_valid(),
+            snapshot.last_valid(),
+            snapshot.genesis_hash(),
+            txn_type,
+        )
+        .genesis_id(snapshot.genesis_id().to_owned());
+        builder.min_fee = Some(snapshot.min_fee());
+        builder
     }
 
     pub fn new(
@@ -54,9 +88,24 @@ impl TxnBuilder {
             lease: None,
             note: None,
             rekey_to: None,
+            min_fee: None,
+            allow_below_min_fee: false,
         }
     }
 
+    /// Sets a flat fee, replacing the one of the params.
+    pub fn fee(mut self, fee: MicroAlgos) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Skips the min fee check of [try_build](Self::try_build), e.g. for a transaction whose fee
+    /// is paid by another transaction of its group (fee pooling).
+    pub fn allow_below_min_fee(mut self) -> Self {
+        self.allow_below_min_fee = true;
+        self
+    }
+
     pub fn genesis_id(mut self, id: String) -> Self {
         self.genesis_id = Some(id);
         self
@@ -82,6 +131,62 @@ impl TxnBuilder {
         self
     }
 
+    /// [build](Self::build), checking the limits enforced by the network: the note length
+    /// ([MAX_NOTE_LEN]) and the validity window ([MAX_TX_VALIDITY_WINDOW]).
+    ///
+    /// If the builder was initialized with params, the fee also has to be at least their
+    /// `min_fee`, unless [allow_below_min_fee](Self::allow_below_min_fee) was set.
+    ///
+    /// Also rejects contradictory field combinations:
+    /// * Closing the account (`close_remainder_to`) and rekeying it: the account ceases to exist.
+    ///   To migrate an account, rekey it or close it into the new account, not both.
+    /// * Closing the account to its sender.
+    /// * Marking the account as nonparticipating while registering participation keys.
+    pub fn try_build(self) -> Result<Transaction, TransactionError> {
+        if let Some(fields) = self.contradictory_fields() {
+            return Err(TransactionError::ContradictoryFields(fields.to_owned()));
+        }
+        if self.note.as_ref().map_or(0, Vec::len) > MAX_NOTE_LEN {
+            return Err(TransactionError::MaxNoteLengthError { max: MAX_NOTE_LEN });
+        }
+        if self.last_valid.0.saturating_sub(self.first_valid.0) > MAX_TX_VALIDITY_WINDOW {
+            return Err(TransactionError::MaxValidityWindowError {
+                max: MAX_TX_VALIDITY_WINDOW,
+            });
+        }
+        match self.min_fee {
+            Some(min) if self.fee < min && !self.allow_below_min_fee => {
+                return Err(TransactionError::FeeTooLow { min })
+            }
+            _ => {}
+        }
+        Ok(self.build())
+    }
+
+    fn contradictory_fields(&self) -> Option<&'static str> {
+        match &self.txn_type {
+            TransactionType::Payment(Payment {
+                close_remainder_to: Some(_),
+                ..
+            }) if self.rekey_to.is_some() => Some("close_remainder_to and rekey_to"),
+            TransactionType::Payment(Payment {
+                sender,
+                close_remainder_to: Some(close_to),
+                ..
+            }) if close_to == sender => Some("close_remainder_to is the sender"),
+            TransactionType::KeyRegistration(KeyRegistration {
+                nonparticipating: Some(true),
+                vote_pk,
+                selection_pk,
+                ..
+            }) if vote_pk.is_some() || selection_pk.is_some() => {
+                Some("nonparticipating and participation keys")
+            }
+            _ => None,
+        }
+    }
+
+    /// Builds the transaction without any check, see [try_build](Self::try_build).
     pub fn build(self) -> Transaction {
         Transaction {
             fee: self.fee,
@@ -121,6 +226,31 @@ impl Pay {
         self
     }
 
+    /// A zero amount payment from `address` to itself, rekeying `address` back to itself, i.e.
+    /// restoring its own key as authorization after a rekey.
+    ///
+    /// Has to be signed by the account's current authorizing key.
+    pub fn rekey_back(params: SuggestedTransactionParams, address: Address) -> TxnBuilder {
+        TxnBuilder::with(params, Pay::new(address, address, MicroAlgos(0)).build())
+            .rekey_to(address)
+    }
+
+    /// A zero amount payment from `address` to itself, rekeying `address` to the address of
+    /// `multisig`.
+    ///
+    /// Has to be signed by the account's current authorizing key. Once confirmed, transactions of
+    /// `address` have to be signed with `multisig` (e.g. with
+    /// [sign_multisig_transaction](crate::account::Account::sign_multisig_transaction)), keeping
+    /// `address` as sender.
+    pub fn rekey_to_multisig(
+        params: SuggestedTransactionParams,
+        address: Address,
+        multisig: &MultisigAddress,
+    ) -> TxnBuilder {
+        TxnBuilder::with(params, Pay::new(address, address, MicroAlgos(0)).build())
+            .rekey_to(multisig.address())
+    }
+
     pub fn build(self) -> TransactionType {
         TransactionType::Payment(Payment {
             sender: self.sender,
@@ -174,6 +304,12 @@ impl RegisterKey {
         }
     }
 
+    /// Marks the account as nonparticipating, which is permanent: it won't earn rewards and can't
+    /// go online anymore.
+    pub fn nonparticipating(sender: Address) -> Self {
+        Self::nonpartipating(sender, true)
+    }
+
     pub fn nonpartipating(sender: Address, nonparticipating: bool) -> Self {
         RegisterKey {
             sender,
@@ -295,6 +431,10 @@ impl CreateAsset {
 }
 
 /// A builder for [AssetConfigurationTransaction].
+///
+/// On-chain, a role address left out of an update clears the role. The builder therefore starts
+/// from the asset's current roles, which are kept unless replaced or cleared, e.g. with
+/// [clear_manager](Self::clear_manager).
 pub struct UpdateAsset {
     sender: Address,
     asset_id: u64,
@@ -312,7 +452,8 @@ pub struct UpdateAsset {
 }
 
 impl UpdateAsset {
-    pub fn new(sender: Address, asset_id: u64) -> Self {
+    /// An update of asset `asset_id`, keeping the roles of its `current` params.
+    pub fn new(sender: Address, asset_id: u64, current: &AssetParams) -> Self {
         UpdateAsset {
             sender,
             asset_id,
@@ -323,10 +464,10 @@ impl UpdateAsset {
             asset_name: None,
             url: None,
             meta_data_hash: None,
-            manager: None,
-            reserve: None,
-            freeze: None,
-            clawback: None,
+            manager: current.manager,
+            reserve: current.reserve,
+            freeze: current.freeze,
+            clawback: current.clawback,
         }
     }
 
@@ -365,23 +506,79 @@ impl UpdateAsset {
         self
     }
 
-    pub fn manager(mut self, manager: Address) -> Self {
+    /// Sets the manager address. Fails for the zero address: use [clear_manager](Self::clear_manager).
+    pub fn manager(mut self, manager: Address) -> Result<Self, TransactionError> {
+        if manager == ZERO_ADDRESS {
+            return Err(TransactionError::ZeroAddressRole {
+                role: "manager".to_owned(),
+            });
+        }
         self.manager = Some(manager);
+        Ok(self)
+    }
+
+    /// Clears the manager address (leaves it out of the update).
+    ///
+    /// This is irreversible: once cleared, the role can't be assigned again.
+    pub fn clear_manager(mut self) -> Self {
+        self.manager = None;
         self
     }
 
-    pub fn reserve(mut self, reserve: Address) -> Self {
+    /// Sets the reserve address. Fails for the zero address: use [clear_reserve](Self::clear_reserve).
+    pub fn reserve(mut self, reserve: Address) -> Result<Self, TransactionError> {
+        if reserve == ZERO_ADDRESS {
+            return Err(TransactionError::ZeroAddressRole {
+                role: "reserve".to_owned(),
+            });
+        }
         self.reserve = Some(reserve);
+        Ok(self)
+    }
+
+    /// Clears the reserve address (leaves it out of the update).
+    ///
+    /// This is irreversible: once cleared, the role can't be assigned again.
+    pub fn clear_reserve(mut self) -> Self {
+        self.reserve = None;
         self
     }
 
-    pub fn freeze(mut self, freeze: Address) -> Self {
+    /// Sets the freeze address. Fails for the zero address: use [clear_freeze](Self::clear_freeze).
+    pub fn freeze(mut self, freeze: Address) -> Result<Self, TransactionError> {
+        if freeze == ZERO_ADDRESS {
+            return Err(TransactionError::ZeroAddressRole {
+                role: "freeze".to_owned(),
+            });
+        }
         self.freeze = Some(freeze);
+        Ok(self)
+    }
+
+    /// Clears the freeze address (leaves it out of the update).
+    ///
+    /// This is irreversible: once cleared, the role can't be assigned again.
+    pub fn clear_freeze(mut self) -> Self {
+        self.freeze = None;
         self
     }
 
-    pub fn clawback(mut self, clawback: Address) -> Self {
+    /// Sets the clawback address. Fails for the zero address: use [clear_clawback](Self::clear_clawback).
+    pub fn clawback(mut self, clawback: Address) -> Result<Self, TransactionError> {
+        if clawback == ZERO_ADDRESS {
+            return Err(TransactionError::ZeroAddressRole {
+                role: "clawback".to_owned(),
+            });
+        }
         self.clawback = Some(clawback);
+        Ok(self)
+    }
+
+    /// Clears the clawback address (leaves it out of the update).
+    ///
+    /// This is irreversible: once cleared, the role can't be assigned again.
+    pub fn clear_clawback(mut self) -> Self {
+        self.clawback = None;
         self
     }
 
@@ -406,15 +603,21 @@ impl UpdateAsset {
     }
 }
 
-/// A builder for [AssetConfigurationTransaction].
+/// A builder for the [AssetConfigurationTransaction] destroying an asset: it references the asset
+/// and has no params.
+///
+/// Only the asset's manager can destroy it, and only when the creator holds all the units.
 pub struct DestroyAsset {
     sender: Address,
     asset_id: u64,
 }
 
 impl DestroyAsset {
-    pub fn new(sender: Address, asset_id: u64) -> Self {
-        DestroyAsset { sender, asset_id }
+    pub fn new(manager: Address, asset_id: u64) -> Self {
+        DestroyAsset {
+            sender: manager,
+            asset_id,
+        }
     }
 
     pub fn build(self) -> TransactionType {
@@ -565,7 +768,7 @@ pub struct CreateApplication {
     foreign_assets: Option<Address>,
     global_state_schema: Option<StateSchema>,
     local_state_schema: Option<StateSchema>,
-    extra_pages: u64,
+    extra_pages: u32,
 }
 
 impl CreateApplication {
@@ -610,9 +813,16 @@ impl CreateApplication {
         self
     }
 
-    pub fn extra_pages(mut self, extra_pages: u64) -> Self {
+    /// Number of additional 2048 byte pages allocated to the approval and clear state programs.
+    /// Needed for programs over 2KB. Must be between 0 and 3.
+    pub fn extra_program_pages(mut self, extra_pages: u32) -> Result<Self, TransactionError> {
+        if extra_pages > MAX_EXTRA_PROGRAM_PAGES {
+            return Err(TransactionError::MaxExtraProgramPagesError {
+                max: MAX_EXTRA_PROGRAM_PAGES,
+            });
+        }
         self.extra_pages = extra_pages;
-        self
+        Ok(self)
     }
 
     pub fn build(self) -> TransactionType {
@@ -628,7 +838,7 @@ impl CreateApplication {
             foreign_assets: self.foreign_assets,
             global_state_schema: self.global_state_schema,
             local_state_schema: self.local_state_schema,
-            extra_pages: Some(self.extra_pages),
+            extra_pages: Some(self.extra_pages.into()),
         })
     }
 }
@@ -1001,3 +1211,492 @@ impl OptInApplication {
         })
     }
 }
+
+/// A builder for [RawTransaction].
+pub struct Raw {
+    sender: Address,
+    type_: String,
+    fields: BTreeMap<String, RawValue>,
+}
+
+impl Raw {
+    pub fn new(sender: Address, type_: &str) -> Self {
+        Raw {
+            sender,
+            type_: type_.to_owned(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    pub fn field(mut self, key: &str, value: RawValue) -> Self {
+        self.fields.insert(key.to_owned(), value);
+        self
+    }
+
+    pub fn build(self) -> TransactionType {
+        TransactionType::RawTransaction(RawTransaction {
+            sender: self.sender,
+            type_: self.type_,
+            fields: self.fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_model::ApiTransaction;
+    use crate::TxType;
+    use algonaut_core::{ToMsgPack, MIN_TXN_FEE};
+
+    fn create_application() -> CreateApplication {
+        let schema = StateSchema {
+            number_ints: 0,
+            number_byteslices: 0,
+        };
+        CreateApplication::new(
+            Address([1; 32]),
+            CompiledTeal(vec![1]),
+            CompiledTeal(vec![1]),
+            schema.clone(),
+            schema,
+        )
+    }
+
+    #[test]
+    fn test_transaction_type_of_builders() {
+        let a = Address([1; 32]);
+        let b = Address([2; 32]);
+        let teal = || CompiledTeal(vec![1]);
+        let cases = vec![
+            (TxType::Payment, Pay::new(a, b, MicroAlgos(1)).build()),
+            (TxType::KeyRegistration, RegisterKey::offline(a).build()),
+            (
+                TxType::AssetConfig,
+                CreateAsset::new(a, 1, 0, false).build(),
+            ),
+            (
+                TxType::AssetConfig,
+                UpdateAsset::new(a, 5, &asset_roles(Some(a))).build(),
+            ),
+            (TxType::AssetConfig, DestroyAsset::new(a, 5).build()),
+            (
+                TxType::AssetTransfer,
+                TransferAsset::new(a, 5, 1, b).build(),
+            ),
+            (TxType::AssetTransfer, AcceptAsset::new(a, 5).build()),
+            (
+                TxType::AssetTransfer,
+                ClawbackAsset::new(a, 5, 1, b, a).build(),
+            ),
+            (TxType::AssetFreeze, FreezeAsset::new(a, b, 5, true).build()),
+            (TxType::ApplicationCall, create_application().build()),
+            (
+                TxType::ApplicationCall,
+                UpdateApplication::new(a, 7, teal(), teal()).build(),
+            ),
+            (TxType::ApplicationCall, CallApplication::new(a, 7).build()),
+            (TxType::ApplicationCall, ClearApplication::new(a, 7).build()),
+            (TxType::ApplicationCall, CloseApplication::new(a, 7).build()),
+            (
+                TxType::ApplicationCall,
+                DeleteApplication::new(a, 7).build(),
+            ),
+            (TxType::ApplicationCall, OptInApplication::new(a, 7).build()),
+            (TxType::StateProof, Raw::new(a, "stpf").build()),
+        ];
+
+        for (expected, txn_type) in cases {
+            let t =
+                TxnBuilder::with(SuggestedTransactionParams::testnet(Round(100)), txn_type).build();
+            assert_eq!(Some(expected), t.transaction_type());
+        }
+        let unknown = TxnBuilder::with(
+            SuggestedTransactionParams::testnet(Round(100)),
+            Raw::new(a, "zzz").build(),
+        )
+        .build();
+        assert_eq!(None, unknown.transaction_type());
+    }
+
+    #[test]
+    fn test_build_offline_with_testnet_params() {
+        let account = crate::account::Account::generate();
+        let params = SuggestedTransactionParams::testnet(Round(100));
+
+        let t = TxnBuilder::with(
+            params,
+            Pay::new(account.address(), account.address(), MicroAlgos(5)).build(),
+        )
+        .build();
+        let signed = account.sign_transaction(&t).unwrap();
+
+        assert_eq!(MicroAlgos(1_000), t.fee);
+        assert_eq!(Round(100), t.first_valid);
+        assert_eq!(Round(1_100), t.last_valid);
+        assert_eq!(Some("testnet-v1.0".to_owned()), t.genesis_id);
+        assert_eq!(
+            "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=",
+            data_encoding::BASE64.encode(&t.genesis_hash.0)
+        );
+        assert!(signed.matches_transaction(&t));
+    }
+
+    #[test]
+    fn test_try_build_checks_note_length() {
+        let address = Address([1; 32]);
+        let builder = |note_len| {
+            TxnBuilder::with(
+                SuggestedTransactionParams::testnet(Round(100)),
+                Pay::new(address, address, MicroAlgos(5)).build(),
+            )
+            .note(vec![0; note_len])
+        };
+
+        assert!(builder(MAX_NOTE_LEN).try_build().is_ok());
+        assert!(matches!(
+            builder(MAX_NOTE_LEN + 1).try_build(),
+            Err(TransactionError::MaxNoteLengthError { max: MAX_NOTE_LEN })
+        ));
+    }
+
+    #[test]
+    fn test_try_build_rejects_contradictory_fields() {
+        let sender = Address([1; 32]);
+        let other = Address([2; 32]);
+        let builder =
+            |txn_type| TxnBuilder::with(SuggestedTransactionParams::testnet(Round(100)), txn_type);
+        let contradictory = |builder: TxnBuilder, expected: &str| {
+            assert!(matches!(
+                builder.try_build(),
+                Err(TransactionError::ContradictoryFields(fields)) if fields == expected
+            ));
+        };
+
+        contradictory(
+            builder(
+                Pay::new(sender, other, MicroAlgos(0))
+                    .close_remainder_to(other)
+                    .build(),
+            )
+            .rekey_to(other),
+            "close_remainder_to and rekey_to",
+        );
+        contradictory(
+            builder(
+                Pay::new(sender, other, MicroAlgos(0))
+                    .close_remainder_to(sender)
+                    .build(),
+            ),
+            "close_remainder_to is the sender",
+        );
+        let mut keyreg = RegisterKey::online(
+            sender,
+            VotePk([1; 32]),
+            VrfPk([2; 32]),
+            Round(0),
+            Round(1_000),
+            10,
+        );
+        keyreg.nonparticipating = Some(true);
+        contradictory(
+            builder(keyreg.build()),
+            "nonparticipating and participation keys",
+        );
+
+        assert!(builder(
+            Pay::new(sender, other, MicroAlgos(0))
+                .close_remainder_to(other)
+                .build()
+        )
+        .try_build()
+        .is_ok());
+        assert!(builder(Pay::new(sender, sender, MicroAlgos(0)).build())
+            .rekey_to(other)
+            .try_build()
+            .is_ok());
+        assert!(builder(RegisterKey::nonparticipating(sender).build())
+            .try_build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_try_build_checks_validity_window() {
+        let address = Address([1; 32]);
+        let builder = |last_valid| {
+            TxnBuilder::new(
+                MIN_TXN_FEE,
+                Round(100),
+                last_valid,
+                HashDigest([1; 32]),
+                Pay::new(address, address, MicroAlgos(5)).build(),
+            )
+        };
+
+        let t = builder(Round(100 + MAX_TX_VALIDITY_WINDOW))
+            .try_build()
+            .unwrap();
+        assert_eq!(Round(100 + MAX_TX_VALIDITY_WINDOW), t.last_valid);
+        assert!(matches!(
+            builder(Round(101 + MAX_TX_VALIDITY_WINDOW)).try_build(),
+            Err(TransactionError::MaxValidityWindowError { max }) if max == MAX_TX_VALIDITY_WINDOW
+        ));
+    }
+
+    #[test]
+    fn test_try_build_checks_min_fee() {
+        let address = Address([1; 32]);
+        let builder = |fee| {
+            TxnBuilder::with(
+                SuggestedTransactionParams::testnet(Round(100)),
+                Pay::new(address, address, MicroAlgos(5)).build(),
+            )
+            .fee(fee)
+        };
+
+        assert_eq!(
+            MicroAlgos(2_000),
+            builder(MicroAlgos(2_000)).try_build().unwrap().fee
+        );
+        assert!(builder(MIN_TXN_FEE).try_build().is_ok());
+        assert!(matches!(
+            builder(MicroAlgos(999)).try_build(),
+            Err(TransactionError::FeeTooLow { min }) if min == MIN_TXN_FEE
+        ));
+    }
+
+    #[test]
+    fn test_try_build_checks_snapshot_min_fee() {
+        let address = Address([1; 32]);
+        let snapshot = ParamsSnapshot::new(&SuggestedTransactionParams::testnet(Round(100)));
+
+        let res =
+            TxnBuilder::with_snapshot(&snapshot, Pay::new(address, address, MicroAlgos(5)).build())
+                .fee(MicroAlgos(999))
+                .try_build();
+
+        assert!(matches!(
+            res,
+            Err(TransactionError::FeeTooLow { min }) if min == MIN_TXN_FEE
+        ));
+    }
+
+    #[test]
+    fn test_allow_below_min_fee() {
+        let address = Address([1; 32]);
+
+        let t = TxnBuilder::with(
+            SuggestedTransactionParams::testnet(Round(100)),
+            Pay::new(address, address, MicroAlgos(5)).build(),
+        )
+        .fee(MicroAlgos(0))
+        .allow_below_min_fee()
+        .try_build()
+        .unwrap();
+
+        assert_eq!(MicroAlgos(0), t.fee);
+    }
+
+    #[test]
+    fn test_pay_rekey_to_multisig() {
+        let address = Address([1; 32]);
+        let multisig = MultisigAddress::new(1, 2, &[Address([2; 32]), Address([3; 32])]).unwrap();
+        let params = SuggestedTransactionParams::testnet(Round(1));
+
+        let t = Pay::rekey_to_multisig(params, address, &multisig).build();
+
+        let api_t: ApiTransaction = rmp_serde::from_slice(&t.to_msg_pack().unwrap()).unwrap();
+        assert_eq!(address, api_t.sender);
+        assert_eq!(Some(address), api_t.receiver);
+        assert_eq!(None, api_t.amount);
+        assert_eq!(Some(multisig.address()), api_t.rekey_to);
+    }
+
+    #[test]
+    fn test_pay_rekey_back() {
+        let address = Address([1; 32]);
+        let params = SuggestedTransactionParams::testnet(Round(1));
+
+        let t = Pay::rekey_back(params, address).build();
+
+        let api_t: ApiTransaction = rmp_serde::from_slice(&t.to_msg_pack().unwrap()).unwrap();
+        assert_eq!(address, api_t.sender);
+        assert_eq!(Some(address), api_t.receiver);
+        assert_eq!(None, api_t.amount);
+        assert_eq!(Some(address), api_t.rekey_to);
+        assert!(matches!(
+            t.txn_type,
+            TransactionType::Payment(Payment {
+                amount: MicroAlgos(0),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_register_key_nonparticipating() {
+        let address = Address([1; 32]);
+
+        let t = TxnBuilder::with(
+            SuggestedTransactionParams::testnet(Round(100)),
+            RegisterKey::nonparticipating(address).build(),
+        )
+        .build();
+
+        let api_t: ApiTransaction = rmp_serde::from_slice(&t.to_msg_pack().unwrap()).unwrap();
+        assert_eq!("keyreg", api_t.type_);
+        assert_eq!(Some(true), api_t.nonparticipating);
+        assert_eq!(None, api_t.vote_pk);
+        assert!(matches!(
+            t.txn_type,
+            TransactionType::KeyRegistration(KeyRegistration {
+                nonparticipating: Some(true),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_create_application_extra_program_pages() {
+        let t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            create_application().extra_program_pages(2).unwrap().build(),
+        )
+        .build();
+
+        let serialized = t.to_msg_pack().unwrap();
+        let api_t: ApiTransaction = rmp_serde::from_slice(&serialized).unwrap();
+        assert_eq!(Some(2), api_t.extra_pages);
+    }
+
+    #[test]
+    fn test_create_application_too_many_extra_program_pages() {
+        let res = create_application().extra_program_pages(4);
+        assert!(matches!(
+            res,
+            Err(TransactionError::MaxExtraProgramPagesError { max: 3 })
+        ));
+    }
+
+    /// Params of an asset with all the roles set to `role`.
+    fn asset_roles(role: Option<Address>) -> AssetParams {
+        AssetParams {
+            asset_name: None,
+            decimals: None,
+            default_frozen: None,
+            total: None,
+            unit_name: None,
+            meta_data_hash: None,
+            url: None,
+            clawback: role,
+            freeze: role,
+            manager: role,
+            reserve: role,
+        }
+    }
+
+    fn encoded_asset_params(update: UpdateAsset) -> crate::api_model::ApiAssetParams {
+        let t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            update.build(),
+        )
+        .build();
+        let api_t: ApiTransaction = rmp_serde::from_slice(&t.to_msg_pack().unwrap()).unwrap();
+        api_t.asset_params.unwrap()
+    }
+
+    #[test]
+    fn test_with_snapshot_is_reproducible() {
+        let params = SuggestedTransactionParams {
+            fee: MicroAlgos(10),
+            ..SuggestedTransactionParams::testnet(Round(100))
+        };
+        let snapshot = ParamsSnapshot::new(&params);
+        let pay = || {
+            TxnBuilder::with_snapshot(
+                &snapshot,
+                Pay::new(Address([1; 32]), Address([2; 32]), MicroAlgos(5)).build(),
+            )
+            .note(b"golden".to_vec())
+            .build()
+            .to_msg_pack()
+            .unwrap()
+        };
+
+        assert_eq!(pay(), pay());
+        assert_eq!(
+            TxnBuilder::with(
+                params,
+                Pay::new(Address([1; 32]), Address([2; 32]), MicroAlgos(5)).build()
+            )
+            .note(b"golden".to_vec())
+            .build()
+            .to_msg_pack()
+            .unwrap(),
+            pay()
+        );
+        assert_eq!(MIN_TXN_FEE, snapshot.fee());
+    }
+
+    #[test]
+    fn test_destroy_asset() {
+        let manager = Address([1; 32]);
+        let t = TxnBuilder::with(
+            SuggestedTransactionParams::testnet(Round(100)),
+            DestroyAsset::new(manager, 5).build(),
+        )
+        .build();
+
+        let api_t: ApiTransaction = rmp_serde::from_slice(&t.to_msg_pack().unwrap()).unwrap();
+
+        assert_eq!("acfg", api_t.type_);
+        assert_eq!(manager, api_t.sender);
+        assert_eq!(Some(5), api_t.config_asset);
+        assert!(api_t.asset_params.is_none());
+    }
+
+    #[test]
+    fn test_update_asset_clear_manager() {
+        let current = asset_roles(Some(Address([3; 32])));
+        let params = encoded_asset_params(
+            UpdateAsset::new(Address([1; 32]), 5, &current)
+                .clear_manager()
+                .freeze(Address([2; 32]))
+                .unwrap(),
+        );
+
+        assert_eq!(None, params.manager);
+        assert_eq!(Some(Address([2; 32])), params.freeze);
+    }
+
+    #[test]
+    fn test_update_asset_keeps_current_roles() {
+        let current = asset_roles(Some(Address([3; 32])));
+        let params = encoded_asset_params(
+            UpdateAsset::new(Address([1; 32]), 5, &current)
+                .manager(Address([2; 32]))
+                .unwrap(),
+        );
+
+        assert_eq!(Some(Address([2; 32])), params.manager);
+        assert_eq!(Some(Address([3; 32])), params.reserve);
+        assert_eq!(Some(Address([3; 32])), params.freeze);
+        assert_eq!(Some(Address([3; 32])), params.clawback);
+    }
+
+    #[test]
+    fn test_update_asset_rejects_zero_address() {
+        let res = UpdateAsset::new(Address([1; 32]), 5, &asset_roles(None)).manager(ZERO_ADDRESS);
+
+        assert!(matches!(
+            res,
+            Err(TransactionError::ZeroAddressRole { role }) if role == "manager"
+        ));
+    }
+}
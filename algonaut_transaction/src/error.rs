@@ -1,4 +1,6 @@
 extern crate derive_more;
+use crate::TxType;
+use algonaut_core::{Address, MicroAlgos};
 use std::fmt::Debug;
 use thiserror::Error;
 
@@ -14,16 +16,111 @@ pub enum TransactionError {
     InvalidNumberOfSubsignatures,
     #[error("Transaction msig public keys do not match.")]
     InvalidPublicKeyInMultisig,
+    #[error("Multisig signatures to merge must have the same version and threshold.")]
+    MismatchingMultisigParams,
     #[error("Transaction msig has mismatched signatures.")]
     MismatchingSignatures,
+    #[error("Multisig signatures to merge are for different transactions.")]
+    MismatchingTransactions,
+    #[error("Transaction at index {} is not signed with a multisig.", index)]
+    MissingMultisig { index: usize },
+    #[error(
+        "Multisig subsignature {} is present in more than one transaction.",
+        index
+    )]
+    DuplicateSubsignature { index: usize },
     #[error("Empty transaction list.")]
     EmptyTransactionListError,
     #[error("Max group size is {}.", size)]
     MaxTransactionGroupSizeError { size: usize },
+    #[error("Transaction has no group id.")]
+    MissingGroupId,
+    #[error("Transactions don't share the same group id.")]
+    MismatchingGroupIds,
+    #[error("No transaction at index {} of the group.", index)]
+    InvalidGroupIndex { index: usize },
+    #[error("Transaction at index {} of the group is signed twice.", index)]
+    DuplicateGroupIndex { index: usize },
+    #[error("Zero address for asset {}: clear the role explicitly.", role)]
+    ZeroAddressRole { role: String },
+    #[error("Contradictory fields: {0}.")]
+    ContradictoryFields(String),
+    #[error("Max note length is {} bytes.", max)]
+    MaxNoteLengthError { max: usize },
+    #[error("Max validity window is {} rounds.", max)]
+    MaxValidityWindowError { max: u64 },
+    #[error("Fee is below the min fee of {}.", min)]
+    FeeTooLow { min: MicroAlgos },
+    #[error("Max extra program pages is {}.", max)]
+    MaxExtraProgramPagesError { max: u32 },
+    #[error("The fees of the group overflow.")]
+    FeeOverflow,
     #[error("serde encode error {0}")]
     RmpSerdeError(#[from] rmp_serde::encode::Error),
     #[error("crypto error {0}")]
     MnemonicError(#[from] algonaut_crypto::error::CryptoError),
     #[error("Deserialization error: {0}")]
     Deserialization(String),
+    #[error("ABI error: {0}")]
+    Abi(String),
+}
+
+/// The first divergence of a group from a [GroupTemplate](crate::group_template::GroupTemplate).
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum MismatchError {
+    #[error("Expected {} transactions, got {}.", expected, actual)]
+    Length { expected: usize, actual: usize },
+    #[error(
+        "Transaction {} has type {:?}, expected {:?}.",
+        index,
+        actual,
+        expected
+    )]
+    Type {
+        index: usize,
+        expected: TxType,
+        actual: Option<TxType>,
+    },
+    #[error(
+        "Transaction {} is sent by {:?}, expected {:?}.",
+        index,
+        actual,
+        expected
+    )]
+    Sender {
+        index: usize,
+        expected: Address,
+        actual: Address,
+    },
+    #[error(
+        "Transaction {} is received by {:?}, expected {:?}.",
+        index,
+        actual,
+        expected
+    )]
+    Receiver {
+        index: usize,
+        expected: Address,
+        actual: Option<Address>,
+    },
+}
+
+/// Why transactions can't be grouped together, see [can_group](crate::tx_group::can_group).
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum GroupIncompatibility {
+    #[error(
+        "Transaction {} has a different genesis hash than transaction 0.",
+        index
+    )]
+    GenesisHash { index: usize },
+    #[error(
+        "The validity window of transaction {} doesn't overlap with those of the previous transactions.",
+        index
+    )]
+    ValidityWindow { index: usize },
+    #[error(
+        "Transaction {} already has a group id, different from transaction 0's.",
+        index
+    )]
+    GroupId { index: usize },
 }
@@ -0,0 +1,189 @@
+use crate::error::MismatchError;
+use crate::{Transaction, TransactionType, TxType};
+use algonaut_core::Address;
+
+/// The expected type, sender and receiver of a transaction of a [GroupTemplate]. Unset fields
+/// match any transaction.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TemplateEntry {
+    tx_type: Option<TxType>,
+    sender: Option<Address>,
+    receiver: Option<Address>,
+}
+
+impl TemplateEntry {
+    /// Matches any transaction.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Matches the transactions of type `tx_type`.
+    pub fn of_type(tx_type: TxType) -> Self {
+        TemplateEntry {
+            tx_type: Some(tx_type),
+            ..Self::default()
+        }
+    }
+
+    pub fn sender(mut self, sender: Address) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// The receiver of the algos or assets: payments, asset transfers (the sender for opt-ins)
+    /// and clawbacks. Other transactions have no receiver, so they don't match.
+    pub fn receiver(mut self, receiver: Address) -> Self {
+        self.receiver = Some(receiver);
+        self
+    }
+
+    fn check(&self, index: usize, transaction: &Transaction) -> Result<(), MismatchError> {
+        if let Some(expected) = self.tx_type {
+            let actual = transaction.transaction_type();
+            if actual != Some(expected) {
+                return Err(MismatchError::Type {
+                    index,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if let Some(expected) = self.sender {
+            let actual = transaction.sender();
+            if actual != expected {
+                return Err(MismatchError::Sender {
+                    index,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if let Some(expected) = self.receiver {
+            let actual = receiver(transaction);
+            if actual != Some(expected) {
+                return Err(MismatchError::Receiver {
+                    index,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The structure an atomic group has to follow, e.g. "a payment to the app's account, then a call
+/// of the app", to validate the groups submitted to a dApp backend.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct GroupTemplate {
+    entries: Vec<TemplateEntry>,
+}
+
+impl GroupTemplate {
+    pub fn new(entries: Vec<TemplateEntry>) -> Self {
+        GroupTemplate { entries }
+    }
+
+    /// Checks that `group` has one transaction per entry, each matching its entry, returning the
+    /// first divergence otherwise.
+    pub fn matches(&self, group: &[Transaction]) -> Result<(), MismatchError> {
+        if group.len() != self.entries.len() {
+            return Err(MismatchError::Length {
+                expected: self.entries.len(),
+                actual: group.len(),
+            });
+        }
+        self.entries
+            .iter()
+            .zip(group)
+            .enumerate()
+            .try_for_each(|(index, (entry, transaction))| entry.check(index, transaction))
+    }
+}
+
+fn receiver(transaction: &Transaction) -> Option<Address> {
+    match &transaction.txn_type {
+        TransactionType::Payment(t) => Some(t.receiver),
+        TransactionType::AssetTransferTransaction(t) => Some(t.receiver),
+        TransactionType::AssetAcceptTransaction(t) => Some(t.sender),
+        TransactionType::AssetClawbackTransaction(t) => Some(t.asset_receiver),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::CallApplication;
+    use crate::{Pay, TxnBuilder};
+    use algonaut_core::{MicroAlgos, Round, SuggestedTransactionParams};
+
+    fn group(app_account: Address, user: Address) -> Vec<Transaction> {
+        let params = SuggestedTransactionParams::testnet(Round(100));
+        vec![
+            TxnBuilder::with(
+                params.clone(),
+                Pay::new(user, app_account, MicroAlgos(1_000_000)).build(),
+            )
+            .build(),
+            TxnBuilder::with(params, CallApplication::new(user, 5).build()).build(),
+        ]
+    }
+
+    #[test]
+    fn test_matches_valid_group() {
+        let app_account = Address([1; 32]);
+        let user = Address([2; 32]);
+        let template = GroupTemplate::new(vec![
+            TemplateEntry::of_type(TxType::Payment).receiver(app_account),
+            TemplateEntry::of_type(TxType::ApplicationCall).sender(user),
+        ]);
+
+        assert_eq!(Ok(()), template.matches(&group(app_account, user)));
+    }
+
+    #[test]
+    fn test_matches_reports_first_mismatch() {
+        let app_account = Address([1; 32]);
+        let user = Address([2; 32]);
+        let other = Address([3; 32]);
+        let template = GroupTemplate::new(vec![
+            TemplateEntry::of_type(TxType::Payment).receiver(app_account),
+            TemplateEntry::of_type(TxType::ApplicationCall).sender(user),
+        ]);
+
+        assert_eq!(
+            Err(MismatchError::Receiver {
+                index: 0,
+                expected: app_account,
+                actual: Some(other),
+            }),
+            template.matches(&group(other, user))
+        );
+        assert_eq!(
+            Err(MismatchError::Sender {
+                index: 1,
+                expected: user,
+                actual: other,
+            }),
+            template.matches(&group(app_account, other))
+        );
+        let mut reversed = group(app_account, user);
+        reversed.reverse();
+        assert_eq!(
+            Err(MismatchError::Type {
+                index: 0,
+                expected: TxType::Payment,
+                actual: Some(TxType::ApplicationCall),
+            }),
+            template.matches(&reversed)
+        );
+        assert_eq!(
+            Err(MismatchError::Length {
+                expected: 2,
+                actual: 1,
+            }),
+            template.matches(&group(app_account, user)[..1])
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Readable JSON representation of transactions, meant for fixtures and snapshot tests.
+//!
+//! The shape mirrors the msgpack encoding: same keys, empty fields omitted, addresses as base32
+//! strings and other bytes as base64 strings. It's not a wire format: nodes don't accept it.
+//!
+//! Unknown fields of [RawTransaction](crate::RawTransaction)s are assumed to be ints or bytes, so
+//! their strings are read back as base64 bytes.
+
+use crate::error::TransactionError;
+use crate::Transaction;
+use algonaut_core::{Address, ToMsgPack};
+use data_encoding::BASE64;
+use rmpv::Value;
+use serde_json::{Map, Number};
+use std::str::FromStr;
+
+/// Fields holding addresses, including the roles of `apar` and the accounts of `apat`.
+const ADDRESS_KEYS: &[&str] = &[
+    "aclose", "apat", "arcv", "asnd", "c", "close", "f", "fadd", "m", "r", "rcv", "rekey", "snd",
+];
+
+/// Fields holding strings. All the other string-like fields are bytes.
+const STRING_KEYS: &[&str] = &["an", "au", "gen", "type", "un"];
+
+pub(crate) fn to_json(transaction: &Transaction) -> Result<String, TransactionError> {
+    let msg_pack = transaction.to_msg_pack()?;
+    let value = rmpv::decode::read_value(&mut msg_pack.as_slice())
+        .map_err(|e| TransactionError::Deserialization(e.to_string()))?;
+    serde_json::to_string_pretty(&msg_pack_to_json(&value, None)?)
+        .map_err(|e| TransactionError::Deserialization(e.to_string()))
+}
+
+pub(crate) fn from_json(json: &str) -> Result<Transaction, TransactionError> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| TransactionError::Deserialization(e.to_string()))?;
+    let mut msg_pack = vec![];
+    rmpv::encode::write_value(&mut msg_pack, &json_to_msg_pack(&value, None)?)
+        .map_err(|e| TransactionError::Deserialization(e.to_string()))?;
+    rmp_serde::from_slice(&msg_pack).map_err(|e| TransactionError::Deserialization(e.to_string()))
+}
+
+fn msg_pack_to_json(
+    value: &Value,
+    key: Option<&str>,
+) -> Result<serde_json::Value, TransactionError> {
+    Ok(match value {
+        Value::Nil => serde_json::Value::Null,
+        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Integer(i) => match (i.as_u64(), i.as_i64()) {
+            (Some(u), _) => u.into(),
+            (_, Some(i)) => i.into(),
+            _ => return Err(invalid_json(&format!("unsupported integer {}", i))),
+        },
+        Value::F32(f) => float(*f as f64)?,
+        Value::F64(f) => float(*f)?,
+        Value::String(s) => serde_json::Value::String(
+            s.as_str()
+                .ok_or_else(|| invalid_json("non UTF-8 string"))?
+                .to_owned(),
+        ),
+        Value::Binary(bytes) => match (key, bytes.len()) {
+            (Some(key), 32) if ADDRESS_KEYS.contains(&key) => {
+                let mut address = [0; 32];
+                address.copy_from_slice(bytes);
+                serde_json::Value::String(Address(address).to_string())
+            }
+            _ => serde_json::Value::String(BASE64.encode(bytes)),
+        },
+        Value::Array(values) => serde_json::Value::Array(
+            values
+                .iter()
+                .map(|v| msg_pack_to_json(v, key))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(entries) => {
+            let mut map = Map::new();
+            for (k, v) in entries {
+                let k = k.as_str().ok_or_else(|| invalid_json("non string key"))?;
+                map.insert(k.to_owned(), msg_pack_to_json(v, Some(k))?);
+            }
+            serde_json::Value::Object(map)
+        }
+        Value::Ext(..) => return Err(invalid_json("unsupported extension type")),
+    })
+}
+
+fn json_to_msg_pack(
+    value: &serde_json::Value,
+    key: Option<&str>,
+) -> Result<Value, TransactionError> {
+    Ok(match value {
+        serde_json::Value::Null => Value::Nil,
+        serde_json::Value::Bool(b) => Value::Boolean(*b),
+        serde_json::Value::Number(n) => match (n.as_u64(), n.as_i64(), n.as_f64()) {
+            (Some(u), _, _) => Value::from(u),
+            (_, Some(i), _) => Value::from(i),
+            (_, _, Some(f)) => Value::F64(f),
+            _ => return Err(invalid_json(&format!("unsupported number {}", n))),
+        },
+        serde_json::Value::String(s) => match key {
+            Some(key) if STRING_KEYS.contains(&key) => Value::from(s.as_str()),
+            Some(key) if ADDRESS_KEYS.contains(&key) => Value::Binary(
+                Address::from_str(s)
+                    .map_err(|e| invalid_json(&e))?
+                    .0
+                    .to_vec(),
+            ),
+            _ => Value::Binary(
+                BASE64
+                    .decode(s.as_bytes())
+                    .map_err(|e| invalid_json(&e.to_string()))?,
+            ),
+        },
+        serde_json::Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|v| json_to_msg_pack(v, key))
+                .collect::<Result<_, _>>()?,
+        ),
+        serde_json::Value::Object(map) => Value::Map(
+            map.iter()
+                .map(|(k, v)| Ok((Value::from(k.as_str()), json_to_msg_pack(v, Some(k))?)))
+                .collect::<Result<_, TransactionError>>()?,
+        ),
+    })
+}
+
+fn float(f: f64) -> Result<serde_json::Value, TransactionError> {
+    Number::from_f64(f)
+        .map(serde_json::Value::Number)
+        .ok_or_else(|| invalid_json(&format!("unsupported float {}", f)))
+}
+
+fn invalid_json(reason: &str) -> TransactionError {
+    TransactionError::Deserialization(format!("Invalid transaction JSON: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Pay, Transaction, TxnBuilder};
+    use algonaut_core::{Address, MicroAlgos, Round};
+    use algonaut_crypto::HashDigest;
+
+    #[test]
+    fn test_payment_json_round_trip() {
+        let transaction = TxnBuilder::new(
+            MicroAlgos(1_000),
+            Round(1),
+            Round(1_001),
+            HashDigest([1; 32]),
+            Pay::new(Address([2; 32]), Address([3; 32]), MicroAlgos(5)).build(),
+        )
+        .note(b"hi".to_vec())
+        .build();
+
+        let json = transaction.to_json().unwrap();
+
+        assert_eq!(
+            format!(
+                r#"{{
+  "amt": 5,
+  "fee": 1000,
+  "fv": 1,
+  "gh": "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=",
+  "lv": 1001,
+  "note": "aGk=",
+  "rcv": "{}",
+  "snd": "{}",
+  "type": "pay"
+}}"#,
+                Address([3; 32]).to_string(),
+                Address([2; 32]).to_string()
+            ),
+            json
+        );
+        assert_eq!(transaction, Transaction::from_json(&json).unwrap());
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_address() {
+        assert!(Transaction::from_json(r#"{"snd": "not an address", "type": "pay"}"#).is_err());
+    }
+}
@@ -1,14 +1,29 @@
+pub mod abi;
 pub mod account;
 mod api_model;
+pub mod asset_metadata;
 pub mod auction;
+pub mod block;
 pub mod builder;
 pub mod error;
+pub mod group_template;
+mod json;
+pub mod onboarding;
+pub mod signer;
 pub mod transaction;
 pub mod tx_group;
 pub mod url;
 
+#[cfg(test)]
+mod roundtrip_tests;
+#[cfg(test)]
+mod test_utils;
+
 pub use builder::{
-    AcceptAsset, ClawbackAsset, CreateApplication, CreateAsset, FreezeAsset, Pay, RegisterKey,
-    TransferAsset, TxnBuilder,
+    AcceptAsset, ClawbackAsset, CreateApplication, CreateAsset, DestroyAsset, FreezeAsset, Pay,
+    Raw, RegisterKey, TransferAsset, TxnBuilder,
+};
+pub use transaction::{
+    RawTransaction, RawValue, SignatureKind, SignedTransaction, Transaction, TransactionType,
+    TxType,
 };
-pub use transaction::{SignedTransaction, Transaction, TransactionType};
@@ -0,0 +1,197 @@
+use crate::error::TransactionError;
+use crate::tx_group::TxGroup;
+use crate::{AcceptAsset, Pay, Transaction, TransferAsset, TxnBuilder};
+use algonaut_core::{Address, MicroAlgos, SuggestedTransactionParams, MAX_TX_GROUP_SIZE};
+
+/// Minimum balance of an account.
+pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
+
+/// Increase of the minimum balance per asset the account is opted into.
+pub const ASSET_MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
+
+/// Increase of the minimum balance of an application's account per box.
+pub const BOX_MIN_BALANCE: MicroAlgos = MicroAlgos(2_500);
+
+/// Increase of the minimum balance of an application's account per byte of its boxes' names
+/// and values.
+pub const BOX_BYTE_MIN_BALANCE: MicroAlgos = MicroAlgos(400);
+
+/// Minimum balance of an account opted into `assets` assets (and not into applications).
+pub fn min_balance(assets: u64) -> MicroAlgos {
+    MIN_BALANCE + ASSET_MIN_BALANCE * assets
+}
+
+/// Minimum balance the account of an application has to hold for `num_boxes` boxes totalling
+/// `total_bytes` bytes (names and values), on top of its own minimum balance. The account's
+/// `total_boxes` and `total_box_bytes` report the current usage.
+pub fn box_min_balance(num_boxes: u64, total_bytes: u64) -> MicroAlgos {
+    BOX_MIN_BALANCE * num_boxes + BOX_BYTE_MIN_BALANCE * total_bytes
+}
+
+/// A transaction of a group, with the address of the account that has to sign it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GroupLeg {
+    pub transaction: Transaction,
+    pub signer: Address,
+}
+
+/// Builds the group onboarding a new (empty) account to an asset:
+///
+/// 1. `service` pays `user` the minimum balance for holding the asset, plus the opt-in fee.
+/// 2. `user` opts into the asset.
+/// 3. `service` transfers `amount` of the asset to `user`.
+///
+/// The legs are returned in this order with a group id assigned. The service signs its legs and
+/// passes the opt-in to the user to sign.
+pub fn asset_onboarding_group(
+    params: &SuggestedTransactionParams,
+    service: Address,
+    user: Address,
+    asset_id: u64,
+    amount: u64,
+) -> Result<Vec<GroupLeg>, TransactionError> {
+    let mut opt_in =
+        TxnBuilder::with(params.clone(), AcceptAsset::new(user, asset_id).build()).build();
+    let funding = min_balance(1) + opt_in.fee;
+    let mut fund =
+        TxnBuilder::with(params.clone(), Pay::new(service, user, funding).build()).build();
+    let mut deliver = TxnBuilder::with(
+        params.clone(),
+        TransferAsset::new(service, asset_id, amount, user).build(),
+    )
+    .build();
+    TxGroup::assign_group_id(vec![&mut fund, &mut opt_in, &mut deliver])?;
+
+    Ok(vec![
+        GroupLeg {
+            transaction: fund,
+            signer: service,
+        },
+        GroupLeg {
+            transaction: opt_in,
+            signer: user,
+        },
+        GroupLeg {
+            transaction: deliver,
+            signer: service,
+        },
+    ])
+}
+
+/// The groups opting an account into assets, see [opt_in_assets].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AssetOptIns {
+    /// Groups of at most [MAX_TX_GROUP_SIZE] opt-ins, with their group id assigned.
+    pub groups: Vec<Vec<Transaction>>,
+    /// Increase of the account's minimum balance once all the opt-ins are confirmed: the account
+    /// has to hold it on top of its current minimum balance and the fees.
+    pub min_balance_increase: MicroAlgos,
+}
+
+/// Builds the opt-ins (zero amount transfers to self) of `account` into `asset_ids`, chunked into
+/// as many groups as needed. The account signs all the transactions.
+pub fn opt_in_assets(
+    account: Address,
+    asset_ids: &[u64],
+    params: &SuggestedTransactionParams,
+) -> Result<AssetOptIns, TransactionError> {
+    let mut groups = vec![];
+    for chunk in asset_ids.chunks(MAX_TX_GROUP_SIZE) {
+        let mut group: Vec<Transaction> = chunk
+            .iter()
+            .map(|id| {
+                TxnBuilder::with(params.clone(), AcceptAsset::new(account, *id).build()).build()
+            })
+            .collect();
+        TxGroup::assign_group_id(group.iter_mut().collect())?;
+        groups.push(group);
+    }
+    Ok(AssetOptIns {
+        groups,
+        min_balance_increase: ASSET_MIN_BALANCE * asset_ids.len() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransactionType;
+    use algonaut_core::Round;
+
+    #[test]
+    fn test_asset_onboarding_group() {
+        let params = SuggestedTransactionParams::testnet(Round(1));
+        let service = Address([1; 32]);
+        let user = Address([2; 32]);
+
+        let legs = asset_onboarding_group(&params, service, user, 5, 10).unwrap();
+
+        assert_eq!(
+            vec![service, user, service],
+            legs.iter().map(|l| l.signer).collect::<Vec<_>>()
+        );
+        let group = legs[0].transaction.group;
+        assert!(group.is_some());
+        assert!(legs.iter().all(|l| l.transaction.group == group));
+        match &legs[0].transaction.txn_type {
+            TransactionType::Payment(p) => {
+                assert_eq!(user, p.receiver);
+                assert_eq!(MicroAlgos(201_000), p.amount);
+            }
+            t => panic!("Expected payment, got: {:?}", t),
+        }
+        assert!(matches!(
+            &legs[1].transaction.txn_type,
+            TransactionType::AssetAcceptTransaction(a) if a.sender == user && a.xfer == 5
+        ));
+        assert!(matches!(
+            &legs[2].transaction.txn_type,
+            TransactionType::AssetTransferTransaction(t) if t.receiver == user && t.amount == 10
+        ));
+    }
+
+    #[test]
+    fn test_box_min_balance() {
+        // A box named "counter" (7 bytes) holding a uint64 (8 bytes): 2500 + 400 * 15.
+        assert_eq!(MicroAlgos(8_500), box_min_balance(1, 15));
+        assert_eq!(MicroAlgos(0), box_min_balance(0, 0));
+        assert_eq!(
+            BOX_MIN_BALANCE * 2 + BOX_BYTE_MIN_BALANCE * 1_037,
+            box_min_balance(2, 1_037)
+        );
+    }
+
+    #[test]
+    fn test_opt_in_assets_chunks_groups() {
+        let account = Address([2; 32]);
+        let asset_ids: Vec<u64> = (1..=20).collect();
+
+        let opt_ins = opt_in_assets(
+            account,
+            &asset_ids,
+            &SuggestedTransactionParams::testnet(Round(1)),
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![16, 4],
+            opt_ins.groups.iter().map(Vec::len).collect::<Vec<_>>()
+        );
+        for group in &opt_ins.groups {
+            assert!(group[0].group.is_some());
+            assert!(group.iter().all(|t| t.group == group[0].group));
+        }
+        assert_ne!(opt_ins.groups[0][0].group, opt_ins.groups[1][0].group);
+        let opted_in: Vec<u64> = opt_ins
+            .groups
+            .iter()
+            .flatten()
+            .map(|t| match &t.txn_type {
+                TransactionType::AssetAcceptTransaction(a) if a.sender == account => a.xfer,
+                t => panic!("Expected opt-in, got: {:?}", t),
+            })
+            .collect();
+        assert_eq!(asset_ids, opted_in);
+        assert_eq!(MicroAlgos(2_000_000), opt_ins.min_balance_increase);
+    }
+}
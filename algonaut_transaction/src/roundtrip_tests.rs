@@ -0,0 +1,396 @@
+//! Property tests: msg pack encoding and decoding arbitrary values gives back the same values.
+
+use crate::transaction::{
+    ApplicationCallOnComplete, ApplicationCallTransaction, AssetAcceptTransaction,
+    AssetClawbackTransaction, AssetConfigurationTransaction, AssetFreezeTransaction, AssetParams,
+    AssetTransferTransaction, KeyRegistration, Payment, StateSchema, TransactionSignature,
+};
+use crate::{SignedTransaction, Transaction, TransactionType};
+use algonaut_core::{
+    Address, CompiledTeal, LogicSignature, MicroAlgos, MultisigSignature, MultisigSubsig, Round,
+    SignedLogic, ToMsgPack, VotePk, VrfPk,
+};
+use algonaut_crypto::{Ed25519PublicKey, HashDigest, Signature};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: ToMsgPack + DeserializeOwned,
+{
+    rmp_serde::from_slice(&value.to_msg_pack().unwrap()).unwrap()
+}
+
+fn assert_round_trip<T>(value: T)
+where
+    T: ToMsgPack + DeserializeOwned + PartialEq + Debug,
+{
+    assert_eq!(value, round_trip(&value));
+}
+
+/// Wrapper to encode values that aren't encoded on their own.
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Wrapped<T>(T);
+
+impl<T: serde::Serialize> ToMsgPack for Wrapped<T> {}
+
+fn bytes32() -> impl Strategy<Value = [u8; 32]> {
+    any::<[u8; 32]>()
+}
+
+fn address() -> impl Strategy<Value = Address> {
+    bytes32().prop_map(Address)
+}
+
+fn hash_digest() -> impl Strategy<Value = HashDigest> {
+    bytes32().prop_map(HashDigest)
+}
+
+fn signature() -> impl Strategy<Value = Signature> {
+    (bytes32(), bytes32()).prop_map(|(a, b)| {
+        let mut sig = [0; 64];
+        sig[..32].copy_from_slice(&a);
+        sig[32..].copy_from_slice(&b);
+        Signature(sig)
+    })
+}
+
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 1..64)
+}
+
+fn multisig_signature() -> impl Strategy<Value = MultisigSignature> {
+    (
+        vec(
+            (bytes32(), option::of(signature())).prop_map(|(key, sig)| MultisigSubsig {
+                key: Ed25519PublicKey(key),
+                sig,
+            }),
+            1..5,
+        ),
+        any::<u8>(),
+        any::<u8>(),
+    )
+        .prop_map(|(subsigs, threshold, version)| MultisigSignature {
+            subsigs,
+            threshold,
+            version,
+        })
+}
+
+// Zero values are not encoded, so the generated optional numbers are non zero.
+fn non_zero() -> impl Strategy<Value = u64> {
+    1..u64::MAX
+}
+
+fn state_schema() -> impl Strategy<Value = StateSchema> {
+    (non_zero(), non_zero()).prop_map(|(number_ints, number_byteslices)| StateSchema {
+        number_ints,
+        number_byteslices,
+    })
+}
+
+fn asset_params() -> impl Strategy<Value = AssetParams> {
+    (
+        option::of("[a-z]{1,8}"),
+        option::of(0..20u32),
+        option::of(any::<bool>()),
+        option::of(any::<u64>()),
+        option::of("[A-Z]{1,8}"),
+        option::of(bytes()),
+        option::of("https://[a-z]{1,8}"),
+        (
+            option::of(address()),
+            option::of(address()),
+            option::of(address()),
+            option::of(address()),
+        ),
+    )
+        .prop_map(
+            |(
+                asset_name,
+                decimals,
+                default_frozen,
+                total,
+                unit_name,
+                meta_data_hash,
+                url,
+                (clawback, freeze, manager, reserve),
+            )| AssetParams {
+                asset_name,
+                decimals,
+                default_frozen,
+                total,
+                unit_name,
+                meta_data_hash,
+                url,
+                clawback,
+                freeze,
+                manager,
+                reserve,
+            },
+        )
+}
+
+fn on_complete() -> impl Strategy<Value = ApplicationCallOnComplete> {
+    prop_oneof![
+        Just(ApplicationCallOnComplete::NoOp),
+        Just(ApplicationCallOnComplete::OptIn),
+        Just(ApplicationCallOnComplete::CloseOut),
+        Just(ApplicationCallOnComplete::ClearState),
+        Just(ApplicationCallOnComplete::UpdateApplication),
+        Just(ApplicationCallOnComplete::DeleteApplication),
+    ]
+}
+
+fn transaction_type() -> impl Strategy<Value = TransactionType> {
+    prop_oneof![
+        (address(), address(), any::<u64>(), option::of(address())).prop_map(
+            |(sender, receiver, amount, close_remainder_to)| {
+                TransactionType::Payment(Payment {
+                    sender,
+                    receiver,
+                    amount: MicroAlgos(amount),
+                    close_remainder_to,
+                })
+            }
+        ),
+        (
+            address(),
+            option::of(bytes32()),
+            option::of(bytes32()),
+            option::of(any::<u64>()),
+            option::of(any::<u64>()),
+            option::of(any::<u64>()),
+            option::of(any::<bool>()),
+        )
+            .prop_map(
+                |(sender, vote_pk, selection_pk, vote_first, vote_last, dilution, nonpart)| {
+                    TransactionType::KeyRegistration(KeyRegistration {
+                        sender,
+                        vote_pk: vote_pk.map(VotePk),
+                        selection_pk: selection_pk.map(VrfPk),
+                        vote_first: vote_first.map(Round),
+                        vote_last: vote_last.map(Round),
+                        vote_key_dilution: dilution,
+                        nonparticipating: nonpart,
+                    })
+                }
+            ),
+        (
+            address(),
+            option::of(asset_params()),
+            option::of(non_zero())
+        )
+            .prop_map(|(sender, params, config_asset)| {
+                TransactionType::AssetConfigurationTransaction(AssetConfigurationTransaction {
+                    sender,
+                    params,
+                    config_asset,
+                })
+            }),
+        (
+            address(),
+            any::<u64>(),
+            non_zero(),
+            address(),
+            option::of(address())
+        )
+            .prop_map(|(sender, xfer, amount, receiver, close_to)| {
+                TransactionType::AssetTransferTransaction(AssetTransferTransaction {
+                    sender,
+                    xfer,
+                    amount,
+                    receiver,
+                    close_to,
+                })
+            }),
+        (address(), any::<u64>()).prop_map(|(sender, xfer)| {
+            TransactionType::AssetAcceptTransaction(AssetAcceptTransaction { sender, xfer })
+        }),
+        (
+            address(),
+            any::<u64>(),
+            non_zero(),
+            address(),
+            address(),
+            option::of(address())
+        )
+            .prop_map(
+                |(sender, xfer, asset_amount, asset_sender, asset_receiver, asset_close_to)| {
+                    TransactionType::AssetClawbackTransaction(AssetClawbackTransaction {
+                        sender,
+                        xfer,
+                        asset_amount,
+                        asset_sender,
+                        asset_receiver,
+                        asset_close_to,
+                    })
+                }
+            ),
+        (address(), address(), any::<u64>(), any::<bool>()).prop_map(
+            |(sender, freeze_account, asset_id, frozen)| {
+                TransactionType::AssetFreezeTransaction(AssetFreezeTransaction {
+                    sender,
+                    freeze_account,
+                    asset_id,
+                    frozen,
+                })
+            }
+        ),
+        (
+            address(),
+            option::of(non_zero()),
+            on_complete(),
+            option::of(vec(address(), 1..4)),
+            option::of(bytes()),
+            option::of(vec(bytes(), 1..4)),
+            option::of(bytes()),
+            (
+                option::of(state_schema()),
+                option::of(state_schema()),
+                option::of(1..=3u64),
+            ),
+        )
+            .prop_map(
+                |(
+                    sender,
+                    app_id,
+                    on_complete,
+                    accounts,
+                    approval_program,
+                    app_arguments,
+                    clear_state_program,
+                    (global_state_schema, local_state_schema, extra_pages),
+                )| {
+                    TransactionType::ApplicationCallTransaction(ApplicationCallTransaction {
+                        sender,
+                        app_id,
+                        on_complete,
+                        accounts,
+                        approval_program: approval_program.map(CompiledTeal),
+                        app_arguments,
+                        clear_state_program: clear_state_program.map(CompiledTeal),
+                        foreign_apps: None,
+                        foreign_assets: None,
+                        global_state_schema,
+                        local_state_schema,
+                        extra_pages,
+                    })
+                }
+            ),
+    ]
+}
+
+fn transaction() -> impl Strategy<Value = Transaction> {
+    (
+        any::<u64>(),
+        any::<u64>(),
+        hash_digest(),
+        any::<u64>(),
+        transaction_type(),
+        option::of("[a-z]{1,8}-v1.0"),
+        option::of(hash_digest()),
+        option::of(hash_digest()),
+        option::of(bytes()),
+        option::of(address()),
+    )
+        .prop_map(
+            |(
+                fee,
+                first_valid,
+                genesis_hash,
+                last_valid,
+                txn_type,
+                genesis_id,
+                group,
+                lease,
+                note,
+                rekey_to,
+            )| {
+                Transaction {
+                    fee: MicroAlgos(fee),
+                    first_valid: Round(first_valid),
+                    genesis_hash,
+                    last_valid: Round(last_valid),
+                    txn_type,
+                    genesis_id,
+                    group,
+                    lease,
+                    note,
+                    rekey_to,
+                }
+            },
+        )
+}
+
+fn transaction_signature() -> impl Strategy<Value = TransactionSignature> {
+    prop_oneof![
+        signature().prop_map(TransactionSignature::Single),
+        multisig_signature().prop_map(TransactionSignature::Multi),
+        (
+            bytes(),
+            vec(bytes(), 0..3),
+            prop_oneof![
+                Just(LogicSignature::ContractAccount),
+                signature().prop_map(LogicSignature::DelegatedSig),
+                multisig_signature().prop_map(LogicSignature::DelegatedMultiSig),
+            ]
+        )
+            .prop_map(|(logic, args, sig)| {
+                TransactionSignature::Logic(SignedLogic {
+                    logic: CompiledTeal(logic),
+                    args,
+                    sig,
+                })
+            }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_address_round_trip(address in address()) {
+        assert_round_trip(Wrapped(address));
+    }
+
+    #[test]
+    fn test_hash_digest_round_trip(digest in hash_digest()) {
+        assert_round_trip(Wrapped(digest));
+    }
+
+    #[test]
+    fn test_signature_round_trip(signature in signature()) {
+        assert_round_trip(Wrapped(signature));
+    }
+
+    #[test]
+    fn test_micro_algos_and_round_round_trip(amount in any::<u64>(), round in any::<u64>()) {
+        assert_round_trip(Wrapped((MicroAlgos(amount), Round(round))));
+    }
+
+    #[test]
+    fn test_multisig_signature_round_trip(msig in multisig_signature()) {
+        assert_round_trip(Wrapped(msig));
+    }
+
+    #[test]
+    fn test_transaction_round_trip(transaction in transaction()) {
+        assert_round_trip(transaction);
+    }
+
+    #[test]
+    fn test_signed_transaction_round_trip(
+        transaction in transaction(),
+        sig in transaction_signature(),
+    ) {
+        let transaction_id = transaction.id().unwrap();
+        assert_round_trip(SignedTransaction {
+            transaction,
+            transaction_id,
+            sig,
+        });
+    }
+}
@@ -0,0 +1,165 @@
+use crate::account::Account;
+use crate::error::TransactionError;
+use crate::transaction::TransactionSignature;
+use crate::{SignedTransaction, Transaction};
+use algonaut_core::{MultisigAddress, SignedLogic};
+
+/// Signs transactions of a group, independently of where the keys are stored (local accounts,
+/// logic signatures, multisig accounts, kmd, hardware wallets...).
+pub trait TransactionSigner {
+    /// Signs the transactions of `txns` at `indices`, returning them in the order of `indices`.
+    ///
+    /// The whole group is passed so signers can show or check the other transactions.
+    fn sign(
+        &self,
+        txns: &[Transaction],
+        indices: &[usize],
+    ) -> Result<Vec<SignedTransaction>, TransactionError>;
+}
+
+impl TransactionSigner for Account {
+    fn sign(
+        &self,
+        txns: &[Transaction],
+        indices: &[usize],
+    ) -> Result<Vec<SignedTransaction>, TransactionError> {
+        sign_each(txns, indices, |t| self.sign_transaction(t))
+    }
+}
+
+/// A logic signature (contract account or delegated) authorizes the transactions it approves.
+impl TransactionSigner for SignedLogic {
+    fn sign(
+        &self,
+        txns: &[Transaction],
+        indices: &[usize],
+    ) -> Result<Vec<SignedTransaction>, TransactionError> {
+        sign_each(txns, indices, |t| {
+            Ok(SignedTransaction {
+                transaction: t.clone(),
+                transaction_id: t.id()?,
+                sig: TransactionSignature::Logic(self.clone()),
+            })
+        })
+    }
+}
+
+/// Signs with the accounts of a multisig address the transactions it sends.
+///
+/// A [MultisigAddress] only holds public keys: the signing accounts have to be provided, at least
+/// as many as the threshold.
+pub struct MultisigSigner<'a> {
+    pub address: MultisigAddress,
+    pub accounts: Vec<&'a Account>,
+}
+
+impl<'a> MultisigSigner<'a> {
+    pub fn new(address: MultisigAddress, accounts: Vec<&'a Account>) -> Self {
+        MultisigSigner { address, accounts }
+    }
+}
+
+impl<'a> TransactionSigner for MultisigSigner<'a> {
+    fn sign(
+        &self,
+        txns: &[Transaction],
+        indices: &[usize],
+    ) -> Result<Vec<SignedTransaction>, TransactionError> {
+        let (first, others) = self
+            .accounts
+            .split_first()
+            .ok_or(TransactionError::InvalidSecretKeyInMultisig)?;
+        sign_each(txns, indices, |t| {
+            let mut msig = first.init_transaction_msig(t, &self.address)?;
+            for account in others {
+                msig = account.append_to_transaction_msig(t, msig)?;
+            }
+            Ok(SignedTransaction {
+                transaction: t.clone(),
+                transaction_id: t.id()?,
+                sig: TransactionSignature::Multi(msig),
+            })
+        })
+    }
+}
+
+fn sign_each<F>(
+    txns: &[Transaction],
+    indices: &[usize],
+    sign: F,
+) -> Result<Vec<SignedTransaction>, TransactionError>
+where
+    F: Fn(&Transaction) -> Result<SignedTransaction, TransactionError>,
+{
+    indices
+        .iter()
+        .map(|index| {
+            txns.get(*index)
+                .ok_or(TransactionError::InvalidGroupIndex { index: *index })
+                .and_then(&sign)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::payment;
+    use algonaut_core::{CompiledTeal, LogicSignature};
+
+    #[test]
+    fn test_account_signs_indices_in_order() {
+        let account = Account::generate();
+        let txns: Vec<Transaction> = (0..3).map(|a| payment(account.address(), a)).collect();
+
+        let signed = account.sign(&txns, &[2, 0]).unwrap();
+
+        assert_eq!(
+            vec![txns[2].id().unwrap(), txns[0].id().unwrap()],
+            signed
+                .into_iter()
+                .map(|t| t.transaction_id)
+                .collect::<Vec<_>>()
+        );
+        assert!(matches!(
+            account.sign(&txns, &[3]),
+            Err(TransactionError::InvalidGroupIndex { index: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_logic_signs_contract_account_transactions() {
+        let lsig = SignedLogic {
+            logic: CompiledTeal(vec![0x01, 0x20, 0x01, 0x01, 0x22]),
+            args: vec![],
+            sig: LogicSignature::ContractAccount,
+        };
+        let txns = vec![payment(lsig.as_address(), 1)];
+
+        let signed = lsig.sign(&txns, &[0]).unwrap();
+
+        assert_eq!(TransactionSignature::Logic(lsig), signed[0].sig);
+    }
+
+    #[test]
+    fn test_multisig_signs_with_all_accounts() {
+        let accounts = [Account::generate(), Account::generate()];
+        let address = MultisigAddress::new(
+            1,
+            2,
+            &accounts.iter().map(Account::address).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let txns = vec![payment(address.address(), 1)];
+        let signer = MultisigSigner::new(address.clone(), accounts.iter().collect());
+
+        let signed = signer.sign(&txns, &[0]).unwrap();
+
+        match &signed[0].sig {
+            TransactionSignature::Multi(msig) => {
+                assert!(msig.verify(&address, &txns[0].bytes_to_sign().unwrap()))
+            }
+            sig => panic!("Expected multisig, got: {:?}", sig),
+        }
+    }
+}
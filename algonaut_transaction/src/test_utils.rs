@@ -0,0 +1,17 @@
+//! Fixtures shared by the unit tests of the crate.
+
+use crate::{Pay, Transaction, TxnBuilder};
+use algonaut_core::{Address, MicroAlgos, Round};
+use algonaut_crypto::HashDigest;
+
+/// A payment of `amount` from `sender` to itself, with the min fee and a 1000 rounds window.
+pub fn payment(sender: Address, amount: u64) -> Transaction {
+    TxnBuilder::new(
+        MicroAlgos(1_000),
+        Round(1),
+        Round(1_001),
+        HashDigest([1; 32]),
+        Pay::new(sender, sender, MicroAlgos(amount)).build(),
+    )
+    .build()
+}
@@ -1,14 +1,20 @@
 use crate::account::Account;
 use crate::error::TransactionError;
 use algonaut_core::CompiledTeal;
-use algonaut_core::SignedLogic;
 use algonaut_core::ToMsgPack;
 use algonaut_core::{Address, MultisigSignature};
+use algonaut_core::{LogicSignature, SignedLogic};
 use algonaut_core::{MicroAlgos, Round, VotePk, VrfPk};
 use algonaut_crypto::HashDigest;
 use algonaut_crypto::Signature;
-use data_encoding::BASE32_NOPAD;
+use data_encoding::{BASE32_NOPAD, BASE64};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
 
 const MIN_TXN_FEE: MicroAlgos = MicroAlgos(1000);
 
@@ -23,6 +29,92 @@ pub enum TransactionType {
     AssetClawbackTransaction(AssetClawbackTransaction),
     AssetFreezeTransaction(AssetFreezeTransaction),
     ApplicationCallTransaction(ApplicationCallTransaction),
+    RawTransaction(RawTransaction),
+}
+
+impl TransactionType {
+    /// The type tag of the transaction, as encoded in its `type` field.
+    /// For [RawTransaction]s, `None` unless the type is known to the SDK ([TxType::StateProof]).
+    pub fn tx_type(&self) -> Option<TxType> {
+        match self {
+            TransactionType::Payment(_) => Some(TxType::Payment),
+            TransactionType::KeyRegistration(_) => Some(TxType::KeyRegistration),
+            TransactionType::AssetConfigurationTransaction(_) => Some(TxType::AssetConfig),
+            TransactionType::AssetTransferTransaction(_) => Some(TxType::AssetTransfer),
+            TransactionType::AssetAcceptTransaction(_) => Some(TxType::AssetTransfer),
+            TransactionType::AssetClawbackTransaction(_) => Some(TxType::AssetTransfer),
+            TransactionType::AssetFreezeTransaction(_) => Some(TxType::AssetFreeze),
+            TransactionType::ApplicationCallTransaction(_) => Some(TxType::ApplicationCall),
+            TransactionType::RawTransaction(raw) => raw.type_.parse().ok(),
+        }
+    }
+}
+
+/// The kinds of transactions, as distinguished on the network by the `type` field.
+///
+/// Unlike [TransactionType], this carries no fields: e.g. asset transfers, opt-ins and clawbacks
+/// are all [TxType::AssetTransfer] (`axfer`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TxType {
+    Payment,
+    KeyRegistration,
+    AssetConfig,
+    AssetTransfer,
+    AssetFreeze,
+    ApplicationCall,
+    /// Issued by the network only: the SDK decodes them as [RawTransaction]s.
+    StateProof,
+}
+
+impl TxType {
+    /// The string used for this type in the `type` field.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxType::Payment => "pay",
+            TxType::KeyRegistration => "keyreg",
+            TxType::AssetConfig => "acfg",
+            TxType::AssetTransfer => "axfer",
+            TxType::AssetFreeze => "afrz",
+            TxType::ApplicationCall => "appl",
+            TxType::StateProof => "stpf",
+        }
+    }
+}
+
+impl FromStr for TxType {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pay" => Ok(TxType::Payment),
+            "keyreg" => Ok(TxType::KeyRegistration),
+            "acfg" => Ok(TxType::AssetConfig),
+            "axfer" => Ok(TxType::AssetTransfer),
+            "afrz" => Ok(TxType::AssetFreeze),
+            "appl" => Ok(TxType::ApplicationCall),
+            "stpf" => Ok(TxType::StateProof),
+            unsupported_type => Err(TransactionError::Deserialization(format!(
+                "Not supported transaction type: {}",
+                unsupported_type
+            ))),
+        }
+    }
+}
+
+/// Reads only the `type` field of a msg pack encoded transaction, skipping the other fields.
+///
+/// Meant for quickly routing or filtering transactions (e.g. the transactions of a block)
+/// without paying for a full deserialization.
+pub fn peek_transaction_type(bytes: &[u8]) -> Result<TxType, TransactionError> {
+    #[derive(Deserialize)]
+    struct TypeField<'a> {
+        #[serde(rename = "type", borrow)]
+        type_: &'a str,
+    }
+
+    let field: TypeField = rmp_serde::from_read_ref(bytes)
+        .map_err(|e| TransactionError::Deserialization(e.to_string()))?;
+    field.type_.parse()
 }
 
 /// A transaction that can appear in a block
@@ -78,6 +170,11 @@ pub struct Transaction {
 }
 
 impl Transaction {
+    /// The type of the transaction, see [TransactionType::tx_type].
+    pub fn transaction_type(&self) -> Option<TxType> {
+        self.txn_type.tx_type()
+    }
+
     /// Creates a new transaction with a fee calculated based on `fee_per_byte`.
     pub fn fee_per_byte(
         mut self,
@@ -87,6 +184,28 @@ impl Transaction {
         Ok(self)
     }
 
+    /// The network fee paid by the sender, on top of any amount transferred.
+    pub fn fee(&self) -> MicroAlgos {
+        self.fee
+    }
+
+    /// How much the fee is above `min_fee` (zero if it isn't), e.g. to warn about excessive flat
+    /// fees.
+    pub fn fee_overpay(&self, min_fee: MicroAlgos) -> MicroAlgos {
+        MicroAlgos(self.fee.0.saturating_sub(min_fee.0))
+    }
+
+    /// Readable JSON representation, see [from_json](Self::from_json).
+    pub fn to_json(&self) -> Result<String, TransactionError> {
+        crate::json::to_json(self)
+    }
+
+    /// Parses the JSON representation written by [to_json](Self::to_json), meant for fixtures:
+    /// the msgpack keys, with addresses as base32 strings and other bytes as base64 strings.
+    pub fn from_json(json: &str) -> Result<Transaction, TransactionError> {
+        crate::json::from_json(json)
+    }
+
     pub fn bytes_to_sign(&self) -> Result<Vec<u8>, TransactionError> {
         let encoded_tx = self.to_owned().to_msg_pack()?;
         let mut prefix_encoded_tx = b"TX".to_vec();
@@ -108,7 +227,7 @@ impl Transaction {
     }
 
     // Estimates the size of the encoded transaction, used in calculating the fee
-    fn estimate_size(&self) -> Result<u64, TransactionError> {
+    pub(crate) fn estimate_size(&self) -> Result<u64, TransactionError> {
         let account = Account::generate();
         let signed_transaction = account.sign_transaction(self)?;
         Ok(signed_transaction.to_msg_pack()?.len() as u64)
@@ -127,6 +246,7 @@ impl Transaction {
             TransactionType::AssetClawbackTransaction(t) => t.sender,
             TransactionType::AssetFreezeTransaction(t) => t.sender,
             TransactionType::ApplicationCallTransaction(t) => t.sender,
+            TransactionType::RawTransaction(t) => t.sender,
         }
     }
 }
@@ -388,6 +508,109 @@ pub struct StateSchema {
     pub number_byteslices: u64,
 }
 
+/// A transaction of a type not modeled by the SDK yet (e.g. introduced by a newer protocol).
+///
+/// The type specific `fields` are merged with the common transaction fields into the canonical
+/// encoding. They must use the network's field names and, like those, omit empty values.
+///
+/// Decoded transactions of unknown types (e.g. in blocks) are returned as raw transactions, so
+/// a new type doesn't break decoding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawTransaction {
+    /// The address of the account that signs and pays the fee.
+    pub sender: Address,
+
+    /// The value of the `type` field.
+    pub type_: String,
+
+    /// Fields specific to this transaction type, by encoded name.
+    pub fields: BTreeMap<String, RawValue>,
+}
+
+/// A msg pack value of a [RawTransaction] field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RawValue {
+    Bool(bool),
+    Int(u64),
+    String(String),
+    Bytes(Vec<u8>),
+    Array(Vec<RawValue>),
+    Map(BTreeMap<String, RawValue>),
+}
+
+impl Serialize for RawValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            RawValue::Bool(b) => serializer.serialize_bool(*b),
+            RawValue::Int(i) => serializer.serialize_u64(*i),
+            RawValue::String(s) => serializer.serialize_str(s),
+            RawValue::Bytes(b) => serializer.serialize_bytes(b),
+            RawValue::Array(a) => a.serialize(serializer),
+            RawValue::Map(m) => m.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RawValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(RawValueVisitor)
+    }
+}
+
+struct RawValueVisitor;
+
+impl<'de> Visitor<'de> for RawValueVisitor {
+    type Value = RawValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a msg pack value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(RawValue::Bool(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(RawValue::Int(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        u64::try_from(v)
+            .map(RawValue::Int)
+            .map_err(|_| E::custom(format!("negative integer: {}", v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(RawValue::String(v.to_owned()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(RawValue::Bytes(v.to_vec()))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = vec![];
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(RawValue::Array(values))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut values = BTreeMap::new();
+        while let Some((key, value)) = map.next_entry()? {
+            values.insert(key, value);
+        }
+        Ok(RawValue::Map(values))
+    }
+}
+
 /// Wraps a transaction in a signature. The encoding of this struct is suitable to be broadcast
 /// on the network
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -397,9 +620,601 @@ pub struct SignedTransaction {
     pub sig: TransactionSignature,
 }
 
+impl SignedTransaction {
+    /// Whether `transaction` is the transaction that was signed, i.e. has the signed transaction
+    /// id. The signature is invalid for a transaction modified after signing.
+    pub fn matches_transaction(&self, transaction: &Transaction) -> bool {
+        matches!(transaction.id(), Ok(id) if id == self.transaction_id)
+    }
+
+    /// Whether the transaction carries a signature: a non-zero single signature, at least
+    /// threshold multisig subsignatures, or a logic signature. The signature isn't verified.
+    ///
+    /// Catches e.g. an unsigned transaction wrapped by hand before submitting it.
+    pub fn is_signed(&self) -> bool {
+        fn signed(sig: &Signature) -> bool {
+            sig.0 != [0; 64]
+        }
+        fn msig_signed(msig: &MultisigSignature) -> bool {
+            let signatures = msig
+                .subsigs
+                .iter()
+                .filter(|s| matches!(&s.sig, Some(sig) if signed(sig)))
+                .count();
+            msig.threshold > 0 && signatures >= msig.threshold as usize
+        }
+        match &self.sig {
+            TransactionSignature::Single(sig) => signed(sig),
+            TransactionSignature::Multi(msig) => msig_signed(msig),
+            TransactionSignature::Logic(lsig) => match &lsig.sig {
+                LogicSignature::ContractAccount => true,
+                LogicSignature::DelegatedSig(sig) => signed(sig),
+                LogicSignature::DelegatedMultiSig(msig) => msig_signed(msig),
+            },
+        }
+    }
+
+    /// Attaches a single signature of `transaction` (of its [bytes_to_sign](Transaction::bytes_to_sign)),
+    /// produced elsewhere, e.g. by a remote signer or hardware wallet.
+    ///
+    /// The signature isn't verified.
+    pub fn with_signature(
+        transaction: Transaction,
+        signature: Signature,
+    ) -> Result<SignedTransaction, TransactionError> {
+        Ok(SignedTransaction {
+            transaction_id: transaction.id()?,
+            transaction,
+            sig: TransactionSignature::Single(signature),
+        })
+    }
+
+    /// Assembles the multisig transaction signed independently by each of `partials`, merging
+    /// their subsignatures, e.g. after collecting them from the signers.
+    ///
+    /// Fails if there are fewer than two partials, they're not all multisig signatures of the
+    /// same transaction by the same multisig address, or a subsignature is present in more than
+    /// one of them.
+    pub fn assemble_multisig(
+        partials: &[SignedTransaction],
+    ) -> Result<SignedTransaction, TransactionError> {
+        if partials.len() < 2 {
+            return Err(TransactionError::InsufficientTransactions);
+        }
+        let msigs = partials
+            .iter()
+            .enumerate()
+            .map(|(index, partial)| match &partial.sig {
+                TransactionSignature::Multi(msig) => Ok(msig),
+                _ => Err(TransactionError::MissingMultisig { index }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = &partials[0];
+        if partials[1..]
+            .iter()
+            .any(|p| p.transaction_id != first.transaction_id || p.transaction != first.transaction)
+        {
+            return Err(TransactionError::MismatchingTransactions);
+        }
+
+        let mut merged = msigs[0].clone();
+        for msig in &msigs[1..] {
+            if msig.subsigs.len() != merged.subsigs.len() {
+                return Err(TransactionError::InvalidNumberOfSubsignatures);
+            }
+            if msig.version != merged.version || msig.threshold != merged.threshold {
+                return Err(TransactionError::MismatchingMultisigParams);
+            }
+            if msig
+                .subsigs
+                .iter()
+                .zip(&merged.subsigs)
+                .any(|(subsig, merged_subsig)| subsig.key != merged_subsig.key)
+            {
+                return Err(TransactionError::InvalidPublicKeyInMultisig);
+            }
+            for (index, (merged_subsig, subsig)) in
+                merged.subsigs.iter_mut().zip(&msig.subsigs).enumerate()
+            {
+                match (&merged_subsig.sig, &subsig.sig) {
+                    (Some(merged_sig), Some(sig)) if merged_sig != sig => {
+                        return Err(TransactionError::MismatchingSignatures)
+                    }
+                    (Some(_), Some(_)) => {
+                        return Err(TransactionError::DuplicateSubsignature { index })
+                    }
+                    (None, Some(sig)) => merged_subsig.sig = Some(*sig),
+                    (_, None) => {}
+                }
+            }
+        }
+        Ok(SignedTransaction {
+            transaction: first.transaction.clone(),
+            transaction_id: first.transaction_id.clone(),
+            sig: TransactionSignature::Multi(merged),
+        })
+    }
+
+    /// Base64 of the msgpack encoding, e.g. to send it through JSON, see
+    /// [from_base64](Self::from_base64).
+    pub fn to_base64(&self) -> Result<String, TransactionError> {
+        Ok(BASE64.encode(&self.to_msg_pack()?))
+    }
+
+    /// Decodes a signed transaction encoded with [to_base64](Self::to_base64).
+    pub fn from_base64(base64_str: &str) -> Result<SignedTransaction, TransactionError> {
+        let bytes = BASE64
+            .decode(base64_str.as_bytes())
+            .map_err(|e| TransactionError::Deserialization(e.to_string()))?;
+        rmp_serde::from_slice(&bytes).map_err(|e| TransactionError::Deserialization(e.to_string()))
+    }
+
+    /// Genesis hash of the network the transaction was built for.
+    ///
+    /// Useful to check a signed transaction loaded from a file against the target network before
+    /// broadcasting it, e.g. comparing with the `genesis_hash` of the node's
+    /// [SuggestedTransactionParams](algonaut_core::SuggestedTransactionParams): a transaction of
+    /// another network would be rejected.
+    pub fn genesis_hash(&self) -> HashDigest {
+        self.transaction.genesis_hash
+    }
+
+    /// The kind of signature authorizing the transaction.
+    pub fn signature_kind(&self) -> SignatureKind {
+        match &self.sig {
+            TransactionSignature::Single(_) => SignatureKind::Single,
+            TransactionSignature::Multi(_) => SignatureKind::Multisig,
+            TransactionSignature::Logic(_) => SignatureKind::LogicSig,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TransactionSignature {
     Single(Signature),
     Multi(MultisigSignature),
     Logic(SignedLogic),
 }
+
+/// Kind of a [TransactionSignature].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SignatureKind {
+    Single,
+    Multisig,
+    /// Contract account or delegated logic signature.
+    LogicSig,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Pay, Raw, TransferAsset, TxnBuilder};
+    use algonaut_core::{MultisigAddress, SuggestedTransactionParams};
+
+    fn encoded(txn_type: TransactionType) -> Vec<u8> {
+        TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            txn_type,
+        )
+        .build()
+        .to_msg_pack()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_signed() {
+        let account = Account::generate();
+        let t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        let unsigned = SignedTransaction {
+            transaction: t.clone(),
+            transaction_id: t.id().unwrap(),
+            sig: TransactionSignature::Single(Signature([0; 64])),
+        };
+
+        assert!(account.sign_transaction(&t).unwrap().is_signed());
+        assert!(!unsigned.is_signed());
+
+        let other = Account::generate();
+        let msig_address =
+            MultisigAddress::new(1, 2, &[account.address(), other.address()]).unwrap();
+        let msig_t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            Pay::new(msig_address.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        let partial = account
+            .init_transaction_msig(&msig_t, &msig_address)
+            .unwrap();
+        let signed_msig = |msig| SignedTransaction {
+            transaction: msig_t.clone(),
+            transaction_id: msig_t.id().unwrap(),
+            sig: TransactionSignature::Multi(msig),
+        };
+        assert!(!signed_msig(partial.clone()).is_signed());
+        let complete = other.append_to_transaction_msig(&msig_t, partial).unwrap();
+        assert!(signed_msig(complete).is_signed());
+    }
+
+    #[test]
+    fn test_fee_overpay() {
+        let transaction = |fee| {
+            TxnBuilder::new(
+                MicroAlgos(fee),
+                Round(1),
+                Round(1001),
+                HashDigest([1; 32]),
+                Pay::new(Address([1; 32]), Address([1; 32]), MicroAlgos(1)).build(),
+            )
+            .build()
+        };
+
+        assert_eq!(
+            MicroAlgos(999_000),
+            transaction(1_000_000).fee_overpay(MicroAlgos(1_000))
+        );
+        assert_eq!(
+            MicroAlgos(0),
+            transaction(1_000).fee_overpay(MicroAlgos(1_000))
+        );
+        assert_eq!(MicroAlgos(0), transaction(0).fee_overpay(MicroAlgos(1_000)));
+    }
+
+    #[test]
+    fn test_signed_transaction_base64_round_trip() {
+        let account = crate::account::Account::generate();
+        let transaction = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .note(b"hi".to_vec())
+        .build();
+        let signed = account.sign_transaction(&transaction).unwrap();
+
+        let base64 = signed.to_base64().unwrap();
+
+        assert_eq!(BASE64.encode(&signed.to_msg_pack().unwrap()), base64);
+        assert_eq!(signed, SignedTransaction::from_base64(&base64).unwrap());
+        assert!(SignedTransaction::from_base64("not base64").is_err());
+    }
+
+    #[test]
+    fn test_with_external_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let sender = Address(key.verifying_key().to_bytes());
+        let transaction = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            Pay::new(sender, sender, MicroAlgos(1)).build(),
+        )
+        .build();
+        let raw = key.sign(&transaction.bytes_to_sign().unwrap()).to_bytes();
+
+        let signature = Signature::from_bytes(&raw).unwrap();
+        let signed = SignedTransaction::with_signature(transaction.clone(), signature).unwrap();
+
+        assert_eq!(transaction.id().unwrap(), signed.transaction_id);
+        assert_eq!(TransactionSignature::Single(signature), signed.sig);
+        assert!(sender
+            .as_public_key()
+            .verify(&transaction.bytes_to_sign().unwrap(), &signature));
+        assert!(Signature::from_bytes(&raw[..63]).is_err());
+    }
+
+    #[test]
+    fn test_signature_kind() {
+        use crate::account::Account;
+        use crate::signer::{MultisigSigner, TransactionSigner};
+        use algonaut_core::{LogicSignature, MultisigAddress};
+
+        let account = Account::generate();
+        let payment = |sender| {
+            TxnBuilder::new(
+                MicroAlgos(1000),
+                Round(1),
+                Round(1001),
+                HashDigest([1; 32]),
+                Pay::new(sender, sender, MicroAlgos(1)).build(),
+            )
+            .build()
+        };
+        let multisig = MultisigAddress::new(1, 1, &[account.address()]).unwrap();
+        let lsig = SignedLogic {
+            logic: CompiledTeal(vec![0x01, 0x20, 0x01, 0x01, 0x22]),
+            args: vec![],
+            sig: LogicSignature::ContractAccount,
+        };
+
+        let single = account
+            .sign_transaction(&payment(account.address()))
+            .unwrap();
+        let multi = MultisigSigner::new(multisig.clone(), vec![&account])
+            .sign(&[payment(multisig.address())], &[0])
+            .unwrap();
+        let logic = lsig.sign(&[payment(lsig.as_address())], &[0]).unwrap();
+
+        assert_eq!(SignatureKind::Single, single.signature_kind());
+        assert_eq!(SignatureKind::Multisig, multi[0].signature_kind());
+        assert_eq!(SignatureKind::LogicSig, logic[0].signature_kind());
+    }
+
+    #[test]
+    fn test_matches_transaction_detects_modification() {
+        let account = crate::account::Account::generate();
+        let t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        let mut signed = account.sign_transaction(&t).unwrap();
+
+        assert!(signed.matches_transaction(&t));
+
+        if let TransactionType::Payment(payment) = &mut signed.transaction.txn_type {
+            payment.amount = MicroAlgos(2);
+        }
+        assert!(!signed.matches_transaction(&signed.transaction));
+    }
+
+    #[test]
+    fn test_genesis_hash_of_decoded_signed_transaction() {
+        let account = crate::account::Account::generate();
+        let params = SuggestedTransactionParams::testnet(Round(1));
+        let t = TxnBuilder::with(
+            params.clone(),
+            Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        let bytes = account.sign_transaction(&t).unwrap().to_msg_pack().unwrap();
+
+        let signed: SignedTransaction = rmp_serde::from_slice(&bytes).unwrap();
+
+        assert_eq!(params.genesis_hash, signed.genesis_hash());
+    }
+
+    fn multisig_partials() -> (
+        MultisigAddress,
+        Vec<crate::account::Account>,
+        Transaction,
+        Vec<SignedTransaction>,
+    ) {
+        use crate::account::Account;
+
+        let accounts = vec![
+            Account::generate(),
+            Account::generate(),
+            Account::generate(),
+        ];
+        let address = MultisigAddress::new(
+            1,
+            2,
+            &accounts.iter().map(Account::address).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            Pay::new(address.address(), address.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        let partials = accounts
+            .iter()
+            .map(|account| SignedTransaction {
+                transaction: t.clone(),
+                transaction_id: t.id().unwrap(),
+                sig: TransactionSignature::Multi(
+                    account.init_transaction_msig(&t, &address).unwrap(),
+                ),
+            })
+            .collect();
+        (address, accounts, t, partials)
+    }
+
+    #[test]
+    fn test_assemble_multisig() {
+        let (address, _, t, partials) = multisig_partials();
+
+        let assembled =
+            SignedTransaction::assemble_multisig(&[partials[2].clone(), partials[0].clone()])
+                .unwrap();
+
+        assert_eq!(t.id().unwrap(), assembled.transaction_id);
+        match &assembled.sig {
+            TransactionSignature::Multi(msig) => {
+                assert!(msig.verify(&address, &t.bytes_to_sign().unwrap()));
+                assert!(msig.subsigs[1].sig.is_none());
+            }
+            sig => panic!("Expected multisig, got: {:?}", sig),
+        }
+    }
+
+    #[test]
+    fn test_assemble_multisig_rejects_mismatching_transactions() {
+        let (address, accounts, t, partials) = multisig_partials();
+        let mut other = t;
+        other.note = Some(b"other".to_vec());
+        let other_partial = SignedTransaction {
+            transaction_id: other.id().unwrap(),
+            sig: TransactionSignature::Multi(
+                accounts[1].init_transaction_msig(&other, &address).unwrap(),
+            ),
+            transaction: other,
+        };
+
+        let res = SignedTransaction::assemble_multisig(&[partials[0].clone(), other_partial]);
+
+        assert!(matches!(
+            res,
+            Err(TransactionError::MismatchingTransactions)
+        ));
+    }
+
+    #[test]
+    fn test_assemble_multisig_rejects_mismatching_metadata() {
+        let (_, _, _, partials) = multisig_partials();
+        let with_msig = |partial: &SignedTransaction, f: &dyn Fn(&mut MultisigSignature)| {
+            let mut partial = partial.clone();
+            if let TransactionSignature::Multi(msig) = &mut partial.sig {
+                f(msig)
+            }
+            partial
+        };
+        let other_threshold = with_msig(&partials[1], &|msig| msig.threshold = 3);
+        let other_version = with_msig(&partials[1], &|msig| msig.version = 2);
+        let other_key = with_msig(&partials[1], &|msig| {
+            msig.subsigs.swap(0, 1);
+        });
+        let fewer_keys = with_msig(&partials[1], &|msig| {
+            msig.subsigs.pop();
+        });
+
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), other_threshold]),
+            Err(TransactionError::MismatchingMultisigParams)
+        ));
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), other_version]),
+            Err(TransactionError::MismatchingMultisigParams)
+        ));
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), other_key]),
+            Err(TransactionError::InvalidPublicKeyInMultisig)
+        ));
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), fewer_keys]),
+            Err(TransactionError::InvalidNumberOfSubsignatures)
+        ));
+    }
+
+    #[test]
+    fn test_assemble_multisig_rejects_duplicate_signers() {
+        let (_, _, _, partials) = multisig_partials();
+        let mut forged = partials[0].clone();
+        if let TransactionSignature::Multi(msig) = &mut forged.sig {
+            msig.subsigs[0].sig = Some(Signature([1; 64]));
+        }
+
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), partials[0].clone()]),
+            Err(TransactionError::DuplicateSubsignature { index: 0 })
+        ));
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), forged]),
+            Err(TransactionError::MismatchingSignatures)
+        ));
+    }
+
+    #[test]
+    fn test_assemble_multisig_rejects_single_and_non_multisig_partials() {
+        let (_, accounts, t, partials) = multisig_partials();
+        let single = accounts[0].sign_transaction(&t).unwrap();
+
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&partials[..1]),
+            Err(TransactionError::InsufficientTransactions)
+        ));
+        assert!(matches!(
+            SignedTransaction::assemble_multisig(&[partials[0].clone(), single]),
+            Err(TransactionError::MissingMultisig { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_built_payment_fee() {
+        let address = Address([1; 32]);
+        let params = SuggestedTransactionParams::testnet(Round(1));
+
+        let t = TxnBuilder::with(params, Pay::new(address, address, MicroAlgos(5)).build()).build();
+
+        assert_eq!(MicroAlgos(1_000), t.fee());
+    }
+
+    #[test]
+    fn test_peek_payment_type() {
+        let address = Address([1; 32]);
+        let bytes = encoded(Pay::new(address, address, MicroAlgos(123)).build());
+
+        assert_eq!(TxType::Payment, peek_transaction_type(&bytes).unwrap());
+    }
+
+    #[test]
+    fn test_peek_asset_transfer_type() {
+        let address = Address([1; 32]);
+        let bytes = encoded(TransferAsset::new(address, 1, 2, address).build());
+
+        assert_eq!(
+            TxType::AssetTransfer,
+            peek_transaction_type(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_peek_does_not_decode_other_fields() {
+        // Not a valid transaction (mandatory fields are missing), but the type can be read.
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("type", "appl");
+        fields.insert("snd", "not an address");
+        let bytes = rmp_serde::to_vec_named(&fields).unwrap();
+
+        assert!(rmp_serde::from_slice::<Transaction>(&bytes).is_err());
+        assert_eq!(
+            TxType::ApplicationCall,
+            peek_transaction_type(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_raw_transaction_canonical_encoding() {
+        let address = Address([1; 32]);
+        let bytes = encoded(
+            Raw::new(address, "newt")
+                .field("zz", RawValue::Int(5))
+                .field("ab", RawValue::Bytes(vec![2; 4]))
+                .build(),
+        );
+
+        let mut expected = BTreeMap::new();
+        expected.insert("ab", RawValue::Bytes(vec![2; 4]));
+        expected.insert("fee", RawValue::Int(1000));
+        expected.insert("fv", RawValue::Int(1));
+        expected.insert("gh", RawValue::Bytes(vec![1; 32]));
+        expected.insert("lv", RawValue::Int(1001));
+        expected.insert("snd", RawValue::Bytes(vec![1; 32]));
+        expected.insert("type", RawValue::String("newt".to_owned()));
+        expected.insert("zz", RawValue::Int(5));
+        assert_eq!(rmp_serde::to_vec_named(&expected).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_peek_unknown_type() {
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("type", "foo");
+        let bytes = rmp_serde::to_vec_named(&fields).unwrap();
+
+        assert!(peek_transaction_type(&bytes).is_err());
+    }
+}
@@ -1,9 +1,10 @@
-use algonaut_core::ToMsgPack;
+use algonaut_core::{MicroAlgos, ToMsgPack, MAX_TX_GROUP_SIZE};
 use algonaut_crypto::HashDigest;
 use serde::{Deserialize, Serialize, Serializer};
 use sha2::Digest;
 
-use crate::{error::TransactionError, Transaction};
+use crate::error::{GroupIncompatibility, TransactionError};
+use crate::{account::Account, SignedTransaction, Transaction};
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
 pub struct TxGroup {
@@ -12,8 +13,6 @@ pub struct TxGroup {
 }
 
 impl TxGroup {
-    const MAX_TX_GROUP_SIZE: usize = 16;
-
     pub fn new(tx_group_hashes: Vec<HashDigest>) -> TxGroup {
         TxGroup { tx_group_hashes }
     }
@@ -27,21 +26,82 @@ impl TxGroup {
     }
 
     fn compute_group_id(txns: &[&mut Transaction]) -> Result<HashDigest, TransactionError> {
-        if txns.is_empty() {
-            return Err(TransactionError::EmptyTransactionListError);
+        let preimage = group_preimage(txns.iter().map(|t| &**t))?;
+        let hashed = sha2::Sha512Trunc256::digest(&preimage);
+        Ok(HashDigest(hashed.into()))
+    }
+
+    /// Encodes signed transactions to be submitted as an atomic group, i.e. concatenates their
+    /// msg pack encodings in order.
+    ///
+    /// Returns an error if a transaction has no group id or the group ids don't match.
+    pub fn encode_signed_group_checked(
+        txns: &[SignedTransaction],
+    ) -> Result<Vec<u8>, TransactionError> {
+        TxGroup::check_signed_group(txns)?;
+        let mut bytes = vec![];
+        for t in txns {
+            bytes.extend_from_slice(&t.to_msg_pack()?);
         }
-        if txns.len() > Self::MAX_TX_GROUP_SIZE {
+        Ok(bytes)
+    }
+
+    /// Checks that signed transactions can be submitted as an atomic group: there are at most
+    /// [MAX_TX_GROUP_SIZE] of them and they all have the same group id.
+    pub fn check_signed_group(txns: &[SignedTransaction]) -> Result<(), TransactionError> {
+        let group = match txns.first() {
+            Some(first) => first.transaction.group,
+            None => return Err(TransactionError::EmptyTransactionListError),
+        };
+        if txns.len() > MAX_TX_GROUP_SIZE {
             return Err(TransactionError::MaxTransactionGroupSizeError {
-                size: Self::MAX_TX_GROUP_SIZE,
+                size: MAX_TX_GROUP_SIZE,
             });
         }
-        let mut ids: Vec<HashDigest> = vec![];
         for t in txns {
-            ids.push(t.raw_id()?);
+            match t.transaction.group {
+                None => return Err(TransactionError::MissingGroupId),
+                Some(_) if t.transaction.group != group => {
+                    return Err(TransactionError::MismatchingGroupIds)
+                }
+                Some(_) => {}
+            }
         }
-        let group = TxGroup::new(ids);
-        let hashed = sha2::Sha512Trunc256::digest(&group.bytes_to_sign()?);
-        Ok(HashDigest(hashed.into()))
+        Ok(())
+    }
+
+    /// Signs the legs of a group owned by the given signers, as `(index in group, signer)`, e.g.
+    /// each party's leg of an atomic swap. The other legs are left to be signed by their owners.
+    ///
+    /// Returns the signed legs in group order. Fails if the transactions don't share a group id
+    /// or an index is out of bounds or repeated.
+    pub fn sign_group_legs(
+        group: &[Transaction],
+        signers: &[(usize, &Account)],
+    ) -> Result<Vec<SignedTransaction>, TransactionError> {
+        let group_id = match group.first() {
+            Some(first) => first.group,
+            None => return Err(TransactionError::EmptyTransactionListError),
+        };
+        if group_id.is_none() {
+            return Err(TransactionError::MissingGroupId);
+        }
+        if group.iter().any(|t| t.group != group_id) {
+            return Err(TransactionError::MismatchingGroupIds);
+        }
+        let mut signers = signers.to_vec();
+        signers.sort_by_key(|(index, _)| *index);
+        let mut signed = vec![];
+        for (i, (index, signer)) in signers.iter().enumerate() {
+            if i > 0 && signers[i - 1].0 == *index {
+                return Err(TransactionError::DuplicateGroupIndex { index: *index });
+            }
+            let transaction = group
+                .get(*index)
+                .ok_or(TransactionError::InvalidGroupIndex { index: *index })?;
+            signed.push(signer.sign_transaction(transaction)?);
+        }
+        Ok(signed)
     }
 
     fn bytes_to_sign(&self) -> Result<Vec<u8>, TransactionError> {
@@ -52,6 +112,92 @@ impl TxGroup {
     }
 }
 
+/// The bytes hashed to compute the group id of `txns`: the `"TG"` prefixed msg pack encoding of
+/// the list of transaction ids, e.g. to compare the group id computation with other
+/// implementations.
+pub fn txgroup_preimage(txns: &[Transaction]) -> Result<Vec<u8>, TransactionError> {
+    group_preimage(txns.iter())
+}
+
+fn group_preimage<'a>(
+    txns: impl ExactSizeIterator<Item = &'a Transaction>,
+) -> Result<Vec<u8>, TransactionError> {
+    if txns.len() == 0 {
+        return Err(TransactionError::EmptyTransactionListError);
+    }
+    if txns.len() > MAX_TX_GROUP_SIZE {
+        return Err(TransactionError::MaxTransactionGroupSizeError {
+            size: MAX_TX_GROUP_SIZE,
+        });
+    }
+    let mut ids: Vec<HashDigest> = vec![];
+    for t in txns {
+        ids.push(t.raw_id()?);
+    }
+    TxGroup::new(ids).bytes_to_sign()
+}
+
+/// Checks that `txns` can be confirmed as a group: they share the genesis hash, their validity
+/// windows overlap, and they don't already have (different) group ids, which
+/// [assign_group_id](TxGroup::assign_group_id) would overwrite.
+///
+/// Returns the first incompatibility found, in group order.
+pub fn can_group(txns: &[Transaction]) -> Result<(), GroupIncompatibility> {
+    let first = match txns.first() {
+        Some(first) => first,
+        None => return Ok(()),
+    };
+    let (mut first_valid, mut last_valid) = (first.first_valid, first.last_valid);
+    for (index, t) in txns.iter().enumerate().skip(1) {
+        if t.genesis_hash != first.genesis_hash {
+            return Err(GroupIncompatibility::GenesisHash { index });
+        }
+        first_valid = first_valid.max(t.first_valid);
+        last_valid = last_valid.min(t.last_valid);
+        if first_valid > last_valid {
+            return Err(GroupIncompatibility::ValidityWindow { index });
+        }
+        if t.group != first.group {
+            return Err(GroupIncompatibility::GroupId { index });
+        }
+    }
+    Ok(())
+}
+
+/// Fees of the transactions of a group.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GroupFeeSummary {
+    /// Fee of each transaction, in group order.
+    pub fees: Vec<MicroAlgos>,
+    /// Sum of the fees. Fees are pooled: a transaction can pay more than its minimum fee to cover
+    /// other transactions of the group.
+    pub total: MicroAlgos,
+}
+
+/// Summarizes the fees paid by the transactions of a group, failing with
+/// [TransactionError::FeeOverflow] if their sum overflows.
+pub fn group_fee_summary(txns: &[Transaction]) -> Result<GroupFeeSummary, TransactionError> {
+    let fees: Vec<MicroAlgos> = txns.iter().map(Transaction::fee).collect();
+    let total = fees
+        .iter()
+        .try_fold(0u64, |total, fee| total.checked_add(fee.0))
+        .ok_or(TransactionError::FeeOverflow)?;
+    Ok(GroupFeeSummary {
+        total: MicroAlgos(total),
+        fees,
+    })
+}
+
+/// Estimated size of the encoded group once signed (with single signatures), i.e. the sum of the
+/// estimated sizes of its signed transactions, e.g. to compute the pooled fee of a group.
+pub fn group_encoded_size(txns: &[Transaction]) -> Result<usize, TransactionError> {
+    let mut size = 0;
+    for t in txns {
+        size += t.estimate_size()? as usize;
+    }
+    Ok(size)
+}
+
 impl Serialize for TxGroup {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
@@ -64,3 +210,237 @@ impl Serialize for TxGroup {
         state.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::payment;
+    use crate::transaction::TransactionSignature;
+    use algonaut_core::Round;
+
+    #[test]
+    fn test_group_fee_summary() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        t1.fee = MicroAlgos(2_000);
+        let mut t2 = payment(account.address(), 2);
+        t2.fee = MicroAlgos(0);
+        let t3 = payment(account.address(), 3);
+
+        let summary = group_fee_summary(&[t1, t2, t3]).unwrap();
+
+        assert_eq!(
+            vec![MicroAlgos(2_000), MicroAlgos(0), MicroAlgos(1_000)],
+            summary.fees
+        );
+        assert_eq!(MicroAlgos(3_000), summary.total);
+    }
+
+    #[test]
+    fn test_group_fee_summary_overflow() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        t1.fee = MicroAlgos(u64::MAX);
+        let t2 = payment(account.address(), 2);
+
+        let res = group_fee_summary(&[t1, t2]);
+
+        assert!(matches!(res, Err(TransactionError::FeeOverflow)));
+    }
+
+    #[test]
+    fn test_can_group() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+        t2.first_valid = Round(1_000);
+        t2.last_valid = Round(2_000);
+
+        assert_eq!(Ok(()), can_group(&[t1.clone(), t2.clone()]));
+
+        TxGroup::assign_group_id(vec![&mut t1]).unwrap();
+        assert_eq!(
+            Err(GroupIncompatibility::GroupId { index: 1 }),
+            can_group(&[t1, t2])
+        );
+    }
+
+    #[test]
+    fn test_can_group_mismatched_genesis_hash() {
+        let account = Account::generate();
+        let t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+        t2.genesis_hash = HashDigest([2; 32]);
+
+        assert_eq!(
+            Err(GroupIncompatibility::GenesisHash { index: 2 }),
+            can_group(&[t1.clone(), t1, t2])
+        );
+    }
+
+    #[test]
+    fn test_can_group_disjoint_validity_windows() {
+        let account = Account::generate();
+        let t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+        t2.first_valid = Round(900);
+        t2.last_valid = Round(1_900);
+        let mut t3 = payment(account.address(), 3);
+        t3.first_valid = Round(1_002);
+        t3.last_valid = Round(2_002);
+
+        // Each window overlaps with t2's, but t3's doesn't overlap with t1's.
+        assert_eq!(Ok(()), can_group(&[t1.clone(), t2.clone()]));
+        assert_eq!(Ok(()), can_group(&[t2.clone(), t3.clone()]));
+        assert_eq!(
+            Err(GroupIncompatibility::ValidityWindow { index: 2 }),
+            can_group(&[t1, t2, t3])
+        );
+    }
+
+    #[test]
+    fn test_group_encoded_size() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+        TxGroup::assign_group_id(vec![&mut t1, &mut t2]).unwrap();
+
+        let predicted = group_encoded_size(&[t1.clone(), t2.clone()]).unwrap();
+
+        let signed = vec![
+            account.sign_transaction(&t1).unwrap(),
+            account.sign_transaction(&t2).unwrap(),
+        ];
+        let actual = TxGroup::encode_signed_group_checked(&signed).unwrap().len();
+        // Single signatures have a fixed size.
+        assert_eq!(actual, predicted);
+    }
+
+    #[test]
+    fn test_txgroup_preimage() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+
+        let preimage = txgroup_preimage(&[t1.clone(), t2.clone()]).unwrap();
+        TxGroup::assign_group_id(vec![&mut t1, &mut t2]).unwrap();
+
+        assert_eq!(b"TG", &preimage[..2]);
+        assert_eq!(
+            Some(HashDigest(sha2::Sha512Trunc256::digest(&preimage).into())),
+            t1.group
+        );
+    }
+
+    #[test]
+    fn test_encode_signed_group_checked() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+        TxGroup::assign_group_id(vec![&mut t1, &mut t2]).unwrap();
+        let signed_t1 = account.sign_transaction(&t1).unwrap();
+        let signed_t2 = account.sign_transaction(&t2).unwrap();
+
+        let encoded =
+            TxGroup::encode_signed_group_checked(&[signed_t1.clone(), signed_t2.clone()]).unwrap();
+
+        let expected = [
+            signed_t1.to_msg_pack().unwrap(),
+            signed_t2.to_msg_pack().unwrap(),
+        ]
+        .concat();
+        assert_eq!(expected, encoded);
+    }
+
+    #[test]
+    fn test_encode_signed_group_checked_mismatching_groups() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        let mut t2 = payment(account.address(), 2);
+        let mut t3 = payment(account.address(), 3);
+        TxGroup::assign_group_id(vec![&mut t1, &mut t2]).unwrap();
+        TxGroup::assign_group_id(vec![&mut t3]).unwrap();
+        let signed_t1 = account.sign_transaction(&t1).unwrap();
+        let signed_t3 = account.sign_transaction(&t3).unwrap();
+
+        let res = TxGroup::encode_signed_group_checked(&[signed_t1, signed_t3]);
+
+        assert!(matches!(res, Err(TransactionError::MismatchingGroupIds)));
+    }
+
+    #[test]
+    fn test_assign_group_id_max_group_size() {
+        let account = Account::generate();
+        let mut txns: Vec<Transaction> = (0..=MAX_TX_GROUP_SIZE as u64)
+            .map(|amount| payment(account.address(), amount))
+            .collect();
+
+        let res = TxGroup::assign_group_id(txns.iter_mut().collect());
+
+        assert!(matches!(
+            res,
+            Err(TransactionError::MaxTransactionGroupSizeError {
+                size: MAX_TX_GROUP_SIZE
+            })
+        ));
+        assert!(TxGroup::assign_group_id(txns.iter_mut().skip(1).collect()).is_ok());
+    }
+
+    #[test]
+    fn test_encode_signed_group_checked_missing_group() {
+        let account = Account::generate();
+        let signed_t = account
+            .sign_transaction(&payment(account.address(), 1))
+            .unwrap();
+
+        let res = TxGroup::encode_signed_group_checked(&[signed_t]);
+
+        assert!(matches!(res, Err(TransactionError::MissingGroupId)));
+    }
+
+    #[test]
+    fn test_sign_group_legs() {
+        let alice = Account::generate();
+        let bob = Account::generate();
+        let mut t1 = payment(alice.address(), 1);
+        let mut t2 = payment(bob.address(), 2);
+        TxGroup::assign_group_id(vec![&mut t1, &mut t2]).unwrap();
+        let group = [t1, t2];
+
+        let alice_legs = TxGroup::sign_group_legs(&group, &[(0, &alice)]).unwrap();
+        let bob_legs = TxGroup::sign_group_legs(&group, &[(1, &bob)]).unwrap();
+        let signed = [alice_legs, bob_legs].concat();
+
+        assert!(TxGroup::encode_signed_group_checked(&signed).is_ok());
+        for (t, signer) in signed.iter().zip([&alice, &bob].iter()) {
+            let sig = match t.sig {
+                TransactionSignature::Single(sig) => sig,
+                _ => panic!("Expected single signature"),
+            };
+            assert!(signer
+                .address()
+                .as_public_key()
+                .verify(&t.transaction.bytes_to_sign().unwrap(), &sig));
+        }
+    }
+
+    #[test]
+    fn test_sign_group_legs_invalid_index() {
+        let account = Account::generate();
+        let mut t1 = payment(account.address(), 1);
+        TxGroup::assign_group_id(vec![&mut t1]).unwrap();
+        let group = [t1];
+
+        let out_of_bounds = TxGroup::sign_group_legs(&group, &[(1, &account)]);
+        let repeated = TxGroup::sign_group_legs(&group, &[(0, &account), (0, &account)]);
+
+        assert!(matches!(
+            out_of_bounds,
+            Err(TransactionError::InvalidGroupIndex { index: 1 })
+        ));
+        assert!(matches!(
+            repeated,
+            Err(TransactionError::DuplicateGroupIndex { index: 0 })
+        ));
+    }
+}
@@ -19,6 +19,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let from = Account::from_mnemonic("fire enlist diesel stamp nuclear chunk student stumble call snow flock brush example slab guide choice option recall south kangaroo hundred matrix school above zero")?;
     let to = Account::from_mnemonic("since during average anxiety protect cherry club long lawsuit loan expand embark forum theory winter park twenty ball kangaroo cram burst board host ability left")?;
 
+    // Offline, e.g. with a known round: SuggestedTransactionParams::testnet(Round(first_valid))
     let params = algod.suggested_transaction_params().await?;
 
     let t = TxnBuilder::with(
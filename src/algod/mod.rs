@@ -1,4 +1,7 @@
-use algonaut_client::{token::ApiToken, Headers};
+use algonaut_client::{pool::PoolConfig, token::ApiToken, Headers};
+use std::borrow::Cow;
+use std::path::Path;
+use std::time::Duration;
 
 use crate::error::AlgonautError;
 
@@ -22,8 +25,12 @@ pub mod v2;
 /// }
 /// ```
 pub struct AlgodBuilder<'a> {
-    url: Option<&'a str>,
-    token: Option<&'a str>,
+    url: Option<Cow<'a, str>>,
+    token: Option<Cow<'a, str>>,
+    pool: PoolConfig,
+    gzip_request: bool,
+    long_poll_retries: Option<u32>,
+    idempotent_broadcasts: Option<Duration>,
 }
 
 impl<'a> AlgodBuilder<'a> {
@@ -32,15 +39,76 @@ impl<'a> AlgodBuilder<'a> {
         Self::default()
     }
 
+    /// Start the creation of a client for a local node, reading its URL and token from the
+    /// `algod.net` and `algod.token` files of its data directory, like `goal` does.
+    pub fn from_data_dir<P: AsRef<Path>>(path: P) -> Result<Self, AlgonautError> {
+        let read = |file: &str| {
+            std::fs::read_to_string(path.as_ref().join(file))
+                .map(|content| content.trim().to_owned())
+                .map_err(|e| AlgonautError::DataDir(format!("{}: {}", file, e)))
+        };
+        let net = read("algod.net")?;
+        let token = read("algod.token")?;
+        Ok(AlgodBuilder {
+            // `algod.net` holds the address the node listens on, without scheme.
+            url: Some(Cow::Owned(format!("http://{}", net))),
+            token: Some(Cow::Owned(token)),
+            ..Self::default()
+        })
+    }
+
     /// Bind to a URL.
     pub fn bind(mut self, url: &'a str) -> Self {
-        self.url = Some(url);
+        self.url = Some(Cow::Borrowed(url));
         self
     }
 
     /// Use a token to authenticate.
     pub fn auth(mut self, token: &'a str) -> Self {
-        self.token = Some(token);
+        self.token = Some(Cow::Borrowed(token));
+        self
+    }
+
+    /// Maximum number of idle connections kept alive per host.
+    ///
+    /// Only applies to v2 clients.
+    pub fn pool_max_idle_per_host(mut self, max_idle_per_host: usize) -> Self {
+        self.pool.max_idle_per_host = Some(max_idle_per_host);
+        self
+    }
+
+    /// How long idle connections are kept alive.
+    ///
+    /// Only applies to v2 clients.
+    pub fn pool_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.pool.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Gzip the bodies of the requests that upload data, e.g. to broadcast large application
+    /// programs. Falls back to uncompressed requests if the node doesn't support it.
+    ///
+    /// Only applies to v2 clients.
+    pub fn gzip_request(mut self, gzip_request: bool) -> Self {
+        self.gzip_request = gzip_request;
+        self
+    }
+
+    /// How many times to re-issue a wait for a round when the connection drops before the round
+    /// arrives. Defaults to 3.
+    ///
+    /// Only applies to v2 clients.
+    pub fn long_poll_retries(mut self, long_poll_retries: u32) -> Self {
+        self.long_poll_retries = Some(long_poll_retries);
+        self
+    }
+
+    /// Refuse to broadcast signed transactions already broadcast within `window`, see
+    /// [v2::Algod::idempotent_broadcasts].
+    ///
+    /// Only applies to v2 clients.
+    pub fn idempotent_broadcasts(mut self, window: Duration) -> Self {
+        self.idempotent_broadcasts = Some(window);
         self
     }
 
@@ -49,9 +117,12 @@ impl<'a> AlgodBuilder<'a> {
     /// Returns an error if url or token is not set or has an invalid format.
     pub fn build_v1(self) -> Result<v1::Algod, AlgonautError> {
         match (self.url, self.token) {
-            (Some(url), Some(token)) => Ok(v1::Algod::new(
-                algonaut_client::algod::v1::Client::new(url, &ApiToken::parse(token)?.to_string())?,
-            )),
+            (Some(url), Some(token)) => {
+                Ok(v1::Algod::new(algonaut_client::algod::v1::Client::new(
+                    &url,
+                    &ApiToken::parse(&token)?.to_string(),
+                )?))
+            }
             (None, Some(_)) => Err(AlgonautError::UnitializedUrl),
             (Some(_), None) => Err(AlgonautError::UnitializedToken),
             (None, None) => Err(AlgonautError::UnitializedUrl),
@@ -64,10 +135,20 @@ impl<'a> AlgodBuilder<'a> {
     pub fn build_v2(self) -> Result<v2::Algod, AlgonautError> {
         match (self.url, self.token) {
             (Some(url), Some(token)) => {
-                Ok(v2::Algod::new(algonaut_client::algod::v2::Client::new(
-                    url,
-                    vec![("X-Algo-API-Token", &ApiToken::parse(token)?.to_string())],
-                )?))
+                let mut client = algonaut_client::algod::v2::Client::with_pool_config(
+                    &url,
+                    vec![("X-Algo-API-Token", &ApiToken::parse(&token)?.to_string())],
+                    &self.pool,
+                )?
+                .gzip_request(self.gzip_request);
+                if let Some(retries) = self.long_poll_retries {
+                    client = client.long_poll_retries(retries);
+                }
+                let algod = v2::Algod::new(client);
+                Ok(match self.idempotent_broadcasts {
+                    Some(window) => algod.idempotent_broadcasts(window),
+                    None => algod,
+                })
             }
             (None, Some(_)) => Err(AlgonautError::UnitializedUrl),
             (Some(_), None) => Err(AlgonautError::UnitializedToken),
@@ -81,6 +162,10 @@ impl<'a> Default for AlgodBuilder<'a> {
         AlgodBuilder {
             url: None,
             token: None,
+            pool: PoolConfig::default(),
+            gzip_request: false,
+            long_poll_retries: None,
+            idempotent_broadcasts: None,
         }
     }
 }
@@ -134,6 +219,7 @@ impl<'a> Default for AlgodCustomEndpointBuilder<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use algonaut_client::test_utils::MockServer;
 
     #[test]
     fn test_valid_client_builder() {
@@ -145,6 +231,21 @@ mod tests {
         assert!(algod.ok().is_some());
     }
 
+    #[tokio::test]
+    async fn test_client_builder_with_pool_settings() {
+        let server = MockServer::start(vec![("200 OK", "".to_owned())]);
+        let algod = AlgodBuilder::new()
+            .bind(&server.url)
+            .auth("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+            .pool_max_idle_per_host(4)
+            .pool_idle_timeout(Duration::from_secs(5))
+            .build_v2()
+            .unwrap();
+
+        assert!(algod.health().await.is_ok());
+        assert!(server.requests()[0].starts_with("GET /health "));
+    }
+
     #[test]
     fn test_client_builder_with_no_token() {
         let res = AlgodBuilder::new().bind("http://example.com").build_v2();
@@ -200,4 +301,43 @@ mod tests {
         assert!(res.is_err());
         assert!(res.err().unwrap() == AlgonautError::BadToken);
     }
+
+    #[tokio::test]
+    async fn test_client_builder_from_data_dir() {
+        let server = MockServer::start(vec![("200 OK", "".to_owned())]);
+        let token = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let dir = std::env::temp_dir().join(format!("algonaut-data-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("algod.net"),
+            format!(
+                "{}\n",
+                server
+                    .url
+                    .trim_start_matches("http://")
+                    .trim_end_matches('/')
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.join("algod.token"), format!("{}\n", token)).unwrap();
+
+        let builder = AlgodBuilder::from_data_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            Some(server.url.trim_end_matches('/')),
+            builder.url.as_deref()
+        );
+        assert_eq!(Some(token), builder.token.as_deref());
+        let algod = builder.build_v2().unwrap();
+        assert!(algod.health().await.is_ok());
+        assert!(server.requests()[0].contains(&format!("x-algo-api-token: {}", token)));
+    }
+
+    #[test]
+    fn test_client_builder_from_missing_data_dir() {
+        let res = AlgodBuilder::from_data_dir("/nonexistent/algod/data");
+
+        assert!(matches!(res.err().unwrap(), AlgonautError::DataDir(_)));
+    }
 }
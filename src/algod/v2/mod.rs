@@ -1,21 +1,101 @@
 use algonaut_client::algod::v2::Client;
-use algonaut_core::{Address, Round, SuggestedTransactionParams, ToMsgPack};
+use algonaut_client::error::{ClientError, RequestError, RequestErrorDetails};
+use algonaut_core::{
+    Address, Round, SuggestedTransactionParams, ToMsgPack, MAX_TX_VALIDITY_WINDOW,
+};
 use algonaut_model::algod::v2::{
     Account, Application, Block, Catchup, CompiledTealWithHash, DryrunRequest, DryrunResponse,
-    GenesisBlock, KeyRegistration, NodeStatus, PendingTransaction, PendingTransactions, Supply,
+    GenesisBlock, KeyRegistration, LedgerStateDelta, NodeInfo, NodeStatus, ParticipationKey,
+    PendingTransaction, PendingTransactions, SpecialAddresses, StateProof, Supply,
     TransactionParams, TransactionResponse, Version,
 };
-use algonaut_transaction::SignedTransaction;
+use algonaut_transaction::block::BlockTransactionsDecoder;
+use algonaut_transaction::error::TransactionError;
+use algonaut_transaction::tx_group::TxGroup;
+use algonaut_transaction::{SignedTransaction, Transaction, TransactionType, TxnBuilder};
+use futures_util::future::join_all;
+use futures_util::stream::{self, Stream, TryStreamExt};
+use std::time::Duration;
 
 use crate::error::AlgonautError;
+use recent_broadcasts::RecentBroadcasts;
+
+mod recent_broadcasts;
+
+/// Outcome of broadcasting signed transactions.
+#[derive(Debug)]
+pub struct BroadcastResult {
+    /// Id of the (first) transaction, computed client-side: known even if the node's response
+    /// omits it.
+    pub tx_id: String,
+    pub node_response: TransactionResponse,
+}
+
+/// Reconnection settings of [Algod::blocks_from].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FollowConfig {
+    /// Times a round is requested again after a failure before the stream ends with the error.
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled on each following one up to `max_backoff`.
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    /// Time after which a request to the node (including the long-poll waiting for the next
+    /// round) fails with [AlgonautError::Timeout], e.g. on a hung connection. The node answers the
+    /// long-poll after about a minute even if no round was committed.
+    pub request_timeout: Duration,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        FollowConfig {
+            max_retries: 5,
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            request_timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+/// The wait before the retry following one after `backoff`.
+fn next_backoff(backoff: Duration, max_backoff: Duration) -> Duration {
+    backoff
+        .checked_mul(2)
+        .map_or(max_backoff, |doubled| doubled.min(max_backoff))
+}
+
+/// Fails with [AlgonautError::Timeout] if `request` doesn't complete within `timeout`.
+async fn with_timeout<T>(
+    timeout: Duration,
+    request: impl std::future::Future<Output = Result<T, AlgonautError>>,
+) -> Result<T, AlgonautError> {
+    tokio::time::timeout(timeout, request)
+        .await
+        .unwrap_or(Err(AlgonautError::Timeout))
+}
 
 pub struct Algod {
     pub(crate) client: Client,
+    recent_broadcasts: Option<RecentBroadcasts>,
 }
 
 impl Algod {
     pub fn new(client: Client) -> Algod {
-        Algod { client }
+        Algod {
+            client,
+            recent_broadcasts: None,
+        }
+    }
+
+    /// Refuses to broadcast signed transactions that were already broadcast (successfully) by this
+    /// client within `window`, failing with [AlgonautError::DuplicateBroadcast], e.g. to guard
+    /// retry loops against double submissions. Use
+    /// [force_broadcast_signed_transaction](Self::force_broadcast_signed_transaction) to broadcast
+    /// a transaction again anyway.
+    ///
+    /// Raw transactions, whose ids aren't known, are not checked.
+    pub fn idempotent_broadcasts(mut self, window: Duration) -> Self {
+        self.recent_broadcasts = Some(RecentBroadcasts::new(window));
+        self
     }
 
     /// Returns the entire genesis file in json.
@@ -23,6 +103,11 @@ impl Algod {
         Ok(self.client.genesis().await?)
     }
 
+    /// The fee sink and rewards pool addresses of the node's network, from its genesis.
+    pub async fn special_addresses(&self) -> Result<SpecialAddresses, AlgonautError> {
+        Ok(self.genesis().await?.special_addresses())
+    }
+
     /// Returns Ok if healthy
     pub async fn health(&self) -> Result<(), AlgonautError> {
         Ok(self.client.health().await?)
@@ -73,11 +158,193 @@ impl Algod {
         Ok(self.client.asset_information(id).await?)
     }
 
+    /// Whether the application exists, i.e. was created and not deleted.
+    pub async fn application_exists(&self, id: u64) -> Result<bool, AlgonautError> {
+        self.exists(&format!("v2/applications/{}", id)).await
+    }
+
+    /// Whether the asset exists, i.e. was created and not destroyed.
+    pub async fn asset_exists(&self, id: u64) -> Result<bool, AlgonautError> {
+        self.exists(&format!("v2/assets/{}", id)).await
+    }
+
+    /// Whether `from` can transfer the asset to `to`: both accounts have to be opted into the
+    /// asset and neither holding frozen. Holdings of assets that are frozen by default start
+    /// frozen, until the freeze manager unfreezes them.
+    ///
+    /// Doesn't check the balance of `from`.
+    pub async fn is_transfer_allowed(
+        &self,
+        asset_id: u64,
+        from: &Address,
+        to: &Address,
+    ) -> Result<bool, AlgonautError> {
+        let holds_unfrozen = |account: &Account| {
+            account
+                .assets
+                .iter()
+                .flatten()
+                .any(|holding| holding.asset_id == asset_id && !holding.is_frozen)
+        };
+        if !holds_unfrozen(&self.account_information(from).await?) {
+            return Ok(false);
+        }
+        Ok(holds_unfrozen(&self.account_information(to).await?))
+    }
+
+    /// Whether the resource at `path` exists: `false` if the node responds "not found".
+    async fn exists(&self, path: &str) -> Result<bool, AlgonautError> {
+        match self.client.get_raw(path).await {
+            Ok(_) => Ok(true),
+            Err(ClientError::Request(RequestError {
+                details: RequestErrorDetails::Http { status: 404, .. },
+                ..
+            })) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Get the block for the given round.
     pub async fn block(&self, round: Round) -> Result<Block, AlgonautError> {
         Ok(self.client.block(round).await?)
     }
 
+    /// Get the state proof of the interval containing the given round. Returns an error if the
+    /// round isn't attested by a state proof yet.
+    pub async fn state_proof(&self, round: Round) -> Result<StateProof, AlgonautError> {
+        Ok(self.client.state_proof(round).await?)
+    }
+
+    /// Get the changes to the ledger (accounts, applications and assets) made in the given round,
+    /// e.g. to update a copy of the state incrementally.
+    ///
+    /// The node only keeps the deltas of recent rounds, and may need to be configured to serve
+    /// them.
+    pub async fn ledger_state_delta(
+        &self,
+        round: Round,
+    ) -> Result<LedgerStateDelta, AlgonautError> {
+        Ok(self.client.ledger_state_delta(round).await?)
+    }
+
+    /// Follows the chain: yields the blocks of `round` and the following rounds, as they are
+    /// committed.
+    ///
+    /// If fetching a round fails (e.g. the node restarts) or times out, it's requested again,
+    /// waiting with exponential backoff, so the stream resumes from that round without skipping
+    /// any. After `config.max_retries` failed retries of a round the stream yields the error and
+    /// ends.
+    pub fn blocks_from(
+        &self,
+        round: Round,
+        config: FollowConfig,
+    ) -> impl Stream<Item = Result<Block, AlgonautError>> + '_ {
+        stream::unfold(Some(round), move |next| async move {
+            let round = next?;
+            match self.follow_block(round, config).await {
+                Ok(block) => Some((Ok(block), Some(Round(round.0 + 1)))),
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Waits until `round` is committed and gets its block, retrying as configured on failures.
+    async fn follow_block(
+        &self,
+        round: Round,
+        config: FollowConfig,
+    ) -> Result<Block, AlgonautError> {
+        let mut retries = 0;
+        let mut backoff = config.backoff.min(config.max_backoff);
+        loop {
+            match self.try_follow_block(round, config.request_timeout).await {
+                Err(_) if retries < config.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff = next_backoff(backoff, config.max_backoff);
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn try_follow_block(
+        &self,
+        round: Round,
+        request_timeout: Duration,
+    ) -> Result<Block, AlgonautError> {
+        let previous = Round(round.0.saturating_sub(1));
+        // The long-poll may return before the round is committed: wait again.
+        while with_timeout(request_timeout, self.status_after_round(previous))
+            .await?
+            .last_round
+            < round.0
+        {}
+        with_timeout(request_timeout, self.block(round)).await
+    }
+
+    /// Streams the signed transactions of the block for the given round.
+    ///
+    /// The block is requested as msgpack and each transaction is decoded as soon as its bytes
+    /// arrive: only the transaction being decoded is buffered, not the whole block, which keeps
+    /// memory bounded for large blocks.
+    pub async fn block_transactions_stream(
+        &self,
+        round: Round,
+    ) -> Result<impl Stream<Item = Result<SignedTransaction, AlgonautError>>, AlgonautError> {
+        let body = self.client.block_msgpack(round).await?;
+        Ok(stream::try_unfold(
+            (body, BlockTransactionsDecoder::new()),
+            |(mut body, mut decoder)| async move {
+                loop {
+                    if let Some(transaction) = decoder.next_transaction()? {
+                        return Ok(Some((transaction.signed_transaction, (body, decoder))));
+                    }
+                    if decoder.is_done() {
+                        return Ok(None);
+                    }
+                    match body.next_chunk().await? {
+                        Some(chunk) => decoder.push(&chunk),
+                        None => {
+                            decoder.finish()?;
+                            return Ok(None);
+                        }
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Gets the transactions of the block for the given round that share the group of `txid`, in
+    /// block order, e.g. both legs of an atomic swap. An ungrouped transaction is returned alone.
+    pub async fn transaction_group(
+        &self,
+        round: Round,
+        txid: &str,
+    ) -> Result<Vec<SignedTransaction>, AlgonautError> {
+        let transactions = self
+            .block_transactions_stream(round)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        let group = transactions
+            .iter()
+            .find(|t| t.transaction_id == txid)
+            .ok_or_else(|| AlgonautError::TransactionNotFound {
+                txid: txid.to_owned(),
+                round,
+            })?
+            .transaction
+            .group;
+        Ok(transactions
+            .into_iter()
+            .filter(|t| match group {
+                Some(group) => t.transaction.group == Some(group),
+                None => t.transaction_id == txid,
+            })
+            .collect())
+    }
+
     /// Starts a catchpoint catchup.
     pub async fn start_catchup(&self, catchpoint: &str) -> Result<Catchup, AlgonautError> {
         Ok(self.client.start_catchup(catchpoint).await?)
@@ -112,6 +379,39 @@ impl Algod {
             .await?)
     }
 
+    /// Return a list of participation keys.
+    ///
+    /// Lists the participation keys installed on the node, with their account and validity rounds.
+    pub async fn participation_keys(&self) -> Result<Vec<ParticipationKey>, AlgonautError> {
+        Ok(self.client.participation_keys().await?)
+    }
+
+    /// Get participation key info given a participation ID.
+    pub async fn participation_key(&self, id: &str) -> Result<ParticipationKey, AlgonautError> {
+        Ok(self.client.participation_key(id).await?)
+    }
+
+    /// Append state proof keys to a participation key.
+    ///
+    /// keys: The state proof keys to add to an existing participation ID, msgpack encoded.
+    pub async fn append_participation_key_state_proof(
+        &self,
+        id: &str,
+        keys: &[u8],
+    ) -> Result<(), AlgonautError> {
+        Ok(self
+            .client
+            .append_participation_key_state_proof(id, keys)
+            .await?)
+    }
+
+    /// Delete a given participation key by ID.
+    ///
+    /// Use this to remove expired keys from the node.
+    pub async fn delete_participation_key(&self, id: &str) -> Result<(), AlgonautError> {
+        Ok(self.client.delete_participation_key(id).await?)
+    }
+
     /// Special management endpoint to shutdown the node. Optionally provide a timeout parameter
     /// to indicate that the node should begin shutting down after a number of seconds.
     pub async fn shutdown(&self, timeout: usize) -> Result<(), AlgonautError> {
@@ -123,11 +423,39 @@ impl Algod {
         Ok(self.client.status().await?)
     }
 
+    /// Whether the validity window of `txn` has passed, i.e. it can't be confirmed anymore because
+    /// the node is already at (or past) its last valid round.
+    pub async fn is_expired(&self, txn: &Transaction) -> Result<bool, AlgonautError> {
+        Ok(self.status().await?.last_round >= txn.last_valid.0)
+    }
+
     /// Gets the node status after waiting for the given round.
+    ///
+    /// If the connection drops before the round arrives, the wait is re-issued, up to
+    /// [long_poll_retries](crate::algod::AlgodBuilder::long_poll_retries) times.
     pub async fn status_after_round(&self, round: Round) -> Result<NodeStatus, AlgonautError> {
         Ok(self.client.status_after_round(round).await?)
     }
 
+    /// Gets the node status after waiting for the given round, failing with
+    /// [AlgonautError::Timeout] if it doesn't arrive within `timeout`.
+    ///
+    /// The endpoint is long-polling, so `timeout` applies to this call only, independently of the
+    /// client's settings.
+    pub async fn status_after_round_timeout(
+        &self,
+        round: Round,
+        timeout: Duration,
+    ) -> Result<NodeStatus, AlgonautError> {
+        match self.client.status_after_round_timeout(round, timeout).await {
+            Err(ClientError::Request(RequestError {
+                details: RequestErrorDetails::Timeout,
+                ..
+            })) => Err(AlgonautError::Timeout),
+            res => Ok(res?),
+        }
+    }
+
     /// Compile TEAL source code to binary, produce its hash.
     ///
     /// Given TEAL source code in plain text, return base64 encoded program bytes and base32
@@ -147,25 +475,115 @@ impl Algod {
     }
 
     /// Broadcasts a transaction to the network.
+    ///
+    /// Returns [AlgonautError::UnsignedTransaction], without broadcasting, if the transaction
+    /// isn't [signed](SignedTransaction::is_signed). This applies to all the `broadcast_signed_*`
+    /// functions, which also return the transaction id computed client-side in the
+    /// [BroadcastResult].
     pub async fn broadcast_signed_transaction(
         &self,
         txn: &SignedTransaction,
-    ) -> Result<TransactionResponse, AlgonautError> {
-        Ok(self.broadcast_raw_transaction(&txn.to_msg_pack()?).await?)
+    ) -> Result<BroadcastResult, AlgonautError> {
+        self.broadcast_recorded(&[txn], false).await
+    }
+
+    /// Broadcasts a transaction to the network, even if it was already broadcast within the
+    /// [idempotent_broadcasts](Self::idempotent_broadcasts) window.
+    pub async fn force_broadcast_signed_transaction(
+        &self,
+        txn: &SignedTransaction,
+    ) -> Result<BroadcastResult, AlgonautError> {
+        self.broadcast_recorded(&[txn], true).await
+    }
+
+    /// Broadcasts the transactions together, recording their ids if
+    /// [idempotent_broadcasts](Self::idempotent_broadcasts) is enabled.
+    async fn broadcast_recorded(
+        &self,
+        txns: &[&SignedTransaction],
+        force: bool,
+    ) -> Result<BroadcastResult, AlgonautError> {
+        if let Some(unsigned) = txns.iter().find(|t| !t.is_signed()) {
+            return Err(AlgonautError::UnsignedTransaction {
+                txid: unsigned.transaction_id.clone(),
+            });
+        }
+        let txids: Vec<&str> = txns.iter().map(|t| t.transaction_id.as_str()).collect();
+        let mut bytes = vec![];
+        for t in txns {
+            bytes.push(t.to_msg_pack()?);
+        }
+        if let Some(recent) = &self.recent_broadcasts {
+            recent.reserve(&txids, force)?;
+        }
+        let node_response = match self.broadcast_raw_transaction(&bytes.concat()).await {
+            Ok(response) => response,
+            Err(e) => {
+                if let Some(recent) = &self.recent_broadcasts {
+                    recent.release(&txids)?;
+                }
+                return Err(e);
+            }
+        };
+        Ok(BroadcastResult {
+            tx_id: txids.first().map(|id| id.to_string()).unwrap_or_default(),
+            node_response,
+        })
+    }
+
+    /// Broadcasts a transaction to the network, after verifying that it was built for the node's
+    /// network.
+    ///
+    /// Returns [AlgonautError::GenesisMismatch] if the transaction's genesis hash doesn't match the
+    /// node's, e.g. when using suggested params cached from a node in a different network.
+    pub async fn submit_checked(
+        &self,
+        txn: &SignedTransaction,
+    ) -> Result<BroadcastResult, AlgonautError> {
+        let node_genesis_hash = self.versions().await?.genesis_hash_b64;
+        if txn.transaction.genesis_hash != node_genesis_hash {
+            return Err(AlgonautError::GenesisMismatch {
+                transaction: txn.transaction.genesis_hash,
+                node: node_genesis_hash,
+            });
+        }
+        self.broadcast_signed_transaction(txn).await
     }
 
     /// Broadcasts a transaction group to the network.
     ///
-    /// Atomic if the transactions share a [group](algonaut_transaction::transaction::Transaction::group)
+    /// The transactions must share a [group](algonaut_transaction::transaction::Transaction::group)
+    /// id, which a single transaction may also not have: returns [AlgonautError::InconsistentGroup]
+    /// otherwise, without broadcasting. To submit unrelated transactions and get an outcome per
+    /// transaction, use [broadcast_independent](Self::broadcast_independent).
     pub async fn broadcast_signed_transactions(
         &self,
         txns: &[SignedTransaction],
-    ) -> Result<TransactionResponse, AlgonautError> {
-        let mut bytes = vec![];
-        for t in txns {
-            bytes.push(t.to_msg_pack()?);
+    ) -> Result<BroadcastResult, AlgonautError> {
+        // A single ungrouped transaction is submitted alone.
+        if txns.len() > 1 || txns.iter().any(|t| t.transaction.group.is_some()) {
+            TxGroup::check_signed_group(txns).map_err(|e| match e {
+                TransactionError::MissingGroupId | TransactionError::MismatchingGroupIds => {
+                    AlgonautError::InconsistentGroup
+                }
+                e => e.into(),
+            })?;
         }
-        Ok(self.broadcast_raw_transaction(&bytes.concat()).await?)
+        self.broadcast_recorded(&txns.iter().collect::<Vec<_>>(), false)
+            .await
+    }
+
+    /// Broadcasts each transaction separately (concurrently), returning the outcome of each
+    /// submission, in order.
+    ///
+    /// Unlike [broadcast_signed_transactions](Self::broadcast_signed_transactions), this is not
+    /// atomic: some transactions may be accepted while others are rejected. Not suitable for
+    /// grouped transactions, which the node only accepts together.
+    pub async fn broadcast_independent(
+        &self,
+        txns: &[SignedTransaction],
+    ) -> Vec<Result<BroadcastResult, AlgonautError>> {
+        join_all(txns.iter().map(|t| self.broadcast_signed_transaction(t))).await
     }
 
     /// Broadcasts raw transactions to the network.
@@ -188,6 +606,17 @@ impl Algod {
         Ok(self.client.transaction_params().await?)
     }
 
+    /// Builds a transaction with freshly fetched [suggested params](Self::suggested_transaction_params),
+    /// for the common case of sending it right away. To build transactions offline, or to add a
+    /// note, lease... use [TxnBuilder](algonaut_transaction::TxnBuilder) with explicit params.
+    pub async fn build_transaction(
+        &self,
+        txn_type: TransactionType,
+    ) -> Result<Transaction, AlgonautError> {
+        let params = self.suggested_transaction_params().await?;
+        Ok(TxnBuilder::with(params, txn_type).try_build()?)
+    }
+
     /// Get suggested parameters for constructing a new transaction.
     pub async fn suggested_transaction_params(
         &self,
@@ -200,7 +629,7 @@ impl Algod {
             fee: params.fee,
             min_fee: params.min_fee,
             first_valid: params.last_round,
-            last_valid: params.last_round + 1000,
+            last_valid: params.last_round + MAX_TX_VALIDITY_WINDOW,
         })
     }
 
@@ -232,8 +661,833 @@ impl Algod {
         Ok(self.client.pending_transaction_with_id(txid).await?)
     }
 
+    /// Waits until the transaction is confirmed.
+    ///
+    /// Fails with [AlgonautError::TransactionRejected] as soon as the node drops it from its pool
+    /// (e.g. overspend), or with [AlgonautError::ConfirmationTimeout] if it isn't confirmed
+    /// `timeout_rounds` rounds after the current one.
+    pub async fn wait_for_confirmation(
+        &self,
+        txid: &str,
+        timeout_rounds: u64,
+    ) -> Result<PendingTransaction, AlgonautError> {
+        let mut confirmed = self
+            .wait_for_group(&[txid.to_owned()], timeout_rounds)
+            .await?;
+        Ok(confirmed.remove(0))
+    }
+
+    /// Waits until all the transactions are confirmed, returning them in the order of `txids`.
+    ///
+    /// The transactions of an atomic group are confirmed in the same round. Fails with
+    /// [AlgonautError::TransactionRejected] if the node drops any of them from its pool, or with
+    /// [AlgonautError::ConfirmationTimeout] if they aren't all confirmed `timeout_rounds` rounds
+    /// after the current one.
+    pub async fn wait_for_group(
+        &self,
+        txids: &[String],
+        timeout_rounds: u64,
+    ) -> Result<Vec<PendingTransaction>, AlgonautError> {
+        let mut round = self.status().await?.last_round;
+        let last_round = round + timeout_rounds;
+        let mut confirmed: Vec<Option<PendingTransaction>> = txids.iter().map(|_| None).collect();
+        loop {
+            for (txid, confirmed) in txids.iter().zip(confirmed.iter_mut()) {
+                if confirmed.is_some() {
+                    continue;
+                }
+                let pending = self.pending_transaction_with_id(txid).await?;
+                if pending.confirmed_round.is_some() {
+                    *confirmed = Some(pending);
+                } else if let Some(pool_error) = pending.pool_error() {
+                    return Err(AlgonautError::TransactionRejected {
+                        txid: txid.clone(),
+                        message: pool_error.to_owned(),
+                    });
+                }
+            }
+            if confirmed.iter().all(Option::is_some) {
+                return Ok(confirmed.into_iter().flatten().collect());
+            }
+            if round >= last_round {
+                return Err(AlgonautError::ConfirmationTimeout {
+                    rounds: timeout_rounds,
+                });
+            }
+            round = self.status_after_round(Round(round)).await?.last_round;
+        }
+    }
+
     /// Retrieves the current version
     pub async fn versions(&self) -> Result<Version, AlgonautError> {
         Ok(self.client.versions().await?)
     }
+
+    /// Gets an arbitrary (authenticated) path, e.g. "/v2/status", returning the raw response
+    /// body. An escape hatch for endpoints that aren't wrapped yet.
+    pub async fn get_raw(&self, path: &str) -> Result<Vec<u8>, AlgonautError> {
+        Ok(self.client.get_raw(path).await?)
+    }
+
+    /// Posts `body` to an arbitrary (authenticated) path, returning the raw response body.
+    pub async fn post_raw(&self, path: &str, body: Vec<u8>) -> Result<Vec<u8>, AlgonautError> {
+        Ok(self.client.post_raw(path, body).await?)
+    }
+
+    /// The node's build version, genesis and supported API versions, in one call.
+    pub async fn node_info(&self) -> Result<NodeInfo, AlgonautError> {
+        Ok(self.versions().await?.node_info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_client::test_utils::{
+        account_response, block_response, params_response, pending_response, stalled_server,
+        status_response, versions_response, MockServer,
+    };
+    use algonaut_core::MicroAlgos;
+    use algonaut_crypto::{HashDigest, Signature};
+    use algonaut_transaction::transaction::TransactionSignature;
+    use algonaut_transaction::tx_group::TxGroup;
+    use algonaut_transaction::{account::Account, Pay, TransferAsset};
+    use data_encoding::BASE64;
+    use futures_util::StreamExt;
+    use serde::Serialize;
+    use std::convert::TryInto;
+
+    const MAINNET_GENESIS_HASH: &str = "wGHE2Pwdvd7S12BL5FaOP20EGYesN73ktiC1qzkkit8=";
+    const TESTNET_GENESIS_HASH: &str = "SGO1GKSzyE7IEPItTxCByw9x8FmnrCDexi9/cOUJOiI=";
+
+    fn signed_payment(genesis_hash: &str) -> SignedTransaction {
+        let account = Account::generate();
+        let genesis_hash = HashDigest(
+            BASE64
+                .decode(genesis_hash.as_bytes())
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let t = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            genesis_hash,
+            Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        account.sign_transaction(&t).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_submit_checked_rejects_other_network() {
+        let server = MockServer::start(vec![("200 OK", versions_response(MAINNET_GENESIS_HASH))]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod
+            .submit_checked(&signed_payment(TESTNET_GENESIS_HASH))
+            .await;
+
+        assert!(matches!(res, Err(AlgonautError::GenesisMismatch { .. })));
+        // the transaction was not broadcast
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_submit_checked_submits_same_network() {
+        let server = MockServer::start(vec![
+            ("200 OK", versions_response(MAINNET_GENESIS_HASH)),
+            ("200 OK", r#"{"txId": "TXID"}"#.to_owned()),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod
+            .submit_checked(&signed_payment(MAINNET_GENESIS_HASH))
+            .await;
+
+        assert_eq!("TXID", res.unwrap().node_response.tx_id);
+        let requests = server.requests();
+        assert!(requests[1].starts_with("POST /v2/transactions "));
+    }
+
+    #[tokio::test]
+    async fn test_get_raw_returns_undecoded_body() {
+        let status = status_response(10);
+        let server =
+            MockServer::start(vec![("200 OK", status.clone()), ("200 OK", status.clone())]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let raw = algod.get_raw("/v2/status").await.unwrap();
+        let typed = algod.status().await.unwrap();
+
+        assert_eq!(status.as_bytes(), raw.as_slice());
+        assert_eq!(10, typed.last_round);
+        assert!(server.requests()[0].starts_with("GET /v2/status "));
+    }
+
+    #[tokio::test]
+    async fn test_post_raw() {
+        let server = MockServer::start(vec![("200 OK", "{}".to_owned())]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod.post_raw("/v2/teal/compile", b"int 1".to_vec()).await;
+
+        assert_eq!(b"{}".to_vec(), res.unwrap());
+        let request = &server.requests()[0];
+        assert!(request.starts_with("POST /v2/teal/compile "));
+        assert!(request.ends_with("int 1"));
+    }
+
+    #[tokio::test]
+    async fn test_append_participation_key_state_proof() {
+        let server = MockServer::start(vec![("200 OK", "".to_owned())]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+        let keys = vec![0x91, 0x81, 0xa1, 0x6b, 0x01];
+
+        let res = algod
+            .append_participation_key_state_proof("KEYID", &keys)
+            .await;
+
+        assert!(res.is_ok());
+        let requests = server.requests_with_bodies();
+        assert!(requests[0].0.starts_with("POST /v2/participation/KEYID "));
+        assert!(requests[0]
+            .0
+            .to_lowercase()
+            .contains("content-type: application/msgpack"));
+        assert_eq!(keys, requests[0].1);
+    }
+
+    fn grouped_payments(account: &Account, count: u64) -> Vec<SignedTransaction> {
+        let mut txns: Vec<Transaction> = (0..count)
+            .map(|amount| {
+                TxnBuilder::new(
+                    MicroAlgos(1000),
+                    Round(1),
+                    Round(1001),
+                    HashDigest([1; 32]),
+                    Pay::new(account.address(), account.address(), MicroAlgos(amount)).build(),
+                )
+                .build()
+            })
+            .collect();
+        TxGroup::assign_group_id(txns.iter_mut().collect()).unwrap();
+        txns.iter()
+            .map(|t| account.sign_transaction(t).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed_transactions_group() {
+        let server = MockServer::start(vec![("200 OK", r#"{"txId": "TXID"}"#.to_owned())]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod
+            .broadcast_signed_transactions(&grouped_payments(&Account::generate(), 2))
+            .await;
+
+        assert_eq!("TXID", res.unwrap().node_response.tx_id);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed_transactions_single_ungrouped() {
+        let server = MockServer::start(vec![("200 OK", r#"{"txId": "TXID"}"#.to_owned())]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod
+            .broadcast_signed_transactions(&[signed_payment(MAINNET_GENESIS_HASH)])
+            .await;
+
+        assert_eq!("TXID", res.unwrap().node_response.tx_id);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed_transactions_rejects_inconsistent_group() {
+        let algod = Algod::new(Client::new(&stalled_server(), vec![]).unwrap());
+        let account = Account::generate();
+        let mut other_group = grouped_payments(&account, 2);
+        let ungrouped = signed_payment(MAINNET_GENESIS_HASH);
+
+        for txns in [
+            vec![
+                other_group.remove(0),
+                grouped_payments(&account, 3).remove(0),
+            ],
+            vec![ungrouped.clone(), ungrouped],
+        ] {
+            let res = algod.broadcast_signed_transactions(&txns).await;
+
+            assert_eq!(Err(AlgonautError::InconsistentGroup), res.map(|_| ()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_broadcasts_suppress_duplicates() {
+        let server = MockServer::start_with(3, |_| ("200 OK", r#"{"txId": "TXID"}"#.to_owned()));
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap())
+            .idempotent_broadcasts(Duration::from_secs(60));
+        let txn = signed_payment(MAINNET_GENESIS_HASH);
+
+        assert!(algod.broadcast_signed_transaction(&txn).await.is_ok());
+        assert_eq!(
+            Err(AlgonautError::DuplicateBroadcast {
+                txid: txn.transaction_id.clone()
+            }),
+            algod.broadcast_signed_transaction(&txn).await.map(|_| ())
+        );
+        assert!(algod
+            .broadcast_signed_transactions(std::slice::from_ref(&txn))
+            .await
+            .is_err());
+        assert!(algod.force_broadcast_signed_transaction(&txn).await.is_ok());
+        assert!(algod
+            .broadcast_signed_transaction(&signed_payment(TESTNET_GENESIS_HASH))
+            .await
+            .is_ok());
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_broadcasts_concurrent_duplicates() {
+        let server = MockServer::start_with(1, |_| ("200 OK", r#"{"txId": "TXID"}"#.to_owned()));
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap())
+            .idempotent_broadcasts(Duration::from_secs(60));
+        let txn = signed_payment(MAINNET_GENESIS_HASH);
+
+        let results = join_all(vec![
+            algod.broadcast_signed_transaction(&txn),
+            algod.broadcast_signed_transaction(&txn),
+        ])
+        .await;
+
+        assert_eq!(1, results.iter().filter(|res| res.is_ok()).count());
+        assert!(results.iter().any(|res| matches!(
+            res,
+            Err(AlgonautError::DuplicateBroadcast { txid }) if *txid == txn.transaction_id
+        )));
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_broadcasts_release_failed() {
+        let server = MockServer::start(vec![
+            ("500 Internal Server Error", "".to_owned()),
+            ("200 OK", r#"{"txId": "TXID"}"#.to_owned()),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap())
+            .idempotent_broadcasts(Duration::from_secs(60));
+        let txn = signed_payment(MAINNET_GENESIS_HASH);
+
+        assert!(algod.broadcast_signed_transaction(&txn).await.is_err());
+        assert!(algod.broadcast_signed_transaction(&txn).await.is_ok());
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_exists() {
+        let server = MockServer::start(vec![
+            ("200 OK", r#"{"index": 5, "params": {}}"#.to_owned()),
+            (
+                "404 Not Found",
+                r#"{"message": "asset does not exist"}"#.to_owned(),
+            ),
+            (
+                "404 Not Found",
+                r#"{"message": "application does not exist"}"#.to_owned(),
+            ),
+            (
+                "500 Internal Server Error",
+                r#"{"message": "failed"}"#.to_owned(),
+            ),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        assert_eq!(Ok(true), algod.asset_exists(5).await);
+        assert_eq!(Ok(false), algod.asset_exists(6).await);
+        assert_eq!(Ok(false), algod.application_exists(7).await);
+        assert!(matches!(
+            algod.application_exists(8).await,
+            Err(AlgonautError::Request(_))
+        ));
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /v2/assets/5 "));
+        assert!(requests[2].starts_with("GET /v2/applications/7 "));
+    }
+
+    #[tokio::test]
+    async fn test_build_transaction() {
+        let server = MockServer::start(vec![(
+            "200 OK",
+            params_response("mainnet-v1.0", MAINNET_GENESIS_HASH, 100),
+        )]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+        let address = Address([1; 32]);
+
+        let txn = algod
+            .build_transaction(Pay::new(address, address, MicroAlgos(5)).build())
+            .await
+            .unwrap();
+
+        assert!(server.requests()[0].starts_with("GET /v2/transactions/params "));
+        assert_eq!(MicroAlgos(1000), txn.fee);
+        assert_eq!(Round(100), txn.first_valid);
+        assert_eq!(Round(1_100), txn.last_valid);
+        assert_eq!(Some("mainnet-v1.0".to_owned()), txn.genesis_id);
+        assert_eq!(MAINNET_GENESIS_HASH, BASE64.encode(&txn.genesis_hash.0));
+    }
+
+    #[tokio::test]
+    async fn test_status_after_round_timeout() {
+        let algod = Algod::new(Client::new(&stalled_server(), vec![]).unwrap());
+
+        let res = algod
+            .status_after_round_timeout(Round(1), Duration::from_millis(100))
+            .await;
+
+        assert_eq!(Err(AlgonautError::Timeout), res.map(|_| ()));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_independent_reports_each_outcome() {
+        let account = Account::generate();
+        let txns: Vec<SignedTransaction> = ["accept", "reject", "accept"]
+            .iter()
+            .map(|note| {
+                let t = TxnBuilder::new(
+                    MicroAlgos(1000),
+                    Round(1),
+                    Round(1001),
+                    HashDigest([1; 32]),
+                    Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+                )
+                .note(note.as_bytes().to_vec())
+                .build();
+                account.sign_transaction(&t).unwrap()
+            })
+            .collect();
+        let server = MockServer::start_with(3, |request| {
+            if request.contains("reject") {
+                ("400 Bad Request", r#"{"message": "overspend"}"#.to_owned())
+            } else {
+                ("200 OK", r#"{"txId": "TXID"}"#.to_owned())
+            }
+        });
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod.broadcast_independent(&txns).await;
+
+        assert_eq!(3, res.len());
+        assert!(res[0].is_ok());
+        assert!(matches!(
+            res[1],
+            Err(AlgonautError::Request(crate::error::RequestError {
+                details: crate::error::RequestErrorDetails::Http { status: 400, .. },
+                ..
+            }))
+        ));
+        assert!(res[2].is_ok());
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_special_addresses() {
+        let genesis = r#"{
+            "alloc": [],
+            "fees": "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE",
+            "id": "v1.0",
+            "network": "testnet",
+            "proto": "future",
+            "rwd": "7777777777777777777777777777777777777777777777777774MSJUVU"
+        }"#;
+        let server = MockServer::start(vec![("200 OK", genesis.to_owned())]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let addresses = algod.special_addresses().await.unwrap();
+
+        assert_eq!(
+            "A7NMWS3NT3IUDMLVO26ULGXGIIOUQ3ND2TXSER6EBGRZNOBOUIQXHIBGDE",
+            addresses.fee_sink.to_string()
+        );
+        assert_eq!(
+            "7777777777777777777777777777777777777777777777777774MSJUVU",
+            addresses.rewards_pool.to_string()
+        );
+        assert!(server.requests()[0].starts_with("GET /genesis "));
+    }
+
+    #[tokio::test]
+    async fn test_is_expired() {
+        let server = MockServer::start_with(3, |_| ("200 OK", status_response(1_001)));
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+        let txn = |last_valid| {
+            TxnBuilder::new(
+                MicroAlgos(1000),
+                Round(1),
+                Round(last_valid),
+                HashDigest([1; 32]),
+                Pay::new(Address([1; 32]), Address([1; 32]), MicroAlgos(1)).build(),
+            )
+            .build()
+        };
+
+        assert!(algod.is_expired(&txn(1_000)).await.unwrap());
+        assert!(algod.is_expired(&txn(1_001)).await.unwrap());
+        assert!(!algod.is_expired(&txn(1_002)).await.unwrap());
+        assert!(server.requests()[0].starts_with("GET /v2/status "));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_group_returns_confirmed_in_order() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(10)),
+            ("200 OK", pending_response(None, "")),
+            ("200 OK", pending_response(None, "")),
+            ("200 OK", status_response(11)),
+            ("200 OK", pending_response(Some(11), "")),
+            ("200 OK", pending_response(Some(11), "")),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod
+            .wait_for_group(&["TX1".to_owned(), "TX2".to_owned()], 5)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![Some(11), Some(11)],
+            res.iter().map(|t| t.confirmed_round).collect::<Vec<_>>()
+        );
+        let requests = server.requests();
+        assert!(requests[1].starts_with("GET /v2/transactions/pending/TX1 "));
+        assert!(requests[2].starts_with("GET /v2/transactions/pending/TX2 "));
+        assert!(requests[3].starts_with("GET /v2/status/wait-for-block-after/10 "));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_group_fails_on_pool_error() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(10)),
+            ("200 OK", pending_response(None, "")),
+            ("200 OK", pending_response(None, "overspend")),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod
+            .wait_for_group(&["TX1".to_owned(), "TX2".to_owned()], 5)
+            .await;
+
+        assert_eq!(
+            Err(AlgonautError::TransactionRejected {
+                txid: "TX2".to_owned(),
+                message: "overspend".to_owned()
+            }),
+            res.map(|_| ())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation_ends_on_pool_error() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(10)),
+            ("200 OK", pending_response(None, "overspend")),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod.wait_for_confirmation("TX1", 1_000).await;
+
+        assert_eq!(
+            Err(AlgonautError::TransactionRejected {
+                txid: "TX1".to_owned(),
+                message: "overspend".to_owned()
+            }),
+            res.map(|_| ())
+        );
+        assert_eq!(2, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(10)),
+            ("200 OK", pending_response(None, "")),
+            ("200 OK", status_response(11)),
+            ("200 OK", pending_response(Some(11), "")),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod.wait_for_confirmation("TX1", 5).await;
+
+        assert_eq!(Some(11), res.unwrap().confirmed_round);
+    }
+
+    #[derive(Serialize)]
+    struct TestBlock {
+        block: TestBlockHeader,
+    }
+
+    #[derive(Serialize)]
+    struct TestBlockHeader {
+        gen: String,
+        gh: HashDigest,
+        rnd: u64,
+        txns: Vec<SignedTransaction>,
+    }
+
+    #[tokio::test]
+    async fn test_transaction_group_returns_swap_legs() {
+        let alice = Account::generate();
+        let bob = Account::generate();
+        let payment = |from: &Account, to: &Account, amount| {
+            TxnBuilder::new(
+                MicroAlgos(1000),
+                Round(1),
+                Round(1001),
+                HashDigest([1; 32]),
+                Pay::new(from.address(), to.address(), MicroAlgos(amount)).build(),
+            )
+            .build()
+        };
+        let unrelated = payment(&alice, &alice, 1);
+        let mut leg1 = payment(&alice, &bob, 10);
+        let mut leg2 = TxnBuilder::new(
+            MicroAlgos(1000),
+            Round(1),
+            Round(1001),
+            HashDigest([1; 32]),
+            TransferAsset::new(bob.address(), 5, 1, alice.address()).build(),
+        )
+        .build();
+        TxGroup::assign_group_id(vec![&mut leg1, &mut leg2]).unwrap();
+        let txns = vec![
+            alice.sign_transaction(&unrelated).unwrap(),
+            alice.sign_transaction(&leg1).unwrap(),
+            bob.sign_transaction(&leg2).unwrap(),
+        ];
+        let block = rmp_serde::to_vec_named(&TestBlock {
+            block: TestBlockHeader {
+                gen: "testnet-v1.0".to_owned(),
+                gh: HashDigest([1; 32]),
+                rnd: 7,
+                txns: txns.clone(),
+            },
+        })
+        .unwrap();
+        let server = MockServer::start_msgpack(vec![("200 OK", block)]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let group = algod
+            .transaction_group(Round(7), &txns[2].transaction_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![&txns[1].transaction_id, &txns[2].transaction_id],
+            group.iter().map(|t| &t.transaction_id).collect::<Vec<_>>()
+        );
+        assert!(server.requests()[0].starts_with("GET /v2/blocks/7?format=msgpack "));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_returns_client_computed_tx_id() {
+        let txn = signed_payment(MAINNET_GENESIS_HASH);
+        let server = MockServer::start(vec![
+            ("200 OK", format!(r#"{{"txId": "{}"}}"#, txn.transaction_id)),
+            ("200 OK", "{}".to_owned()),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod.broadcast_signed_transaction(&txn).await.unwrap();
+        let without_node_id = algod
+            .force_broadcast_signed_transaction(&txn)
+            .await
+            .unwrap();
+
+        assert_eq!(txn.transaction.id().unwrap(), res.tx_id);
+        assert_eq!(res.node_response.tx_id, res.tx_id);
+        assert_eq!(txn.transaction_id, without_node_id.tx_id);
+        assert_eq!("", without_node_id.node_response.tx_id);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_rejects_unsigned_transaction() {
+        let signed = signed_payment(TESTNET_GENESIS_HASH);
+        let unsigned = SignedTransaction {
+            sig: TransactionSignature::Single(Signature([0; 64])),
+            ..signed
+        };
+        let server = MockServer::start(vec![]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = algod.broadcast_signed_transaction(&unsigned).await;
+
+        assert_eq!(
+            Err(AlgonautError::UnsignedTransaction {
+                txid: unsigned.transaction_id.clone()
+            }),
+            res.map(|_| ())
+        );
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blocks_from_resumes_after_failure() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(5)),
+            ("200 OK", block_response(5)),
+            ("503 Service Unavailable", "".to_owned()),
+            ("200 OK", status_response(6)),
+            ("200 OK", block_response(6)),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+        let config = FollowConfig {
+            max_retries: 1,
+            backoff: Duration::from_millis(1),
+            ..FollowConfig::default()
+        };
+
+        let blocks: Vec<_> = algod.blocks_from(Round(5), config).take(2).collect().await;
+
+        assert_eq!(
+            vec![5, 6],
+            blocks
+                .into_iter()
+                .map(|b| b.unwrap().block.rnd)
+                .collect::<Vec<_>>()
+        );
+        let requests = server.requests();
+        assert!(requests[1].starts_with("GET /v2/blocks/5 "));
+        assert!(requests[2].starts_with("GET /v2/status/wait-for-block-after/5 "));
+        assert!(requests[3].starts_with("GET /v2/status/wait-for-block-after/5 "));
+        assert!(requests[4].starts_with("GET /v2/blocks/6 "));
+    }
+
+    #[tokio::test]
+    async fn test_blocks_from_ends_after_max_retries() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(5)),
+            ("200 OK", block_response(5)),
+            ("503 Service Unavailable", "".to_owned()),
+            ("503 Service Unavailable", "".to_owned()),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+        let config = FollowConfig {
+            max_retries: 1,
+            backoff: Duration::from_millis(1),
+            ..FollowConfig::default()
+        };
+
+        let blocks: Vec<_> = algod.blocks_from(Round(5), config).collect().await;
+
+        assert_eq!(2, blocks.len());
+        assert!(blocks[0].is_ok());
+        assert!(blocks[1].is_err());
+        assert_eq!(4, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_blocks_from_waits_until_round_is_committed() {
+        let server = MockServer::start(vec![
+            ("200 OK", status_response(4)),
+            ("200 OK", status_response(5)),
+            ("200 OK", block_response(5)),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+        let config = FollowConfig {
+            max_retries: 0,
+            ..FollowConfig::default()
+        };
+
+        let blocks: Vec<_> = algod.blocks_from(Round(5), config).take(1).collect().await;
+
+        assert_eq!(5, blocks[0].as_ref().unwrap().block.rnd);
+        assert_eq!(3, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_blocks_from_times_out_hung_requests() {
+        let algod = Algod::new(Client::new(&stalled_server(), vec![]).unwrap());
+        let config = FollowConfig {
+            max_retries: 1,
+            backoff: Duration::from_millis(1),
+            request_timeout: Duration::from_millis(50),
+            ..FollowConfig::default()
+        };
+
+        let blocks: Vec<_> = algod.blocks_from(Round(5), config).collect().await;
+
+        assert_eq!(
+            vec![Err(AlgonautError::Timeout)],
+            blocks
+                .into_iter()
+                .map(|b| b.map(|_| ()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_next_backoff_is_capped() {
+        let max = Duration::from_secs(60);
+
+        assert_eq!(
+            Duration::from_secs(2),
+            next_backoff(Duration::from_secs(1), max)
+        );
+        assert_eq!(max, next_backoff(Duration::from_secs(40), max));
+        assert_eq!(max, next_backoff(Duration::MAX, max));
+    }
+
+    #[tokio::test]
+    async fn test_ledger_state_delta() {
+        let server = MockServer::start(vec![(
+            "200 OK",
+            r#"{"Accts": {"Accts": null}, "PrevTimestamp": 1678886400}"#.to_owned(),
+        )]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let delta = algod.ledger_state_delta(Round(10)).await.unwrap();
+
+        assert_eq!(1_678_886_400, delta.prev_timestamp);
+        assert!(server.requests()[0].starts_with("GET /v2/deltas/10 "));
+    }
+
+    #[tokio::test]
+    async fn test_is_transfer_allowed() {
+        let from = Address([1; 32]);
+        let to = Address([2; 32]);
+        let server = MockServer::start(vec![
+            ("200 OK", account_response(&from, &[(3, true), (5, false)])),
+            ("200 OK", account_response(&to, &[(5, false)])),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        assert!(algod.is_transfer_allowed(5, &from, &to).await.unwrap());
+        let requests = server.requests();
+        assert!(requests[0].starts_with(&format!("GET /v2/accounts/{} ", from.to_string())));
+        assert!(requests[1].starts_with(&format!("GET /v2/accounts/{} ", to.to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_is_transfer_allowed_frozen_sender() {
+        let from = Address([1; 32]);
+        let to = Address([2; 32]);
+        let server = MockServer::start(vec![("200 OK", account_response(&from, &[(5, true)]))]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        assert!(!algod.is_transfer_allowed(5, &from, &to).await.unwrap());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[tokio::test]
+    async fn test_is_transfer_allowed_receiver_not_opted_in() {
+        let from = Address([1; 32]);
+        let to = Address([2; 32]);
+        let server = MockServer::start(vec![
+            ("200 OK", account_response(&from, &[(5, false)])),
+            ("200 OK", account_response(&to, &[(3, false)])),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        assert!(!algod.is_transfer_allowed(5, &from, &to).await.unwrap());
+    }
 }
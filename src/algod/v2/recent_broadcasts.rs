@@ -0,0 +1,123 @@
+use crate::error::AlgonautError;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Max number of remembered transaction ids: the oldest are forgotten first.
+const CAPACITY: usize = 1_024;
+
+/// The ids of the transactions broadcast within a time window, to refuse broadcasting them again.
+pub(crate) struct RecentBroadcasts {
+    window: Duration,
+    txids: Mutex<VecDeque<(String, Instant)>>,
+}
+
+impl RecentBroadcasts {
+    pub(crate) fn new(window: Duration) -> Self {
+        RecentBroadcasts {
+            window,
+            txids: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Records `txids` as broadcast, failing with [AlgonautError::DuplicateBroadcast] (and
+    /// recording none) if any of them was broadcast within the window, unless `force`.
+    ///
+    /// Checking and recording happen under one lock, so of concurrent broadcasts of a transaction
+    /// only the first one proceeds. [release](Self::release) the ids if the broadcast fails.
+    pub(crate) fn reserve(&self, txids: &[&str], force: bool) -> Result<(), AlgonautError> {
+        let mut recent = self.lock()?;
+        self.forget_expired(&mut recent);
+        if !force {
+            if let Some(txid) = txids
+                .iter()
+                .find(|txid| recent.iter().any(|(recent, _)| recent == *txid))
+            {
+                return Err(AlgonautError::DuplicateBroadcast {
+                    txid: txid.to_string(),
+                });
+            }
+        }
+        let now = Instant::now();
+        for txid in txids {
+            if recent.len() == CAPACITY {
+                recent.pop_front();
+            }
+            recent.push_back((txid.to_string(), now));
+        }
+        Ok(())
+    }
+
+    /// Forgets the latest records of `txids`, e.g. after their broadcast failed.
+    pub(crate) fn release(&self, txids: &[&str]) -> Result<(), AlgonautError> {
+        let mut recent = self.lock()?;
+        for txid in txids {
+            if let Some(index) = recent.iter().rposition(|(recent, _)| recent == txid) {
+                recent.remove(index);
+            }
+        }
+        Ok(())
+    }
+
+    fn forget_expired(&self, recent: &mut VecDeque<(String, Instant)>) {
+        while matches!(recent.front(), Some((_, at)) if at.elapsed() >= self.window) {
+            recent.pop_front();
+        }
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, VecDeque<(String, Instant)>>, AlgonautError> {
+        self.txids
+            .lock()
+            .map_err(|e| AlgonautError::Internal(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forgets_after_window() {
+        let recent = RecentBroadcasts::new(Duration::from_millis(50));
+        recent.reserve(&["TX1"], false).unwrap();
+
+        assert!(recent.reserve(&["TX2", "TX1"], false).is_err());
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(recent.reserve(&["TX1"], false).is_ok());
+    }
+
+    #[test]
+    fn test_rejected_reservation_records_nothing() {
+        let recent = RecentBroadcasts::new(Duration::from_secs(60));
+        recent.reserve(&["TX1"], false).unwrap();
+
+        assert!(recent.reserve(&["TX2", "TX1"], false).is_err());
+        assert!(recent.reserve(&["TX2"], false).is_ok());
+        assert!(recent.reserve(&["TX1"], true).is_ok());
+    }
+
+    #[test]
+    fn test_release() {
+        let recent = RecentBroadcasts::new(Duration::from_secs(60));
+        recent.reserve(&["TX1", "TX2"], false).unwrap();
+
+        recent.release(&["TX1"]).unwrap();
+
+        assert!(recent.reserve(&["TX1"], false).is_ok());
+        assert!(recent.reserve(&["TX2"], false).is_err());
+    }
+
+    #[test]
+    fn test_forgets_oldest_over_capacity() {
+        let recent = RecentBroadcasts::new(Duration::from_secs(60));
+        let txids: Vec<String> = (0..=CAPACITY).map(|i| i.to_string()).collect();
+        recent
+            .reserve(&txids.iter().map(String::as_str).collect::<Vec<_>>(), false)
+            .unwrap();
+
+        assert!(recent.reserve(&["1"], false).is_err());
+        assert!(recent.reserve(&["0"], false).is_ok());
+    }
+}
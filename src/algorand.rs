@@ -0,0 +1,126 @@
+//! Configuration of the algod and indexer clients of an application in one place.
+
+use crate::algod::{v2::Algod, AlgodBuilder};
+use crate::error::AlgonautError;
+use crate::indexer::{v2::Indexer, IndexerCustomEndpointBuilder};
+use std::env;
+
+/// URLs and tokens of the algod and indexer nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgorandConfig {
+    pub algod_url: String,
+    pub algod_token: String,
+    pub indexer_url: String,
+    /// `None` for indexers that don't require authentication, e.g. the sandbox's.
+    pub indexer_token: Option<String>,
+}
+
+impl AlgorandConfig {
+    /// Reads the config from the `ALGOD_URL`, `ALGOD_TOKEN`, `INDEXER_URL` and (optional)
+    /// `INDEXER_TOKEN` environment variables.
+    pub fn from_env() -> Result<AlgorandConfig, AlgonautError> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    fn from_vars<F>(var: F) -> Result<AlgorandConfig, AlgonautError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        Ok(AlgorandConfig {
+            algod_url: var("ALGOD_URL").ok_or(AlgonautError::UnitializedUrl)?,
+            algod_token: var("ALGOD_TOKEN").ok_or(AlgonautError::UnitializedToken)?,
+            indexer_url: var("INDEXER_URL").ok_or(AlgonautError::UnitializedUrl)?,
+            indexer_token: var("INDEXER_TOKEN"),
+        })
+    }
+}
+
+/// The algod and indexer clients of an application.
+pub struct Algorand {
+    pub algod: Algod,
+    pub indexer: Indexer,
+}
+
+impl Algorand {
+    /// Builds both clients.
+    ///
+    /// Returns an error if a url or the algod token has an invalid format.
+    pub fn from_config(config: &AlgorandConfig) -> Result<Algorand, AlgonautError> {
+        let algod = AlgodBuilder::new()
+            .bind(&config.algod_url)
+            .auth(&config.algod_token)
+            .build_v2()?;
+        let headers = match &config.indexer_token {
+            Some(token) => vec![("X-Indexer-API-Token", token.as_str())],
+            None => vec![],
+        };
+        let indexer = IndexerCustomEndpointBuilder::new()
+            .bind(&config.indexer_url)
+            .headers(headers)
+            .build_v2()?;
+        Ok(Algorand { algod, indexer })
+    }
+
+    /// Builds both clients from the environment, see [AlgorandConfig::from_env].
+    pub fn from_env() -> Result<Algorand, AlgonautError> {
+        Self::from_config(&AlgorandConfig::from_env()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config() -> AlgorandConfig {
+        AlgorandConfig {
+            algod_url: "http://localhost:4001".to_owned(),
+            algod_token: "a".repeat(64),
+            indexer_url: "http://localhost:8980".to_owned(),
+            indexer_token: None,
+        }
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert!(Algorand::from_config(&config()).is_ok());
+        assert!(Algorand::from_config(&AlgorandConfig {
+            indexer_token: Some("token".to_owned()),
+            ..config()
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn test_from_config_rejects_invalid_url() {
+        let config = AlgorandConfig {
+            indexer_url: "not a url".to_owned(),
+            ..config()
+        };
+
+        assert!(Algorand::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_config_from_vars() {
+        let vars: HashMap<&str, String> = vec![
+            ("ALGOD_URL", config().algod_url),
+            ("ALGOD_TOKEN", config().algod_token),
+            ("INDEXER_URL", config().indexer_url),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            Ok(config()),
+            AlgorandConfig::from_vars(|name| vars.get(name).cloned())
+        );
+        assert_eq!(
+            Err(AlgonautError::UnitializedToken),
+            AlgorandConfig::from_vars(|name| vars
+                .get(name)
+                .filter(|_| name != "ALGOD_TOKEN")
+                .cloned())
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Composition of atomic groups of [ARC-4](https://github.com/algorandfoundation/ARCs/blob/main/ARCs/arc-0004.md)
+//! method calls and transactions.
+
+use crate::algod::v2::Algod;
+use crate::error::AlgonautError;
+use algonaut_core::{Address, SuggestedTransactionParams};
+use algonaut_transaction::abi::{decode_abi_return, AbiMethod, AbiValue};
+use algonaut_transaction::builder::CallApplication;
+use algonaut_transaction::signer::TransactionSigner;
+use algonaut_transaction::tx_group::TxGroup;
+use algonaut_transaction::{SignedTransaction, Transaction, TxnBuilder};
+
+struct ComposedTransaction<'a> {
+    transaction: Transaction,
+    signer: &'a dyn TransactionSigner,
+    method: Option<AbiMethod>,
+}
+
+/// Outcome of a method call of an executed group.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AbiMethodResult {
+    pub tx_id: String,
+    pub method: AbiMethod,
+    /// `None` for `void` methods.
+    pub return_value: Option<AbiValue>,
+}
+
+/// Outcome of an executed group.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExecuteResult {
+    pub confirmed_round: Option<u64>,
+    /// Ids of all the transactions of the group, in order.
+    pub tx_ids: Vec<String>,
+    /// Results of the method calls, in group order.
+    pub method_results: Vec<AbiMethodResult>,
+}
+
+/// Accumulates ABI method calls and transactions into an atomic group, which is signed,
+/// submitted and confirmed as a whole by [execute](Self::execute).
+#[derive(Default)]
+pub struct AtomicTransactionComposer<'a> {
+    transactions: Vec<ComposedTransaction<'a>>,
+}
+
+impl<'a> AtomicTransactionComposer<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a transaction, signed by `signer`. The transaction must not have a group id: it's
+    /// assigned when building the group.
+    pub fn add_transaction(
+        &mut self,
+        transaction: Transaction,
+        signer: &'a dyn TransactionSigner,
+    ) -> Result<&mut Self, AlgonautError> {
+        if transaction.group.is_some() {
+            return Err(AlgonautError::Transaction(
+                "transaction already has a group id".to_owned(),
+            ));
+        }
+        self.transactions.push(ComposedTransaction {
+            transaction,
+            signer,
+            method: None,
+        });
+        Ok(self)
+    }
+
+    /// Adds a (no-op) call of `method` of application `app_id` with `args`, sent and signed by
+    /// `signer`.
+    pub fn add_method_call(
+        &mut self,
+        params: &SuggestedTransactionParams,
+        sender: Address,
+        app_id: u64,
+        method: AbiMethod,
+        args: &[AbiValue],
+        signer: &'a dyn TransactionSigner,
+    ) -> Result<&mut Self, AlgonautError> {
+        let transaction = TxnBuilder::with(
+            params.clone(),
+            CallApplication::new(sender, app_id)
+                .app_arguments(method.app_arguments(args)?)
+                .build(),
+        )
+        .build();
+        self.transactions.push(ComposedTransaction {
+            transaction,
+            signer,
+            method: Some(method),
+        });
+        Ok(self)
+    }
+
+    /// Number of transactions in the group.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// The transactions of the group in the order they were added, with the group id assigned.
+    pub fn build_group(&self) -> Result<Vec<Transaction>, AlgonautError> {
+        let mut group: Vec<Transaction> = self
+            .transactions
+            .iter()
+            .map(|t| t.transaction.clone())
+            .collect();
+        TxGroup::assign_group_id(group.iter_mut().collect())?;
+        Ok(group)
+    }
+
+    /// Builds the group and signs each transaction with its signer.
+    pub fn sign(&self) -> Result<Vec<SignedTransaction>, AlgonautError> {
+        let group = self.build_group()?;
+        let mut signed = vec![];
+        for (index, composed) in self.transactions.iter().enumerate() {
+            signed.extend(composed.signer.sign(&group, &[index])?);
+        }
+        Ok(signed)
+    }
+
+    /// Signs and submits the group, waits until it's confirmed (see [Algod::wait_for_group]) and
+    /// decodes the return values of the method calls from the logs.
+    pub async fn execute(
+        &self,
+        algod: &Algod,
+        timeout_rounds: u64,
+    ) -> Result<ExecuteResult, AlgonautError> {
+        let signed = self.sign()?;
+        algod.broadcast_signed_transactions(&signed).await?;
+        let tx_ids: Vec<String> = signed.into_iter().map(|t| t.transaction_id).collect();
+        let confirmed = algod.wait_for_group(&tx_ids, timeout_rounds).await?;
+
+        let mut method_results = vec![];
+        for ((composed, pending), tx_id) in self.transactions.iter().zip(&confirmed).zip(&tx_ids) {
+            if let Some(method) = &composed.method {
+                method_results.push(AbiMethodResult {
+                    tx_id: tx_id.clone(),
+                    method: method.clone(),
+                    return_value: method
+                        .returns
+                        .as_ref()
+                        .map(|t| decode_abi_return(&pending.logs, t))
+                        .transpose()?,
+                });
+            }
+        }
+        Ok(ExecuteResult {
+            confirmed_round: confirmed.first().and_then(|t| t.confirmed_round),
+            tx_ids,
+            method_results,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_client::algod::v2::Client;
+    use algonaut_client::test_utils::{status_response, MockServer};
+    use algonaut_core::{MicroAlgos, Round};
+    use algonaut_crypto::HashDigest;
+    use algonaut_transaction::abi::RETURN_PREFIX;
+    use algonaut_transaction::account::Account;
+    use algonaut_transaction::error::TransactionError;
+    use algonaut_transaction::Pay;
+    use data_encoding::BASE64;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    /// Signs with an account, recording the requested indices.
+    struct RecordingSigner<'a> {
+        account: &'a Account,
+        indices: RefCell<Vec<usize>>,
+    }
+
+    impl<'a> TransactionSigner for RecordingSigner<'a> {
+        fn sign(
+            &self,
+            txns: &[Transaction],
+            indices: &[usize],
+        ) -> Result<Vec<SignedTransaction>, TransactionError> {
+            self.indices.borrow_mut().extend_from_slice(indices);
+            self.account.sign(txns, indices)
+        }
+    }
+
+    fn return_log(value: &[u8]) -> String {
+        BASE64.encode(&[&RETURN_PREFIX[..], value].concat())
+    }
+
+    #[tokio::test]
+    async fn test_execute_decodes_method_results() {
+        let account = Account::generate();
+        let add = AbiMethod::from_signature("add(uint64,uint64)uint64").unwrap();
+        let greet = AbiMethod::from_signature("greet(string)string").unwrap();
+        let mut composer = AtomicTransactionComposer::new();
+        composer
+            .add_method_call(
+                &SuggestedTransactionParams::testnet(Round(1)),
+                account.address(),
+                5,
+                add,
+                &[AbiValue::Uint(7), AbiValue::Uint(8)],
+                &account,
+            )
+            .unwrap()
+            .add_transaction(
+                TxnBuilder::with(
+                    SuggestedTransactionParams::testnet(Round(1)),
+                    Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+                )
+                .build(),
+                &account,
+            )
+            .unwrap()
+            .add_method_call(
+                &SuggestedTransactionParams::testnet(Round(1)),
+                account.address(),
+                5,
+                greet,
+                &[AbiValue::String("algonaut".to_owned())],
+                &account,
+            )
+            .unwrap();
+
+        let group = composer.build_group().unwrap();
+        assert_eq!(3, group.len());
+        assert!(group[0].group.is_some());
+        assert!(group.iter().all(|t| t.group == group[0].group));
+
+        let tx_ids: Vec<String> = group.iter().map(|t| t.id().unwrap()).collect();
+        let logs: HashMap<String, String> = vec![
+            (tx_ids[0].clone(), return_log(&15u64.to_be_bytes())),
+            (tx_ids[1].clone(), "".to_owned()),
+            (tx_ids[2].clone(), return_log(b"\0\x02hi")),
+        ]
+        .into_iter()
+        .collect();
+        let first_id = tx_ids[0].clone();
+        let server = MockServer::start_with(5, move |request| {
+            if request.starts_with("POST /v2/transactions ") {
+                let body = format!(r#"{{"txId": "{}"}}"#, first_id);
+                return ("200 OK", body);
+            }
+            if request.starts_with("GET /v2/status ") {
+                return ("200 OK", status_response(10));
+            }
+            let (tx_id, log) = logs
+                .iter()
+                .find(|(tx_id, _)| request.contains(&format!("/pending/{} ", tx_id)))
+                .unwrap();
+            let logs = if log.is_empty() {
+                "[]".to_owned()
+            } else {
+                format!(r#"["{}"]"#, log)
+            };
+            let body = format!(
+                r#"{{"confirmed-round": 11, "logs": {}, "pool-error": "", "txn": {{"txid": "{}"}}}}"#,
+                logs, tx_id
+            );
+            ("200 OK", body)
+        });
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let res = composer.execute(&algod, 5).await.unwrap();
+
+        assert_eq!(Some(11), res.confirmed_round);
+        assert_eq!(tx_ids, res.tx_ids);
+        assert_eq!(
+            vec![
+                (tx_ids[0].clone(), Some(AbiValue::Uint(15))),
+                (tx_ids[2].clone(), Some(AbiValue::String("hi".to_owned()))),
+            ],
+            res.method_results
+                .into_iter()
+                .map(|r| (r.tx_id, r.return_value))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(5, server.requests().len());
+    }
+
+    #[test]
+    fn test_sign_with_account_and_mock_signers() {
+        let account = Account::generate();
+        let mock = RecordingSigner {
+            account: &account,
+            indices: RefCell::new(vec![]),
+        };
+        let payment = |amount| {
+            TxnBuilder::with(
+                SuggestedTransactionParams::testnet(Round(1)),
+                Pay::new(account.address(), account.address(), MicroAlgos(amount)).build(),
+            )
+            .build()
+        };
+        let mut local = AtomicTransactionComposer::new();
+        local
+            .add_transaction(payment(1), &account)
+            .unwrap()
+            .add_transaction(payment(2), &account)
+            .unwrap();
+        let mut mixed = AtomicTransactionComposer::new();
+        mixed
+            .add_transaction(payment(1), &account)
+            .unwrap()
+            .add_transaction(payment(2), &mock)
+            .unwrap();
+
+        let signed = mixed.sign().unwrap();
+
+        assert_eq!(local.sign().unwrap(), signed);
+        assert_eq!(vec![1], *mock.indices.borrow());
+        assert_eq!(signed[0].transaction.group, signed[1].transaction.group);
+    }
+
+    #[test]
+    fn test_add_transaction_rejects_grouped() {
+        let account = Account::generate();
+        let mut transaction = TxnBuilder::with(
+            SuggestedTransactionParams::testnet(Round(1)),
+            Pay::new(account.address(), account.address(), MicroAlgos(1)).build(),
+        )
+        .build();
+        transaction.group = Some(HashDigest([2; 32]));
+
+        assert!(AtomicTransactionComposer::new()
+            .add_transaction(transaction, &account)
+            .is_err());
+    }
+}
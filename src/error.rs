@@ -1,3 +1,5 @@
+use algonaut_core::Round;
+use algonaut_crypto::HashDigest;
 use std::fmt::Debug;
 use thiserror::Error;
 
@@ -18,6 +20,45 @@ pub enum AlgonautError {
     /// Missing the authentication token for the REST API server.
     #[error("Set a token before calling build.")]
     UnitializedToken,
+    /// The node's files couldn't be read from its data directory.
+    #[error("Couldn't read the algod data directory: {0}")]
+    DataDir(String),
+    /// The transaction was built for a different network than the node's.
+    #[error("Transaction genesis hash: {transaction:?} doesn't match node genesis hash: {node:?}")]
+    GenesisMismatch {
+        transaction: HashDigest,
+        node: HashDigest,
+    },
+    /// Transaction encoding or decoding errors
+    #[error("Transaction error: {0}")]
+    Transaction(String),
+    /// The transaction to broadcast has no (or an incomplete) signature, see
+    /// [is_signed](algonaut_transaction::SignedTransaction::is_signed).
+    #[error("Transaction {txid} is not signed.")]
+    UnsignedTransaction { txid: String },
+    /// The transactions to broadcast together don't share a group id.
+    #[error("Transactions don't share a group id.")]
+    InconsistentGroup,
+    /// The transaction was already broadcast by this client, see
+    /// [idempotent_broadcasts](crate::algod::v2::Algod::idempotent_broadcasts).
+    #[error("Transaction {txid} was already broadcast.")]
+    DuplicateBroadcast { txid: String },
+    /// No response within the time given to the call.
+    #[error("Timeout waiting for the response.")]
+    Timeout,
+    /// The transaction wasn't confirmed within the given number of rounds.
+    #[error("Transaction not confirmed after {rounds} rounds.")]
+    ConfirmationTimeout { rounds: u64 },
+    /// The transaction is not in the block of the given round.
+    #[error("Transaction {txid} not found in round {round}.")]
+    TransactionNotFound { txid: String, round: Round },
+    /// The node removed the transaction from its pool.
+    #[error("Transaction {txid} rejected: {message}")]
+    TransactionRejected { txid: String, message: String },
+    /// The balance of an account reconstructed from its transactions is negative after the
+    /// round: the transactions don't account for all its balance changes.
+    #[error("Negative balance reconstructed after round {round}.")]
+    NegativeBalance { round: Round },
     /// HTTP calls errors
     #[error("http error: {0}")]
     Request(RequestError),
@@ -88,3 +129,9 @@ impl From<rmp_serde::encode::Error> for AlgonautError {
         AlgonautError::Internal(error.to_string())
     }
 }
+
+impl From<algonaut_transaction::error::TransactionError> for AlgonautError {
+    fn from(error: algonaut_transaction::error::TransactionError) -> Self {
+        AlgonautError::Transaction(error.to_string())
+    }
+}
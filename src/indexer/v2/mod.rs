@@ -1,12 +1,17 @@
 use algonaut_client::indexer::v2::Client;
-use algonaut_core::Round;
+use algonaut_core::{Address, MicroAlgos, Round};
 use algonaut_model::indexer::v2::{
     AccountInfoResponse, AccountResponse, AccountTransactionResponse, ApplicationInfoResponse,
     ApplicationResponse, AssetResponse, AssetTransactionResponse, AssetsInfoResponse,
-    BalancesResponse, Block, QueryAccount, QueryAccountInfo, QueryAccountTransaction,
-    QueryApplicationInfo, QueryApplications, QueryAssetTransaction, QueryAssets, QueryAssetsInfo,
-    QueryBalances, QueryTransaction, TransactionResponse,
+    BalancesResponse, Block, IndexerHealth, QueryAccount, QueryAccountInfo,
+    QueryAccountTransaction, QueryApplicationInfo, QueryApplications, QueryAssetTransaction,
+    QueryAssets, QueryAssetsInfo, QueryBalances, QueryTransaction, Transaction,
+    TransactionResponse,
 };
+use data_encoding::BASE64;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::future::Future;
 
 use crate::error::AlgonautError;
 
@@ -19,8 +24,10 @@ impl Indexer {
         Indexer { client }
     }
 
-    /// Returns Ok if healthy
-    pub async fn health(&self) -> Result<(), AlgonautError> {
+    /// Returns the indexer's health, including the last round it imported, if healthy.
+    ///
+    /// Compare the round with algod's (see [IndexerHealth::lag_rounds]) to detect stale data.
+    pub async fn health(&self) -> Result<IndexerHealth, AlgonautError> {
         Ok(self.client.health().await?)
     }
 
@@ -47,6 +54,46 @@ impl Indexer {
         Ok(self.client.account_transactions(id, query).await?)
     }
 
+    /// Balance of the account after each round in `[start, end]` in which it sent or received
+    /// MicroAlgos, in ascending round order.
+    ///
+    /// The balances are reconstructed backwards from the balance at `end`, applying the fees,
+    /// amounts, close amounts and rewards of the account's transactions.
+    pub async fn account_balance_history(
+        &self,
+        address: &Address,
+        start: Round,
+        end: Round,
+    ) -> Result<Vec<(Round, MicroAlgos)>, AlgonautError> {
+        let id = address.to_string();
+        let account = self
+            .client
+            .account_info(
+                &id,
+                &QueryAccountInfo {
+                    include_all: None,
+                    round: Some(end),
+                },
+            )
+            .await?
+            .account;
+
+        let id = &id;
+        let transactions = all_pages(|next| async move {
+            let query = QueryAccountTransaction {
+                min_round: Some(start),
+                max_round: Some(end),
+                next,
+                ..QueryAccountTransaction::default()
+            };
+            let page = self.client.account_transactions(id, &query).await?;
+            Ok((page.transactions, page.next_token))
+        })
+        .await?;
+
+        balance_history(id, MicroAlgos(account.amount), &transactions)
+    }
+
     /// Search for applications
     pub async fn applications(
         &self,
@@ -87,6 +134,41 @@ impl Indexer {
         Ok(self.client.asset_balances(id, query).await?)
     }
 
+    /// All the accounts holding asset `asset_id` as `(address, amount, frozen)`, fetching every
+    /// page of [asset_balances](Self::asset_balances).
+    ///
+    /// Optionally filtered by amounts greater than `currency_greater_than` and less than
+    /// `currency_less_than`.
+    pub async fn asset_holders(
+        &self,
+        asset_id: u64,
+        currency_greater_than: Option<u64>,
+        currency_less_than: Option<u64>,
+    ) -> Result<Vec<(Address, u64, bool)>, AlgonautError> {
+        let id = &asset_id.to_string();
+        let balances = all_pages(|next| async move {
+            let query = QueryBalances {
+                currency_greater_than,
+                currency_less_than,
+                next,
+                ..QueryBalances::default()
+            };
+            let page = self.client.asset_balances(id, &query).await?;
+            Ok((page.balances, page.next_token))
+        })
+        .await?;
+        balances
+            .into_iter()
+            .map(|holding| {
+                let address = holding
+                    .address
+                    .parse()
+                    .map_err(|e| AlgonautError::Internal(format!("Invalid address: {}", e)))?;
+                Ok((address, holding.amount, holding.is_frozen))
+            })
+            .collect()
+    }
+
     /// Lookup transactions for an asset.
     pub async fn asset_transactions(
         &self,
@@ -109,8 +191,385 @@ impl Indexer {
         Ok(self.client.transactions(query).await?)
     }
 
+    /// All the transactions whose note starts with `prefix` (e.g. a tag of an application),
+    /// fetching every page of [transactions](Self::transactions).
+    ///
+    /// Optionally limited to rounds from `min_round` to `max_round`.
+    pub async fn transactions_with_note_prefix(
+        &self,
+        prefix: &[u8],
+        min_round: Option<Round>,
+        max_round: Option<Round>,
+    ) -> Result<Vec<Transaction>, AlgonautError> {
+        let note_prefix = &BASE64.encode(prefix);
+        all_pages(|next| async move {
+            let query = QueryTransaction {
+                min_round,
+                max_round,
+                note_prefix: Some(note_prefix.clone()),
+                next,
+                ..QueryTransaction::default()
+            };
+            let page = self.client.transactions(&query).await?;
+            Ok((page.transactions, page.next_token))
+        })
+        .await
+    }
+
     /// Search for transactions.
     pub async fn transaction_info(&self, id: &str) -> Result<TransactionResponse, AlgonautError> {
         Ok(self.client.transaction_info(id).await?)
     }
 }
+
+/// Collects the items of all the pages of a query, fetching each page with the next token of the
+/// previous one (`None` for the first), until a page is empty or has no next token.
+async fn all_pages<T, F, Fut>(mut fetch_page: F) -> Result<Vec<T>, AlgonautError>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<String>), AlgonautError>>,
+{
+    let mut items = vec![];
+    let mut next = None;
+    loop {
+        let (page, next_token) = fetch_page(next).await?;
+        if page.is_empty() {
+            break;
+        }
+        items.extend(page);
+        match next_token {
+            Some(token) => next = Some(token),
+            None => break,
+        }
+    }
+    Ok(items)
+}
+
+/// Walks the balance back from `final_balance` through the (confirmed) transactions.
+///
+/// Fails if the balance after a round comes out negative: the transactions don't account for all
+/// the balance changes, e.g. because some are missing.
+fn balance_history(
+    address: &str,
+    final_balance: MicroAlgos,
+    transactions: &[Transaction],
+) -> Result<Vec<(Round, MicroAlgos)>, AlgonautError> {
+    let mut deltas: BTreeMap<Round, i128> = BTreeMap::new();
+    for t in transactions {
+        if let Some(round) = t.confirmed_round {
+            *deltas.entry(round).or_insert(0) += balance_delta(address, t);
+        }
+    }
+    let mut balance = final_balance.0 as i128;
+    let mut history = Vec::with_capacity(deltas.len());
+    for (round, delta) in deltas.into_iter().rev() {
+        let after_round =
+            u64::try_from(balance).map_err(|_| AlgonautError::NegativeBalance { round })?;
+        history.push((round, MicroAlgos(after_round)));
+        balance -= delta;
+    }
+    history.reverse();
+    Ok(history)
+}
+
+/// Change of the address' MicroAlgos caused by the transaction.
+fn balance_delta(address: &str, t: &Transaction) -> i128 {
+    let mut delta = 0;
+    if t.sender == address {
+        delta -= t.fee as i128;
+        delta += t.sender_rewards.unwrap_or(0) as i128;
+    }
+    if let Some(payment) = &t.payment_transaction {
+        let close_amount = payment.close_amount.map_or(0, |a| a.0) as i128;
+        if t.sender == address {
+            delta -= payment.amount.0 as i128 + close_amount;
+        }
+        if payment.receiver == address {
+            delta += payment.amount.0 as i128 + t.receiver_rewards.map_or(0, |r| r.0) as i128;
+        }
+        if payment.close_remainder_to.as_deref() == Some(address) {
+            delta += close_amount + t.close_rewards.map_or(0, |r| r.0) as i128;
+        }
+    }
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_client::test_utils::MockServer;
+
+    fn transaction(round: u64, sender: &Address, fee: u64, body: &str) -> String {
+        format!(
+            r#"{{
+                "confirmed-round": {},
+                "fee": {},
+                "first-valid": {},
+                "id": "TX{}",
+                "last-valid": {},
+                "sender": "{}",
+                "signature": {{}},
+                {}
+            }}"#,
+            round,
+            fee,
+            round - 1,
+            round,
+            round + 999,
+            sender.to_string(),
+            body
+        )
+    }
+
+    fn account(address: &Address, amount: u64) -> String {
+        format!(
+            r#"{{
+                "account": {{
+                    "address": "{}",
+                    "amount": {},
+                    "amount-without-pending-rewards": {},
+                    "pending-rewards": 0,
+                    "rewards": 0,
+                    "round": 20,
+                    "status": "Offline"
+                }},
+                "current-round": 20
+            }}"#,
+            address.to_string(),
+            amount,
+            amount
+        )
+    }
+
+    #[tokio::test]
+    async fn test_account_balance_history() {
+        let address = Address([1; 32]);
+        let other = Address([2; 32]);
+        let account = account(&address, 1_000_000);
+        let received = transaction(
+            10,
+            &other,
+            1000,
+            &format!(
+                r#""payment-transaction": {{"amount": 500000, "receiver": "{}"}}, "tx-type": "pay""#,
+                address.to_string()
+            ),
+        );
+        let sent = transaction(
+            15,
+            &address,
+            1000,
+            &format!(
+                r#""payment-transaction": {{"amount": 100000, "receiver": "{}"}}, "tx-type": "pay""#,
+                other.to_string()
+            ),
+        );
+        let asset_sent = transaction(
+            15,
+            &address,
+            1000,
+            &format!(
+                r#""asset-transfer-transaction": {{"amount": 5, "asset-id": 1, "receiver": "{}"}}, "tx-type": "axfer""#,
+                other.to_string()
+            ),
+        );
+        let transactions = format!(
+            r#"{{"current-round": 20, "transactions": [{}, {}, {}]}}"#,
+            asset_sent, sent, received
+        );
+        let server = MockServer::start(vec![("200 OK", account), ("200 OK", transactions)]);
+        let indexer = Indexer::new(Client::new(&server.url, vec![]).unwrap());
+
+        let history = indexer
+            .account_balance_history(&address, Round(5), Round(20))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                (Round(10), MicroAlgos(1_102_000)),
+                (Round(15), MicroAlgos(1_000_000))
+            ],
+            history
+        );
+        let requests = server.requests();
+        assert!(requests[0].contains("round=20"));
+        assert!(requests[1].contains("min-round=5"));
+        assert!(requests[1].contains("max-round=20"));
+    }
+
+    #[tokio::test]
+    async fn test_account_balance_history_rejects_negative_balance() {
+        let address = Address([1; 32]);
+        let other = Address([2; 32]);
+        // The account received more at round 15 than its balance at round 20: a transaction
+        // spending the difference is missing.
+        let received = transaction(
+            15,
+            &other,
+            1000,
+            &format!(
+                r#""payment-transaction": {{"amount": 500000, "receiver": "{}"}}, "tx-type": "pay""#,
+                address.to_string()
+            ),
+        );
+        let sent = transaction(
+            10,
+            &address,
+            1000,
+            &format!(
+                r#""payment-transaction": {{"amount": 1, "receiver": "{}"}}, "tx-type": "pay""#,
+                other.to_string()
+            ),
+        );
+        let transactions = format!(
+            r#"{{"current-round": 20, "transactions": [{}, {}]}}"#,
+            received, sent
+        );
+        let server = MockServer::start(vec![
+            ("200 OK", account(&address, 1_000)),
+            ("200 OK", transactions),
+        ]);
+        let indexer = Indexer::new(Client::new(&server.url, vec![]).unwrap());
+
+        let history = indexer
+            .account_balance_history(&address, Round(5), Round(20))
+            .await;
+
+        assert_eq!(
+            Err(AlgonautError::NegativeBalance { round: Round(10) }),
+            history
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let health = r#"{
+            "data": {"migration-required": false, "read-only-mode": false},
+            "db-available": true,
+            "is-migrating": false,
+            "message": "17843222",
+            "round": 17843222,
+            "version": "2.8.3"
+        }"#;
+        let server = MockServer::start(vec![("200 OK", health.to_owned())]);
+        let indexer = Indexer::new(Client::new(&server.url, vec![]).unwrap());
+
+        let health = indexer.health().await.unwrap();
+
+        assert_eq!(Round(17_843_222), health.round);
+        assert!(health.db_available);
+        assert!(!health.is_migrating);
+        assert_eq!(Some("2.8.3".to_owned()), health.version);
+        assert_eq!(5, health.lag_rounds(Round(17_843_227)));
+        assert_eq!(0, health.lag_rounds(Round(17_843_000)));
+        assert!(server.requests()[0].starts_with("GET /health "));
+    }
+
+    #[tokio::test]
+    async fn test_transactions_with_note_prefix_paginates() {
+        let sender = Address([1; 32]);
+        let payment = |round| {
+            transaction(
+                round,
+                &sender,
+                1000,
+                &format!(
+                    r#""note": "YWxnb25hdXQ6aGk=", "payment-transaction": {{"amount": 1, "receiver": "{}"}}, "tx-type": "pay""#,
+                    sender.to_string()
+                ),
+            )
+        };
+        let server = MockServer::start(vec![
+            (
+                "200 OK",
+                format!(
+                    r#"{{"current-round": 20, "next-token": "page2", "transactions": [{}, {}]}}"#,
+                    payment(10),
+                    payment(11)
+                ),
+            ),
+            (
+                "200 OK",
+                format!(
+                    r#"{{"current-round": 20, "transactions": [{}]}}"#,
+                    payment(12)
+                ),
+            ),
+        ]);
+        let indexer = Indexer::new(Client::new(&server.url, vec![]).unwrap());
+
+        let transactions = indexer
+            .transactions_with_note_prefix(b"algonaut:", Some(Round(5)), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec!["TX10", "TX11", "TX12"],
+            transactions
+                .iter()
+                .map(|t| t.id.as_str())
+                .collect::<Vec<_>>()
+        );
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /v2/transactions?"));
+        assert!(requests[0].contains("note-prefix=YWxnb25hdXQ6"));
+        assert!(requests[0].contains("min-round=5"));
+        assert!(!requests[0].contains("max-round"));
+        assert!(!requests[0].contains("next="));
+        assert!(requests[1].contains("note-prefix=YWxnb25hdXQ6"));
+        assert!(requests[1].contains("next=page2"));
+    }
+
+    fn balances_page(holders: &[(&Address, u64, bool)], next_token: Option<&str>) -> String {
+        format!(
+            r#"{{"balances": [{}], "current-round": 20{}}}"#,
+            holders
+                .iter()
+                .map(|(address, amount, frozen)| format!(
+                    r#"{{"address": "{}", "amount": {}, "is-frozen": {}}}"#,
+                    address.to_string(),
+                    amount,
+                    frozen
+                ))
+                .collect::<Vec<_>>()
+                .join(", "),
+            next_token.map_or("".to_owned(), |t| format!(r#", "next-token": "{}""#, t))
+        )
+    }
+
+    #[tokio::test]
+    async fn test_asset_holders_paginates() {
+        let first = Address([1; 32]);
+        let second = Address([2; 32]);
+        let third = Address([3; 32]);
+        let server = MockServer::start(vec![
+            (
+                "200 OK",
+                balances_page(&[(&first, 100, false), (&second, 200, true)], Some("page2")),
+            ),
+            ("200 OK", balances_page(&[(&third, 300, false)], None)),
+        ]);
+        let indexer = Indexer::new(Client::new(&server.url, vec![]).unwrap());
+
+        let holders = indexer
+            .asset_holders(5, Some(50), Some(1000))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                (first, 100, false),
+                (second, 200, true),
+                (third, 300, false)
+            ],
+            holders
+        );
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /v2/assets/5/balances?"));
+        assert!(requests[0].contains("currency-greater-than=50"));
+        assert!(requests[0].contains("currency-less-than=1000"));
+        assert!(!requests[0].contains("next="));
+        assert!(requests[1].contains("next=page2"));
+    }
+}
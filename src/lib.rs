@@ -23,6 +23,10 @@ pub use algonaut_model as model;
 pub use algonaut_transaction as transaction;
 
 pub mod algod;
+pub mod algorand;
+pub mod atomic_transaction_composer;
 pub mod error;
 pub mod indexer;
 pub mod kmd;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
@@ -0,0 +1,88 @@
+//! Utilities to set up tests against a network, e.g. a sandbox private network.
+//!
+//! Requires the `testing` feature.
+
+use crate::algod::v2::Algod;
+use crate::error::AlgonautError;
+use algonaut_core::MicroAlgos;
+use algonaut_transaction::account::Account;
+use algonaut_transaction::{Pay, TxnBuilder};
+
+/// Rounds to wait for the funding transaction of [fund_from] to be confirmed.
+const FUNDING_TIMEOUT_ROUNDS: u64 = 10;
+
+/// Generates a new account, funds it with `amount` from `dispenser` and waits until the payment
+/// is confirmed.
+pub async fn fund_from(
+    algod: &Algod,
+    dispenser: &Account,
+    amount: MicroAlgos,
+) -> Result<Account, AlgonautError> {
+    let account = Account::generate();
+    let params = algod.suggested_transaction_params().await?;
+    let payment = TxnBuilder::with(
+        params,
+        Pay::new(dispenser.address(), account.address(), amount).build(),
+    )
+    .build();
+    let signed = dispenser.sign_transaction(&payment)?;
+    algod.broadcast_signed_transaction(&signed).await?;
+    algod
+        .wait_for_confirmation(&signed.transaction_id, FUNDING_TIMEOUT_ROUNDS)
+        .await?;
+    Ok(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use algonaut_client::algod::v2::Client;
+    use algonaut_client::test_utils::{
+        params_response, pending_response, status_response, MockServer,
+    };
+    use algonaut_core::{Round, SuggestedTransactionParams};
+
+    #[tokio::test]
+    async fn test_fund_from() {
+        let dispenser = Account::generate();
+        let params = SuggestedTransactionParams::testnet(Round(100));
+        let server = MockServer::start(vec![
+            (
+                "200 OK",
+                params_response(
+                    "testnet-v1.0",
+                    &data_encoding::BASE64.encode(&params.genesis_hash.0),
+                    100,
+                ),
+            ),
+            ("200 OK", r#"{"txId": "ignored"}"#.to_owned()),
+            ("200 OK", status_response(101)),
+            ("200 OK", pending_response(Some(102), "")),
+        ]);
+        let algod = Algod::new(Client::new(&server.url, vec![]).unwrap());
+
+        let account = fund_from(&algod, &dispenser, MicroAlgos(5_000_000))
+            .await
+            .unwrap();
+
+        let expected_txid = TxnBuilder::with(
+            params,
+            Pay::new(
+                dispenser.address(),
+                account.address(),
+                MicroAlgos(5_000_000),
+            )
+            .build(),
+        )
+        .build()
+        .id()
+        .unwrap();
+        let requests = server.requests();
+        assert_eq!(4, requests.len());
+        assert!(requests[1].starts_with("POST /v2/transactions "));
+        assert!(
+            requests[3].starts_with(&format!("GET /v2/transactions/pending/{} ", expected_txid))
+        );
+        assert_ne!(dispenser.address(), account.address());
+    }
+}